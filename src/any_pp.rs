@@ -0,0 +1,753 @@
+#[cfg(any(feature = "fruits-ppv1", feature = "catch-2020"))]
+use rosu_pp::catch::CatchPerformanceAttributes;
+use rosu_pp::Beatmap;
+
+use crate::{
+    custom::{self, CustomScore},
+    mods::DifficultyAdjust,
+    unranked::{AutopilotMode, RelaxMode},
+    util::mode::check_mode,
+    version::any_attributes,
+    AnyDifficultyAttributes, Error, IfFcAttributes, ScoreState, Version,
+};
+#[cfg(feature = "store")]
+use crate::{AnyAttributeStore, AnyStars};
+
+// The accuracy is set last so that it only generates the remaining hits
+macro_rules! calculate {
+    (
+        $self:ident, $finish:ident, $variant:ident, $calculator:path
+        $(, $setter:ident: $value:expr )* $(; $toggle:ident: $enabled:expr )?
+    ) => {{
+        let mut calculator = <$calculator>::new($self.map)
+            .mods($self.mods)
+            .allow_unranked($self.allow_unranked);
+
+        if let Some(AnyDifficultyAttributes::$variant(attrs)) = $self.stored_attributes()? {
+            calculator = calculator.attributes(attrs);
+        }
+
+        $(
+            if let Some(value) = $value {
+                calculator = calculator.$setter(value);
+            }
+        )*
+
+        $(
+            if $enabled {
+                calculator = calculator.$toggle();
+            }
+        )?
+
+        $finish!($variant, calculator)
+    }};
+}
+
+macro_rules! osu {
+    ($self:ident, $finish:ident, $variant:ident, $module:ident, $acc:ident) => {
+        calculate!(
+            $self,
+            $finish,
+            $variant,
+            crate::$module::OsuPP,
+            combo: $self.combo,
+            n300: $self.n300,
+            n100: $self.n100,
+            n50: $self.n50,
+            misses: $self.n_misses,
+            passed_objects: $self.passed_objects,
+            difficulty_adjust: Some($self.difficulty_adjust),
+            relax: $self.relax.clone(),
+            autopilot: $self.autopilot.clone(),
+            accuracy: $acc;
+            estimate_hitresults: $self.estimate_hitresults
+        )
+    };
+}
+
+macro_rules! taiko {
+    ($self:ident, $finish:ident, $variant:ident, $module:ident, $acc:ident) => {
+        calculate!(
+            $self,
+            $finish,
+            $variant,
+            crate::$module::TaikoPP,
+            combo: $self.combo,
+            n300: $self.n300,
+            n100: $self.n100,
+            misses: $self.n_misses,
+            passed_objects: $self.passed_objects,
+            accuracy: $acc
+        )
+    };
+}
+
+macro_rules! catch {
+    ($self:ident, $finish:ident, $variant:ident, $calculator:path, $acc:ident) => {
+        calculate!(
+            $self,
+            $finish,
+            $variant,
+            $calculator,
+            combo: $self.combo,
+            fruits: $self.n300,
+            droplets: $self.n100,
+            tiny_droplets: $self.n50,
+            tiny_droplet_misses: $self.n_katu,
+            misses: $self.n_misses,
+            passed_objects: $self.passed_objects,
+            accuracy: $acc
+        )
+    };
+}
+
+/// Pass the score of an [`AnyPP`] on to the calculator of its version and
+/// let the `$finish` macro calculate the attributes of the variant.
+macro_rules! dispatch {
+    ($self:ident, $finish:ident) => {{
+        let acc = $self.acc;
+        let acc_f32 = $self.acc.map(|acc| acc as f32);
+
+        match $self.version {
+            #[cfg(feature = "osu-2012-tp")]
+            Version::Osu2012Tp => osu!($self, $finish, Osu2012Tp, osu_2012_tp, acc_f32),
+            #[cfg(feature = "osu-2014-february")]
+            Version::Osu2014February => {
+                osu!($self, $finish, Osu2014February, osu_2014_february, acc_f32)
+            }
+            #[cfg(feature = "osu-2014-may")]
+            Version::Osu2014May => osu!($self, $finish, Osu2014May, osu_2014_may, acc_f32),
+            #[cfg(feature = "osu-2014-july")]
+            Version::Osu2014July => osu!($self, $finish, Osu2014July, osu_2014_july, acc_f32),
+            #[cfg(feature = "osu-2015-february")]
+            Version::Osu2015February => {
+                osu!($self, $finish, Osu2015February, osu_2015_february, acc_f32)
+            }
+            #[cfg(feature = "osu-2015-april")]
+            Version::Osu2015April => osu!($self, $finish, Osu2015April, osu_2015_april, acc_f32),
+            #[cfg(feature = "osu-2018")]
+            Version::Osu2018 => osu!($self, $finish, Osu2018, osu_2018, acc_f32),
+            #[cfg(feature = "osu-2019")]
+            Version::Osu2019 => osu!($self, $finish, Osu2019, osu_2019, acc_f32),
+            #[cfg(feature = "osu-2021-january")]
+            Version::Osu2021January => {
+                osu!($self, $finish, Osu2021January, osu_2021_january, acc_f32)
+            }
+            #[cfg(feature = "osu-2021-july")]
+            Version::Osu2021July => osu!($self, $finish, Osu2021July, osu_2021_july, acc_f32),
+            #[cfg(feature = "osu-2021-november")]
+            Version::Osu2021November => {
+                osu!($self, $finish, Osu2021November, osu_2021_november, acc)
+            }
+            #[cfg(feature = "osu-2022-september")]
+            Version::Osu2022September => {
+                osu!($self, $finish, Osu2022September, osu_2022_september, acc)
+            }
+            #[cfg(feature = "taiko-ppv1")]
+            Version::TaikoPpv1 => taiko!($self, $finish, TaikoPpv1, taiko_ppv1, acc_f32),
+            #[cfg(feature = "taiko-2020")]
+            Version::Taiko2020 => taiko!($self, $finish, Taiko2020, taiko_2020, acc),
+            #[cfg(feature = "taiko-2022")]
+            Version::Taiko2022 => taiko!($self, $finish, Taiko2022, taiko_2022, acc),
+            #[cfg(feature = "mania-ppv1")]
+            Version::ManiaPpv1 => calculate!(
+                $self,
+                $finish,
+                ManiaPpv1,
+                crate::mania_ppv1::ManiaPP,
+                score: $self.score,
+                passed_objects: $self.passed_objects,
+                state: $self.mania_hits(),
+                accuracy: acc_f32
+            ),
+            #[cfg(feature = "mania-2018")]
+            Version::Mania2018 => calculate!(
+                $self,
+                $finish,
+                Mania2018,
+                crate::mania_2018::ManiaPP,
+                score: $self.score,
+                passed_objects: $self.passed_objects
+            ),
+            #[cfg(feature = "mania-2022")]
+            Version::Mania2022 => calculate!(
+                $self,
+                $finish,
+                Mania2022,
+                crate::mania_2022::ManiaPP,
+                n320: $self.n_geki,
+                n300: $self.n300,
+                n200: $self.n_katu,
+                n100: $self.n100,
+                n50: $self.n50,
+                misses: $self.n_misses,
+                passed_objects: $self.passed_objects,
+                accuracy: acc
+            ),
+            #[cfg(feature = "fruits-ppv1")]
+            Version::FruitsPpv1 => {
+                catch!($self, $finish, FruitsPpv1, crate::fruits_ppv1::FruitsPP, acc_f32)
+            }
+            #[cfg(feature = "catch-2020")]
+            Version::Catch2020 => {
+                catch!($self, $finish, Catch2020, crate::catch_2020::CatchPP, acc_f32)
+            }
+        }
+    }};
+}
+
+/// Performance calculator for any [`Version`].
+///
+/// The score is specified once and passed along to the calculator of the version.
+/// Values that the version does not consider are ignored, e.g. the combo in
+/// osu!mania or the amount of 50s in osu!taiko.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "osu-2015-april")]
+/// # {
+/// use rosu_pp::Beatmap;
+/// use rosu_pp_older::{AnyPP, Version};
+///
+/// # /*
+/// let map: Beatmap = ...
+/// # */
+/// # let map = Beatmap::default();
+///
+/// let attrs = AnyPP::new(&map, Version::Osu2015April)
+///     .mods(8 + 64) // HDDT
+///     .combo(1234)
+///     .misses(1)
+///     .accuracy(98.5)
+///     .calculate()
+///     .unwrap();
+///
+/// println!("PP: {} | Stars: {}", attrs.pp(), attrs.stars());
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+#[allow(clippy::upper_case_acronyms)]
+pub struct AnyPP<'map> {
+    map: &'map Beatmap,
+    version: Version,
+    mods: u32,
+    combo: Option<usize>,
+    acc: Option<f64>,
+    score: Option<u32>,
+    n300: Option<usize>,
+    n100: Option<usize>,
+    n50: Option<usize>,
+    n_geki: Option<usize>,
+    n_katu: Option<usize>,
+    n_misses: Option<usize>,
+    passed_objects: Option<usize>,
+    difficulty_adjust: DifficultyAdjust,
+    estimate_hitresults: bool,
+    allow_unranked: bool,
+    relax: Option<RelaxMode>,
+    autopilot: Option<AutopilotMode>,
+    #[cfg(feature = "store")]
+    store: Option<(&'map AnyAttributeStore, u64)>,
+}
+
+impl<'map> AnyPP<'map> {
+    /// Create a new performance calculator of the given version.
+    #[inline]
+    pub fn new(map: &'map Beatmap, version: Version) -> Self {
+        Self {
+            map,
+            version,
+            mods: 0,
+            combo: None,
+            acc: None,
+            score: None,
+            n300: None,
+            n100: None,
+            n50: None,
+            n_geki: None,
+            n_katu: None,
+            n_misses: None,
+            passed_objects: None,
+            difficulty_adjust: DifficultyAdjust::default(),
+            estimate_hitresults: false,
+            allow_unranked: false,
+            relax: None,
+            autopilot: None,
+            #[cfg(feature = "store")]
+            store: None,
+        }
+    }
+
+    /// Specify mods through their bit values.
+    ///
+    /// See [https://github.com/ppy/osu-api/wiki#mods](https://github.com/ppy/osu-api/wiki#mods)
+    #[inline]
+    pub fn mods(mut self, mods: u32) -> Self {
+        self.mods = mods;
+
+        self
+    }
+
+    /// Specify mods through their acronyms, e.g. `"HDDT"`.
+    ///
+    /// Returns an error for unknown acronyms and for mods
+    /// that did not exist yet at the time of the version.
+    #[inline]
+    pub fn mods_str(self, acronyms: &str) -> Result<Self, Error> {
+        let version = self.version;
+
+        version.parse_mods(acronyms).map(|mods| self.mods(mods))
+    }
+
+    /// Specify the max combo of the play.
+    #[inline]
+    pub fn combo(mut self, combo: usize) -> Self {
+        self.combo = Some(combo);
+
+        self
+    }
+
+    /// Specify the accuracy of a play between 0 and 100.
+    /// The amount of hits that are not specified is generated based on the accuracy.
+    #[inline]
+    pub fn accuracy(mut self, acc: f64) -> Self {
+        self.acc = Some(acc);
+
+        self
+    }
+
+    /// Specify the score of a play, only considered by the score-based osu!mania versions.
+    #[inline]
+    pub fn score(mut self, score: u32) -> Self {
+        self.score = Some(score);
+
+        self
+    }
+
+    /// Specify the amount of 300s of a play, i.e. fruits in osu!catch.
+    #[inline]
+    pub fn n300(mut self, n300: usize) -> Self {
+        self.n300 = Some(n300);
+
+        self
+    }
+
+    /// Specify the amount of 100s of a play, i.e. droplets in osu!catch.
+    #[inline]
+    pub fn n100(mut self, n100: usize) -> Self {
+        self.n100 = Some(n100);
+
+        self
+    }
+
+    /// Specify the amount of 50s of a play, i.e. tiny droplets in osu!catch.
+    #[inline]
+    pub fn n50(mut self, n50: usize) -> Self {
+        self.n50 = Some(n50);
+
+        self
+    }
+
+    /// Specify the amount of gekis of a play, i.e. 320s in osu!mania.
+    ///
+    /// Ignored by osu!standard, osu!taiko, and osu!catch.
+    #[inline]
+    pub fn n_geki(mut self, n_geki: usize) -> Self {
+        self.n_geki = Some(n_geki);
+
+        self
+    }
+
+    /// Specify the amount of katus of a play,
+    /// i.e. 200s in osu!mania or tiny droplet misses in osu!catch.
+    ///
+    /// Ignored by osu!standard and osu!taiko.
+    #[inline]
+    pub fn n_katu(mut self, n_katu: usize) -> Self {
+        self.n_katu = Some(n_katu);
+
+        self
+    }
+
+    /// Specify the amount of misses of a play.
+    #[inline]
+    pub fn misses(mut self, n_misses: usize) -> Self {
+        self.n_misses = Some(n_misses);
+
+        self
+    }
+
+    /// Provide the hit results through a [`ScoreState`].
+    ///
+    /// See [`ScoreState`] for how its fields are interpreted by each mode.
+    #[inline]
+    pub fn state(mut self, state: impl Into<ScoreState>) -> Self {
+        let ScoreState {
+            max_combo,
+            n300,
+            n100,
+            n50,
+            n_misses,
+            n_geki,
+            n_katu,
+        } = state.into();
+
+        self.combo = Some(max_combo);
+        self.n300 = Some(n300);
+        self.n100 = Some(n100);
+        self.n50 = Some(n50);
+        self.n_geki = Some(n_geki);
+        self.n_katu = Some(n_katu);
+        self.n_misses = Some(n_misses);
+
+        self
+    }
+
+    /// Provide the mods, the total score, and the hit results of a score from the osu! api.
+    #[cfg(feature = "rosu-v2")]
+    #[inline]
+    pub fn rosu_score(self, score: &rosu_v2::prelude::Score) -> Self {
+        self.mods(score.mods.bits()).score(score.score).state(score)
+    }
+
+    /// Amount of passed objects for partial plays, e.g. a fail.
+    #[inline]
+    pub fn passed_objects(mut self, passed_objects: usize) -> Self {
+        self.passed_objects = Some(passed_objects);
+
+        self
+    }
+
+    /// Specify the values of lazer's Difficulty Adjust mod.
+    ///
+    /// The values replace the map's base values before HR, EZ, and the clock rate are applied.
+    /// Only considered by osu!standard versions.
+    #[inline]
+    pub fn difficulty_adjust(mut self, difficulty_adjust: DifficultyAdjust) -> Self {
+        self.difficulty_adjust = difficulty_adjust;
+
+        self
+    }
+
+    /// Override the map's approach rate, see [`difficulty_adjust`](Self::difficulty_adjust).
+    #[inline]
+    pub fn ar(mut self, ar: f32) -> Self {
+        self.difficulty_adjust.ar = Some(ar);
+
+        self
+    }
+
+    /// Override the map's circle size, see [`difficulty_adjust`](Self::difficulty_adjust).
+    #[inline]
+    pub fn cs(mut self, cs: f32) -> Self {
+        self.difficulty_adjust.cs = Some(cs);
+
+        self
+    }
+
+    /// Override the map's overall difficulty, see [`difficulty_adjust`](Self::difficulty_adjust).
+    #[inline]
+    pub fn od(mut self, od: f32) -> Self {
+        self.difficulty_adjust.od = Some(od);
+
+        self
+    }
+
+    /// Override the map's drain rate, see [`difficulty_adjust`](Self::difficulty_adjust).
+    #[inline]
+    pub fn hp(mut self, hp: f32) -> Self {
+        self.difficulty_adjust.hp = Some(hp);
+
+        self
+    }
+
+    /// Estimate the hitresults from the combo and the misses alone.
+    ///
+    /// Only considered by osu!standard versions, see `OsuPP::estimate_hitresults`
+    /// of the version modules for the heuristic.
+    #[inline]
+    pub fn estimate_hitresults(mut self) -> Self {
+        self.estimate_hitresults = true;
+
+        self
+    }
+
+    /// Specify whether mods that bancho never awarded pp for should be accepted.
+    ///
+    /// Useful for private servers, defaults to `false`.
+    #[inline]
+    pub fn allow_unranked(mut self, allow_unranked: bool) -> Self {
+        self.allow_unranked = allow_unranked;
+
+        self
+    }
+
+    /// Specify how the Relax mod should be handled, see [`RelaxMode`].
+    ///
+    /// Only osu!standard versions consider this.
+    #[inline]
+    pub fn relax(mut self, relax: RelaxMode) -> Self {
+        self.relax = Some(relax);
+
+        self
+    }
+
+    /// Specify how the Autopilot mod should be handled, see [`AutopilotMode`].
+    ///
+    /// Only osu!standard versions consider this.
+    #[inline]
+    pub fn autopilot(mut self, autopilot: AutopilotMode) -> Self {
+        self.autopilot = Some(autopilot);
+
+        self
+    }
+
+    /// Reuse the difficulty attributes of the store or store them after calculating them,
+    /// see [`AnyStars::store`].
+    ///
+    /// Only available with the `store` feature.
+    #[cfg(feature = "store")]
+    #[inline]
+    pub fn store(mut self, store: &'map AnyAttributeStore, map: u64) -> Self {
+        self.store = Some((store, map));
+
+        self
+    }
+
+    /// Calculate all performance related values, including pp and stars.
+    ///
+    /// Returns an error if the map can not be calculated by the version
+    /// or if the mods are unranked.
+    pub fn calculate(self) -> Result<AnyPerformanceAttributes, Error> {
+        macro_rules! finish {
+            ($variant:ident, $calculator:ident) => {
+                AnyPerformanceAttributes::$variant($calculator.try_calculate()?)
+            };
+        }
+
+        Ok(dispatch!(self, finish))
+    }
+
+    /// Calculate the performance of the score and of its full combo.
+    ///
+    /// The pp calculator of the version derives the full combo from the score, see
+    /// e.g. `OsuPP::calculate_if_fc`. Generally, the misses are counted as 300s, the
+    /// combo is the map's max combo, and a specified accuracy is kept.
+    pub fn calculate_if_fc(self) -> Result<AnyIfFcAttributes, Error> {
+        macro_rules! finish {
+            ($variant:ident, $calculator:ident) => {
+                $calculator
+                    .try_calculate_if_fc()?
+                    .map(AnyPerformanceAttributes::$variant)
+            };
+        }
+
+        Ok(dispatch!(self, finish))
+    }
+
+    /// Calculate the pp with the registered custom version of the given name
+    /// instead, see [`custom`](crate::custom).
+    ///
+    /// The custom version is looked up within the mode of the version and receives the
+    /// mods, the combo, and the hit results. Hits that are not specified count as 300s,
+    /// the accuracy is not passed on.
+    ///
+    /// Returns [`Error::UnknownVersion`] if no such custom version is registered.
+    pub fn calculate_custom(self, name: &str) -> Result<f64, Error> {
+        let mode = self.version.mode();
+        check_mode(self.map.mode, mode)?;
+
+        let custom = custom::custom_version(mode, name)
+            .ok_or_else(|| Error::UnknownVersion(name.to_owned()))?;
+
+        let n_objects = self.passed_objects.unwrap_or(self.map.hit_objects.len());
+        let n100 = self.n100.unwrap_or(0);
+        let n50 = self.n50.unwrap_or(0);
+        let n_misses = self.n_misses.unwrap_or(0);
+
+        let score = CustomScore {
+            max_combo: self.combo,
+            n300: self
+                .n300
+                .unwrap_or_else(|| n_objects.saturating_sub(n100 + n50 + n_misses)),
+            n100,
+            n50,
+            n_misses,
+        };
+
+        Ok(custom.pp(self.map, self.mods, score))
+    }
+
+    /// The difficulty attributes of the store, `None` if there is no store or if the
+    /// score bypasses it.
+    #[cfg(feature = "store")]
+    fn stored_attributes(&self) -> Result<Option<AnyDifficultyAttributes>, Error> {
+        let (store, map) = match self.store {
+            Some(store) if self.passed_objects.is_none() && self.difficulty_adjust.is_empty() => {
+                store
+            }
+            _ => return Ok(None),
+        };
+
+        AnyStars::new(self.map)
+            .version(self.version)
+            .mods(self.mods)
+            .store(store, map)
+            .calculate()
+            .map(Some)
+    }
+
+    #[cfg(not(feature = "store"))]
+    #[inline]
+    fn stored_attributes(&self) -> Result<Option<AnyDifficultyAttributes>, Error> {
+        Ok(None)
+    }
+
+    /// The hit results for versions that only derive the accuracy from them,
+    /// `None` if no hit result was specified.
+    #[cfg(feature = "mania-ppv1")]
+    fn mania_hits(&self) -> Option<ScoreState> {
+        let hits = [
+            self.n_geki,
+            self.n300,
+            self.n_katu,
+            self.n100,
+            self.n50,
+            self.n_misses,
+        ];
+
+        hits.iter().any(Option::is_some).then(|| ScoreState {
+            n_geki: self.n_geki.unwrap_or(0),
+            n300: self.n300.unwrap_or(0),
+            n_katu: self.n_katu.unwrap_or(0),
+            n100: self.n100.unwrap_or(0),
+            n50: self.n50.unwrap_or(0),
+            n_misses: self.n_misses.unwrap_or(0),
+            ..Default::default()
+        })
+    }
+}
+
+/// The performance attributes of any version, see [`AnyPP`].
+#[derive(Clone, Debug)]
+pub enum AnyPerformanceAttributes {
+    #[cfg(feature = "osu-2012-tp")]
+    Osu2012Tp(crate::osu_2012_tp::OsuPerformanceAttributes),
+    #[cfg(feature = "osu-2014-february")]
+    Osu2014February(crate::osu_2014_february::OsuPerformanceAttributes),
+    #[cfg(feature = "osu-2014-may")]
+    Osu2014May(crate::osu_2014_may::OsuPerformanceAttributes),
+    #[cfg(feature = "osu-2014-july")]
+    Osu2014July(crate::osu_2014_july::OsuPerformanceAttributes),
+    #[cfg(feature = "osu-2015-february")]
+    Osu2015February(crate::osu_2015_february::OsuPerformanceAttributes),
+    #[cfg(feature = "osu-2015-april")]
+    Osu2015April(crate::osu_2015_april::OsuPerformanceAttributes),
+    #[cfg(feature = "osu-2018")]
+    Osu2018(crate::osu_2018::OsuPerformanceAttributes),
+    #[cfg(feature = "osu-2019")]
+    Osu2019(crate::osu_2019::OsuPerformanceAttributes),
+    #[cfg(feature = "osu-2021-january")]
+    Osu2021January(crate::osu_2021_january::OsuPerformanceAttributes),
+    #[cfg(feature = "osu-2021-july")]
+    Osu2021July(crate::osu_2021_july::OsuPerformanceAttributes),
+    #[cfg(feature = "osu-2021-november")]
+    Osu2021November(crate::osu_2021_november::OsuPerformanceAttributes),
+    #[cfg(feature = "osu-2022-september")]
+    Osu2022September(crate::osu_2022_september::OsuPerformanceAttributes),
+    #[cfg(feature = "taiko-ppv1")]
+    TaikoPpv1(crate::taiko_ppv1::TaikoPerformanceAttributes),
+    #[cfg(feature = "taiko-2020")]
+    Taiko2020(crate::taiko_2020::TaikoPerformanceAttributes),
+    #[cfg(feature = "taiko-2022")]
+    Taiko2022(crate::taiko_2022::TaikoPerformanceAttributes),
+    #[cfg(feature = "mania-ppv1")]
+    ManiaPpv1(crate::mania_ppv1::ManiaPerformanceAttributes),
+    #[cfg(feature = "mania-2018")]
+    Mania2018(crate::mania_2018::ManiaPerformanceAttributes),
+    #[cfg(feature = "mania-2022")]
+    Mania2022(crate::mania_2022::ManiaPerformanceAttributes),
+    #[cfg(feature = "fruits-ppv1")]
+    FruitsPpv1(CatchPerformanceAttributes),
+    #[cfg(feature = "catch-2020")]
+    Catch2020(CatchPerformanceAttributes),
+}
+
+/// The performance of a score next to the performance of its full combo,
+/// see [`AnyPP::calculate_if_fc`].
+pub type AnyIfFcAttributes = IfFcAttributes<AnyPerformanceAttributes>;
+
+impl AnyPerformanceAttributes {
+    /// The version that calculated the attributes.
+    pub fn version(&self) -> Version {
+        any_attributes!(self => version)
+    }
+
+    /// The pp of the score.
+    #[inline]
+    pub fn pp(&self) -> f64 {
+        any_attributes!(self, attrs => attrs.pp)
+    }
+
+    /// The star rating of the map.
+    #[inline]
+    pub fn stars(&self) -> f64 {
+        any_attributes!(self, attrs => attrs.difficulty.stars)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[cfg(feature = "mania-2022")]
+    #[test]
+    fn unsupported_mode() {
+        use rosu_pp::GameMode;
+
+        let map = Beatmap {
+            mode: GameMode::Taiko,
+            ..Default::default()
+        };
+
+        let err = AnyPP::new(&map, Version::Mania2022)
+            .calculate()
+            .unwrap_err();
+
+        assert_eq!(
+            err,
+            Error::UnsupportedMode {
+                version: GameMode::Mania,
+                map: GameMode::Taiko,
+            }
+        );
+    }
+
+    #[cfg(feature = "osu-2012-tp")]
+    #[test]
+    fn tp_pp() {
+        let attrs = AnyPP::new(&Beatmap::default(), Version::Osu2012Tp)
+            .calculate()
+            .unwrap();
+
+        assert_eq!(attrs.version(), Version::Osu2012Tp);
+    }
+
+    #[cfg(feature = "osu-2019")]
+    #[test]
+    fn autopilot() {
+        use crate::util::mods::AUTOPILOT;
+
+        let map = Beatmap::default();
+        let calculator = AnyPP::new(&map, Version::Osu2019).mods(AUTOPILOT);
+
+        assert_eq!(
+            calculator.clone().calculate().unwrap_err(),
+            Error::UnrankedMods(AUTOPILOT)
+        );
+
+        let attrs = calculator.autopilot(AutopilotMode::NoAim).calculate();
+        assert!(attrs.is_ok());
+    }
+}
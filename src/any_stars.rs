@@ -0,0 +1,414 @@
+use rosu_pp::{Beatmap, GameMode};
+
+use crate::{
+    custom,
+    mods::{self, DifficultyAdjust},
+    util::mode::check_mode,
+    AnyDifficultyAttributes, Error, Version,
+};
+#[cfg(feature = "store")]
+use crate::{AnyAttributeStore, AttributeKey};
+
+/// Difficulty calculator that picks the version based on the mode of the map.
+///
+/// Without further specification, the map is calculated in its own mode by the
+/// latest version of that mode. osu!standard maps can be converted into any other
+/// mode through [`mode`](Self::mode) and a specific version can be chosen through
+/// [`version`](Self::version).
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "taiko-2022")]
+/// # {
+/// use rosu_pp::{Beatmap, GameMode};
+/// use rosu_pp_older::{AnyStars, Version};
+///
+/// # /*
+/// let map: Beatmap = ...
+/// # */
+/// # let map = Beatmap::default();
+///
+/// // The latest osu!taiko version on the converted map
+/// let attrs = AnyStars::new(&map)
+///     .mode(GameMode::Taiko)
+///     .mods(8 + 64) // HDDT
+///     .calculate()
+///     .unwrap();
+///
+/// assert_eq!(attrs.mode(), GameMode::Taiko);
+/// assert_eq!(attrs.version(), Version::Taiko2022);
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct AnyStars<'map> {
+    map: &'map Beatmap,
+    mode: Option<GameMode>,
+    version: Option<Version>,
+    mods: u32,
+    passed_objects: Option<usize>,
+    clock_rate: Option<f64>,
+    difficulty_adjust: DifficultyAdjust,
+    #[cfg(feature = "store")]
+    store: Option<(&'map AnyAttributeStore, u64)>,
+}
+
+impl<'map> AnyStars<'map> {
+    /// Create a new difficulty calculator for the map.
+    #[inline]
+    pub fn new(map: &'map Beatmap) -> Self {
+        Self {
+            map,
+            mode: None,
+            version: None,
+            mods: 0,
+            passed_objects: None,
+            clock_rate: None,
+            difficulty_adjust: DifficultyAdjust::default(),
+            #[cfg(feature = "store")]
+            store: None,
+        }
+    }
+
+    /// Specify the mode to calculate, defaults to the mode of the
+    /// [`version`](Self::version) or otherwise the mode of the map.
+    ///
+    /// Only osu!standard maps can be converted into other modes.
+    #[inline]
+    pub fn mode(mut self, mode: GameMode) -> Self {
+        self.mode = Some(mode);
+
+        self
+    }
+
+    /// Specify the version to calculate with, defaults to the latest enabled version of the mode.
+    ///
+    /// The mode of the version has to match the mode given through [`mode`](Self::mode).
+    #[inline]
+    pub fn version(mut self, version: Version) -> Self {
+        self.version = Some(version);
+
+        self
+    }
+
+    /// Specify mods through their bit values.
+    ///
+    /// See [https://github.com/ppy/osu-api/wiki#mods](https://github.com/ppy/osu-api/wiki#mods)
+    #[inline]
+    pub fn mods(mut self, mods: u32) -> Self {
+        self.mods = mods;
+
+        self
+    }
+
+    /// Specify mods through their acronyms, e.g. `"HDDT"`.
+    ///
+    /// Returns an error for unknown acronyms. Whether the mods existed at the
+    /// time of the version is not checked, see [`Version::parse_mods`] for that.
+    #[inline]
+    pub fn mods_str(self, acronyms: &str) -> Result<Self, Error> {
+        mods::parse(acronyms).map(|mods| self.mods(mods))
+    }
+
+    /// Amount of passed objects for partial plays, e.g. a fail.
+    #[inline]
+    pub fn passed_objects(mut self, passed_objects: usize) -> Self {
+        self.passed_objects = Some(passed_objects);
+
+        self
+    }
+
+    /// Adjust the clock rate used in the calculation, e.g. `1.4` for a lazer DT
+    /// score that was set at 1.4x.
+    ///
+    /// If none is specified, the clock rate of the mods is used.
+    #[inline]
+    pub fn clock_rate(mut self, clock_rate: f64) -> Self {
+        self.clock_rate = Some(clock_rate);
+
+        self
+    }
+
+    /// Specify the values of lazer's Difficulty Adjust mod.
+    ///
+    /// The values replace the map's base values before HR, EZ, and the clock rate are applied.
+    /// Only considered by osu!standard versions.
+    #[inline]
+    pub fn difficulty_adjust(mut self, difficulty_adjust: DifficultyAdjust) -> Self {
+        self.difficulty_adjust = difficulty_adjust;
+
+        self
+    }
+
+    /// Look up the attributes in the store and store them after calculating them.
+    ///
+    /// The attributes are keyed by the given map id or hash of the map's content, the mods,
+    /// and the version. Partial plays, custom clock rates, and Difficulty Adjust values
+    /// bypass the store.
+    ///
+    /// Only available with the `store` feature.
+    #[cfg(feature = "store")]
+    #[inline]
+    pub fn store(mut self, store: &'map AnyAttributeStore, map: u64) -> Self {
+        self.store = Some((store, map));
+
+        self
+    }
+
+    /// Override the map's approach rate, see [`difficulty_adjust`](Self::difficulty_adjust).
+    #[inline]
+    pub fn ar(mut self, ar: f32) -> Self {
+        self.difficulty_adjust.ar = Some(ar);
+
+        self
+    }
+
+    /// Override the map's circle size, see [`difficulty_adjust`](Self::difficulty_adjust).
+    #[inline]
+    pub fn cs(mut self, cs: f32) -> Self {
+        self.difficulty_adjust.cs = Some(cs);
+
+        self
+    }
+
+    /// Override the map's overall difficulty, see [`difficulty_adjust`](Self::difficulty_adjust).
+    #[inline]
+    pub fn od(mut self, od: f32) -> Self {
+        self.difficulty_adjust.od = Some(od);
+
+        self
+    }
+
+    /// Override the map's drain rate, see [`difficulty_adjust`](Self::difficulty_adjust).
+    #[inline]
+    pub fn hp(mut self, hp: f32) -> Self {
+        self.difficulty_adjust.hp = Some(hp);
+
+        self
+    }
+
+    /// The version that [`calculate`](Self::calculate) dispatches to.
+    ///
+    /// Returns [`Error::UnsupportedMode`] if the map can not be converted into the
+    /// requested mode or if the version does not calculate the requested mode, and
+    /// [`Error::DisabledMode`] if no version of the mode is enabled.
+    pub fn resolve(&self) -> Result<Version, Error> {
+        let mode = self.resolve_mode()?;
+
+        match self.version {
+            Some(version) if version.mode() == mode => Ok(version),
+            Some(version) => Err(Error::UnsupportedMode {
+                version: version.mode(),
+                map: mode,
+            }),
+            None => Version::latest(mode).ok_or(Error::DisabledMode(mode)),
+        }
+    }
+
+    /// The requested mode if the map can be converted into it.
+    fn resolve_mode(&self) -> Result<GameMode, Error> {
+        let mode = self
+            .mode
+            .or_else(|| self.version.map(Version::mode))
+            .unwrap_or(self.map.mode);
+        check_mode(self.map.mode, mode)?;
+
+        Ok(mode)
+    }
+
+    /// Calculate the star rating with the registered custom version of the given name
+    /// instead, see [`custom`](crate::custom).
+    ///
+    /// The custom version is looked up within the requested mode and only receives the mods.
+    /// Returns [`Error::UnknownVersion`] if no such custom version is registered.
+    pub fn calculate_custom(self, name: &str) -> Result<f64, Error> {
+        let mode = self.resolve_mode()?;
+
+        let custom = custom::custom_version(mode, name)
+            .ok_or_else(|| Error::UnknownVersion(name.to_owned()))?;
+
+        Ok(custom.stars(self.map, self.mods))
+    }
+
+    /// Calculate the difficulty attributes with the resolved version.
+    ///
+    /// Returns [`Error::UnsupportedMode`] if the map can not be converted into the
+    /// requested mode or if the version does not calculate the requested mode.
+    pub fn calculate(self) -> Result<AnyDifficultyAttributes, Error> {
+        let version = self.resolve()?;
+
+        #[cfg(feature = "store")]
+        if let Some((store, key)) = self.store_key(version) {
+            return Ok(store.get_or_insert_with(key, || self.calculate_version(version)));
+        }
+
+        Ok(self.calculate_version(version))
+    }
+
+    /// The store and the key of the attributes unless the store is bypassed.
+    #[cfg(feature = "store")]
+    fn store_key(&self, version: Version) -> Option<(&'map AnyAttributeStore, AttributeKey)> {
+        let (store, map) = self.store?;
+
+        let adjusted = self.passed_objects.is_some()
+            || self.clock_rate.is_some()
+            || !self.difficulty_adjust.is_empty();
+
+        let key = AttributeKey {
+            map,
+            mods: self.mods,
+            version,
+        };
+
+        (!adjusted).then(|| (store, key))
+    }
+
+    fn calculate_version(&self, version: Version) -> AnyDifficultyAttributes {
+        macro_rules! stars {
+            ($variant:ident, $calculator:path $(, $difficulty_adjust:ident)?) => {{
+                let mut calculator = <$calculator>::new(self.map).mods(self.mods);
+
+                if let Some(passed_objects) = self.passed_objects {
+                    calculator = calculator.passed_objects(passed_objects);
+                }
+
+                if let Some(clock_rate) = self.clock_rate {
+                    calculator = calculator.clock_rate(clock_rate);
+                }
+
+                $(
+                    calculator = calculator.$difficulty_adjust(self.difficulty_adjust);
+                )?
+
+                AnyDifficultyAttributes::$variant(calculator.calculate())
+            }};
+        }
+
+        macro_rules! osu {
+            ($variant:ident, $module:ident) => {
+                stars!($variant, crate::$module::OsuStars, difficulty_adjust)
+            };
+        }
+
+        match version {
+            #[cfg(feature = "osu-2012-tp")]
+            Version::Osu2012Tp => osu!(Osu2012Tp, osu_2012_tp),
+            #[cfg(feature = "osu-2014-february")]
+            Version::Osu2014February => osu!(Osu2014February, osu_2014_february),
+            #[cfg(feature = "osu-2014-may")]
+            Version::Osu2014May => osu!(Osu2014May, osu_2014_may),
+            #[cfg(feature = "osu-2014-july")]
+            Version::Osu2014July => osu!(Osu2014July, osu_2014_july),
+            #[cfg(feature = "osu-2015-february")]
+            Version::Osu2015February => osu!(Osu2015February, osu_2015_february),
+            #[cfg(feature = "osu-2015-april")]
+            Version::Osu2015April => osu!(Osu2015April, osu_2015_april),
+            #[cfg(feature = "osu-2018")]
+            Version::Osu2018 => osu!(Osu2018, osu_2018),
+            #[cfg(feature = "osu-2019")]
+            Version::Osu2019 => osu!(Osu2019, osu_2019),
+            #[cfg(feature = "osu-2021-january")]
+            Version::Osu2021January => osu!(Osu2021January, osu_2021_january),
+            #[cfg(feature = "osu-2021-july")]
+            Version::Osu2021July => osu!(Osu2021July, osu_2021_july),
+            #[cfg(feature = "osu-2021-november")]
+            Version::Osu2021November => osu!(Osu2021November, osu_2021_november),
+            #[cfg(feature = "osu-2022-september")]
+            Version::Osu2022September => osu!(Osu2022September, osu_2022_september),
+            #[cfg(feature = "taiko-ppv1")]
+            Version::TaikoPpv1 => stars!(TaikoPpv1, crate::taiko_ppv1::TaikoStars),
+            #[cfg(feature = "taiko-2020")]
+            Version::Taiko2020 => stars!(Taiko2020, crate::taiko_2020::TaikoStars),
+            #[cfg(feature = "taiko-2022")]
+            Version::Taiko2022 => stars!(Taiko2022, crate::taiko_2022::TaikoStars),
+            #[cfg(feature = "mania-ppv1")]
+            Version::ManiaPpv1 => stars!(ManiaPpv1, crate::mania_ppv1::ManiaStars),
+            #[cfg(feature = "mania-2018")]
+            Version::Mania2018 => stars!(Mania2018, crate::mania_2018::ManiaStars),
+            #[cfg(feature = "mania-2022")]
+            Version::Mania2022 => stars!(Mania2022, crate::mania_2022::ManiaStars),
+            #[cfg(feature = "fruits-ppv1")]
+            Version::FruitsPpv1 => stars!(FruitsPpv1, crate::fruits_ppv1::FruitsStars),
+            #[cfg(feature = "catch-2020")]
+            Version::Catch2020 => stars!(Catch2020, crate::catch_2020::CatchStars),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "all"))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn resolve() {
+        let map = Beatmap::default();
+
+        assert_eq!(AnyStars::new(&map).resolve(), Ok(Version::Osu2022September));
+        assert_eq!(
+            AnyStars::new(&map).mode(GameMode::Catch).resolve(),
+            Ok(Version::Catch2020)
+        );
+
+        let map = Beatmap {
+            mode: GameMode::Mania,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            AnyStars::new(&map).version(Version::ManiaPpv1).resolve(),
+            Ok(Version::ManiaPpv1)
+        );
+        assert_eq!(
+            AnyStars::new(&Beatmap::default())
+                .version(Version::Taiko2020)
+                .resolve(),
+            Ok(Version::Taiko2020)
+        );
+        assert_eq!(
+            AnyStars::new(&map).mode(GameMode::Taiko).resolve(),
+            Err(Error::UnsupportedMode {
+                version: GameMode::Taiko,
+                map: GameMode::Mania,
+            })
+        );
+    }
+
+    #[cfg(feature = "store")]
+    #[test]
+    fn store() {
+        use crate::{AnyPP, StoreStats};
+
+        let map = Beatmap::default();
+        let store = AnyAttributeStore::new();
+
+        for _ in 0..2 {
+            AnyStars::new(&map)
+                .mods(64)
+                .store(&store, 1)
+                .calculate()
+                .unwrap();
+        }
+
+        // Partial plays bypass the store
+        AnyStars::new(&map)
+            .mods(64)
+            .passed_objects(0)
+            .store(&store, 1)
+            .calculate()
+            .unwrap();
+
+        AnyPP::new(&map, Version::Osu2022September)
+            .mods(64)
+            .store(&store, 1)
+            .calculate()
+            .unwrap();
+
+        let expected = StoreStats {
+            hits: 2,
+            misses: 1,
+            len: 1,
+        };
+
+        assert_eq!(store.stats(), expected);
+    }
+}
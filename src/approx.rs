@@ -0,0 +1,158 @@
+//! Tolerance-based comparison of attributes.
+//!
+//! Results may differ slightly between platforms or after refactorings of the
+//! calculation so regression tests against cached values should not compare exactly.
+
+use crate::osu;
+
+/// Compare values while allowing for small differences in floating point numbers.
+pub trait ApproxEq {
+    /// Whether all floating point values of `self` and `other` differ by at most
+    /// `epsilon` and all integer values are equal.
+    fn approx_eq(&self, other: &Self, epsilon: f64) -> bool;
+}
+
+impl ApproxEq for f64 {
+    #[inline]
+    fn approx_eq(&self, other: &Self, epsilon: f64) -> bool {
+        self == other || (self - other).abs() <= epsilon
+    }
+}
+
+impl ApproxEq for usize {
+    #[inline]
+    fn approx_eq(&self, other: &Self, _: f64) -> bool {
+        self == other
+    }
+}
+
+impl<T: ApproxEq> ApproxEq for Option<T> {
+    #[inline]
+    fn approx_eq(&self, other: &Self, epsilon: f64) -> bool {
+        match (self, other) {
+            (Some(this), Some(other)) => this.approx_eq(other, epsilon),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+}
+
+impl<T: ApproxEq> ApproxEq for [T] {
+    #[inline]
+    fn approx_eq(&self, other: &Self, epsilon: f64) -> bool {
+        self.len() == other.len()
+            && self
+                .iter()
+                .zip(other)
+                .all(|(this, other)| this.approx_eq(other, epsilon))
+    }
+}
+
+impl<T: ApproxEq> ApproxEq for Vec<T> {
+    #[inline]
+    fn approx_eq(&self, other: &Self, epsilon: f64) -> bool {
+        self.as_slice().approx_eq(other, epsilon)
+    }
+}
+
+// Destructuring `Self` ensures that no field is forgotten.
+macro_rules! impl_approx_eq {
+    ( $( $( #[$meta:meta] )* $ty:path { $( $field:ident ),* $(,)? } ),* $(,)? ) => {
+        $(
+            $( #[$meta] )*
+            impl ApproxEq for $ty {
+                #[inline]
+                fn approx_eq(&self, other: &Self, epsilon: f64) -> bool {
+                    let Self { $( $field ),* } = self;
+
+                    $( $field.approx_eq(&other.$field, epsilon) )&&*
+                }
+            }
+        )*
+    };
+}
+
+impl_approx_eq! {
+    osu::DifficultyAttributes {
+        aim_strain, speed_strain, speed_note_count, flashlight_rating, slider_factor, ar, od,
+        base_ar, base_od, hp, n_circles, n_sliders, n_spinners, stars, max_combo,
+    },
+    osu::PerformanceAttributes {
+        difficulty, pp, max_pp, pp_acc, pp_aim, pp_flashlight, pp_speed, nf_multiplier, legacy,
+    },
+    osu::LegacyPerformanceAttributes { fl_bonus, n_spinners, acc_on_circles, flashlight },
+    osu::FlashlightBonus { length_bonus, ar_bonus, acc_bonus },
+    osu::Strains { section_len, aim, aim_no_sliders, speed, flashlight },
+
+    #[cfg(feature = "taiko-ppv1")]
+    crate::taiko_ppv1::TaikoDifficultyAttributes { stars, max_combo, n_circles, n_sliders, n_spinners },
+    #[cfg(feature = "taiko-ppv1")]
+    crate::taiko_ppv1::TaikoPerformanceAttributes { difficulty, pp, pp_acc, pp_strain, nf_multiplier },
+
+    #[cfg(feature = "taiko-2020")]
+    crate::taiko_2020::TaikoDifficultyAttributes { stars, max_combo, n_circles, n_sliders, n_spinners },
+    #[cfg(feature = "taiko-2020")]
+    crate::taiko_2020::TaikoPerformanceAttributes { difficulty, pp, pp_acc, pp_strain, nf_multiplier },
+
+    #[cfg(feature = "taiko-2022")]
+    crate::taiko_2022::TaikoDifficultyAttributes {
+        stamina, rhythm, color, peak, hit_window, stars, max_combo, n_circles, n_sliders, n_spinners,
+    },
+    #[cfg(feature = "taiko-2022")]
+    crate::taiko_2022::TaikoPerformanceAttributes {
+        difficulty, pp, pp_acc, pp_strain, effective_miss_count,
+    },
+
+    #[cfg(feature = "mania-ppv1")]
+    crate::mania_ppv1::ManiaDifficultyAttributes { stars, n_circles, n_sliders, n_spinners },
+    #[cfg(feature = "mania-ppv1")]
+    crate::mania_ppv1::ManiaPerformanceAttributes { difficulty, pp, pp_acc, pp_strain, nf_multiplier },
+
+    #[cfg(feature = "mania-2018")]
+    crate::mania_2018::ManiaDifficultyAttributes { stars, n_circles, n_sliders, n_spinners },
+    #[cfg(feature = "mania-2018")]
+    crate::mania_2018::ManiaPerformanceAttributes { difficulty, pp, pp_acc, pp_strain, nf_multiplier },
+
+    #[cfg(feature = "mania-2022")]
+    crate::mania_2022::ManiaDifficultyAttributes { stars, hit_window, n_circles, n_sliders, n_spinners },
+    #[cfg(feature = "mania-2022")]
+    crate::mania_2022::ManiaPerformanceAttributes { difficulty, pp, pp_difficulty },
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn floats_within_epsilon() {
+        assert!(1.0_f64.approx_eq(&1.000_001, 1e-5));
+        assert!(!1.0_f64.approx_eq(&1.001, 1e-5));
+        assert!(f64::INFINITY.approx_eq(&f64::INFINITY, 0.0));
+        assert!(!f64::NAN.approx_eq(&f64::NAN, 1.0));
+    }
+
+    #[cfg(feature = "taiko-2020")]
+    #[test]
+    fn attributes_within_epsilon() {
+        let a = crate::taiko_2020::TaikoDifficultyAttributes {
+            stars: 5.0,
+            max_combo: 1000,
+            ..Default::default()
+        };
+
+        let mut b = a;
+        b.stars += 1e-9;
+        assert!(a.approx_eq(&b, 1e-6));
+
+        b.max_combo += 1;
+        assert!(!a.approx_eq(&b, 1e-6));
+    }
+
+    #[test]
+    fn strains_length_mismatch() {
+        let a: Vec<f64> = vec![1.0, 2.0];
+        let b: Vec<f64> = vec![1.0, 2.0, 3.0];
+
+        assert!(!a.approx_eq(&b, 1.0));
+    }
+}
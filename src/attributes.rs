@@ -0,0 +1,368 @@
+//! Uniform access to the attributes of the osu!standard versions.
+//!
+//! All osu!standard versions share the attribute types of [`osu`](crate::osu). The traits
+//! of this module allow handling them generically alongside [`SharedDifficultyAttributes`].
+//!
+//! The attributes also convert into rosu-pp's own attribute types. Values that a version
+//! does not calculate, e.g. the flashlight rating before 2021, are zero after conversion.
+
+use rosu_pp::{
+    osu::{
+        OsuDifficultyAttributes as RosuDifficultyAttributes,
+        OsuPerformanceAttributes as RosuPerformanceAttributes,
+    },
+    DifficultyAttributes as RosuAnyDifficultyAttributes,
+    PerformanceAttributes as RosuAnyPerformanceAttributes,
+};
+
+use crate::osu;
+
+/// The difficulty attributes of an osu!standard version.
+pub trait DifficultyAttributes {
+    /// The star rating of the map.
+    fn stars(&self) -> f64;
+
+    /// The maximum combo of the map.
+    fn max_combo(&self) -> usize;
+
+    /// The approach rate after applying mods and the clock rate.
+    fn ar(&self) -> f64;
+
+    /// The overall difficulty after applying mods and the clock rate.
+    fn od(&self) -> f64;
+
+    /// Convert into the attributes that all osu!standard versions have in common.
+    fn to_shared(&self) -> SharedDifficultyAttributes;
+}
+
+/// The performance attributes of an osu!standard version.
+pub trait PerformanceAttributes {
+    /// The final performance points.
+    fn pp(&self) -> f64;
+
+    /// The aim portion of the final pp.
+    fn pp_aim(&self) -> f64;
+
+    /// The speed portion of the final pp.
+    fn pp_speed(&self) -> f64;
+
+    /// The accuracy portion of the final pp.
+    fn pp_acc(&self) -> f64;
+
+    /// The flashlight portion of the final pp, zero for versions without a flashlight skill.
+    fn pp_flashlight(&self) -> f64;
+
+    /// The difficulty attributes that were used for the performance calculation.
+    fn difficulty(&self) -> &dyn DifficultyAttributes;
+
+    /// Convert into the attributes that all osu!standard versions have in common.
+    fn to_shared(&self) -> SharedPerformanceAttributes;
+}
+
+/// The difficulty attributes that all osu!standard versions have in common.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct SharedDifficultyAttributes {
+    pub aim_strain: f64,
+    pub speed_strain: f64,
+    /// The approach rate after applying mods and the clock rate.
+    pub ar: f64,
+    /// The overall difficulty after applying mods and the clock rate.
+    pub od: f64,
+    /// The approach rate of the map before applying mods and the clock rate.
+    pub base_ar: f64,
+    /// The overall difficulty of the map before applying mods and the clock rate.
+    pub base_od: f64,
+    pub hp: f64,
+    pub n_circles: usize,
+    pub n_sliders: usize,
+    pub n_spinners: usize,
+    pub stars: f64,
+    pub max_combo: usize,
+}
+
+/// The performance attributes that all osu!standard versions have in common.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct SharedPerformanceAttributes {
+    /// The difficulty attributes that were used for the performance calculation.
+    pub difficulty: SharedDifficultyAttributes,
+    /// The final performance points.
+    pub pp: f64,
+    /// The accuracy portion of the final pp.
+    pub pp_acc: f64,
+    /// The aim portion of the final pp.
+    pub pp_aim: f64,
+    /// The flashlight portion of the final pp.
+    pub pp_flashlight: f64,
+    /// The speed portion of the final pp.
+    pub pp_speed: f64,
+}
+
+impl DifficultyAttributes for osu::DifficultyAttributes {
+    #[inline]
+    fn stars(&self) -> f64 {
+        self.stars
+    }
+
+    #[inline]
+    fn max_combo(&self) -> usize {
+        self.max_combo
+    }
+
+    #[inline]
+    fn ar(&self) -> f64 {
+        self.ar
+    }
+
+    #[inline]
+    fn od(&self) -> f64 {
+        self.od
+    }
+
+    #[inline]
+    fn to_shared(&self) -> SharedDifficultyAttributes {
+        SharedDifficultyAttributes {
+            aim_strain: self.aim_strain,
+            speed_strain: self.speed_strain,
+            ar: self.ar,
+            od: self.od,
+            base_ar: self.base_ar,
+            base_od: self.base_od,
+            hp: self.hp,
+            n_circles: self.n_circles,
+            n_sliders: self.n_sliders,
+            n_spinners: self.n_spinners,
+            stars: self.stars,
+            max_combo: self.max_combo,
+        }
+    }
+}
+
+impl PerformanceAttributes for osu::PerformanceAttributes {
+    #[inline]
+    fn pp(&self) -> f64 {
+        self.pp
+    }
+
+    #[inline]
+    fn pp_aim(&self) -> f64 {
+        self.pp_aim
+    }
+
+    #[inline]
+    fn pp_speed(&self) -> f64 {
+        self.pp_speed
+    }
+
+    #[inline]
+    fn pp_acc(&self) -> f64 {
+        self.pp_acc
+    }
+
+    #[inline]
+    fn pp_flashlight(&self) -> f64 {
+        self.pp_flashlight
+    }
+
+    #[inline]
+    fn difficulty(&self) -> &dyn DifficultyAttributes {
+        &self.difficulty
+    }
+
+    #[inline]
+    fn to_shared(&self) -> SharedPerformanceAttributes {
+        SharedPerformanceAttributes {
+            difficulty: DifficultyAttributes::to_shared(&self.difficulty),
+            pp: self.pp,
+            pp_acc: self.pp_acc,
+            pp_aim: self.pp_aim,
+            pp_flashlight: self.pp_flashlight,
+            pp_speed: self.pp_speed,
+        }
+    }
+}
+
+impl From<osu::DifficultyAttributes> for RosuDifficultyAttributes {
+    #[inline]
+    #[allow(clippy::needless_update)]
+    fn from(attributes: osu::DifficultyAttributes) -> Self {
+        Self {
+            aim_strain: attributes.aim_strain,
+            speed_strain: attributes.speed_strain,
+            speed_note_count: attributes.speed_note_count,
+            flashlight_rating: attributes.flashlight_rating,
+            slider_factor: attributes.slider_factor.unwrap_or_default(),
+            ar: attributes.ar,
+            od: attributes.od,
+            hp: attributes.hp,
+            n_circles: attributes.n_circles,
+            n_sliders: attributes.n_sliders,
+            n_spinners: attributes.n_spinners,
+            stars: attributes.stars,
+            max_combo: attributes.max_combo,
+            ..Default::default()
+        }
+    }
+}
+
+impl From<osu::PerformanceAttributes> for RosuPerformanceAttributes {
+    #[inline]
+    fn from(attributes: osu::PerformanceAttributes) -> Self {
+        Self {
+            difficulty: attributes.difficulty.into(),
+            pp: attributes.pp,
+            pp_acc: attributes.pp_acc,
+            pp_aim: attributes.pp_aim,
+            pp_flashlight: attributes.pp_flashlight,
+            pp_speed: attributes.pp_speed,
+            ..Default::default()
+        }
+    }
+}
+
+impl From<osu::DifficultyAttributes> for RosuAnyDifficultyAttributes {
+    #[inline]
+    fn from(attributes: osu::DifficultyAttributes) -> Self {
+        Self::Osu(attributes.into())
+    }
+}
+
+impl From<osu::PerformanceAttributes> for RosuAnyPerformanceAttributes {
+    #[inline]
+    fn from(attributes: osu::PerformanceAttributes) -> Self {
+        Self::Osu(attributes.into())
+    }
+}
+
+impl DifficultyAttributes for SharedDifficultyAttributes {
+    #[inline]
+    fn stars(&self) -> f64 {
+        self.stars
+    }
+
+    #[inline]
+    fn max_combo(&self) -> usize {
+        self.max_combo
+    }
+
+    #[inline]
+    fn ar(&self) -> f64 {
+        self.ar
+    }
+
+    #[inline]
+    fn od(&self) -> f64 {
+        self.od
+    }
+
+    #[inline]
+    fn to_shared(&self) -> SharedDifficultyAttributes {
+        self.clone()
+    }
+}
+
+impl PerformanceAttributes for SharedPerformanceAttributes {
+    #[inline]
+    fn pp(&self) -> f64 {
+        self.pp
+    }
+
+    #[inline]
+    fn pp_aim(&self) -> f64 {
+        self.pp_aim
+    }
+
+    #[inline]
+    fn pp_speed(&self) -> f64 {
+        self.pp_speed
+    }
+
+    #[inline]
+    fn pp_acc(&self) -> f64 {
+        self.pp_acc
+    }
+
+    #[inline]
+    fn pp_flashlight(&self) -> f64 {
+        self.pp_flashlight
+    }
+
+    #[inline]
+    fn difficulty(&self) -> &dyn DifficultyAttributes {
+        &self.difficulty
+    }
+
+    #[inline]
+    fn to_shared(&self) -> SharedPerformanceAttributes {
+        self.clone()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn stars_of(attrs: &dyn DifficultyAttributes) -> f64 {
+        attrs.stars()
+    }
+
+    #[test]
+    fn shared_across_versions() {
+        let attrs = osu::DifficultyAttributes {
+            stars: 5.5,
+            max_combo: 1234,
+            ar: 9.0,
+            ..Default::default()
+        };
+
+        let shared = attrs.to_shared();
+
+        assert_eq!(stars_of(&attrs), stars_of(&shared));
+        assert_eq!(shared.max_combo, 1234);
+        assert_eq!(shared.ar, 9.0);
+    }
+
+    #[test]
+    fn breakdown_across_versions() {
+        let attrs = osu::PerformanceAttributes {
+            pp: 300.0,
+            pp_aim: 150.0,
+            difficulty: osu::DifficultyAttributes {
+                stars: 6.0,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let breakdown: &dyn PerformanceAttributes = &attrs;
+
+        assert_eq!(breakdown.pp_aim(), 150.0);
+        assert_eq!(breakdown.difficulty().stars(), 6.0);
+        assert_eq!(
+            PerformanceAttributes::to_shared(&attrs).difficulty.stars,
+            6.0
+        );
+    }
+
+    #[test]
+    fn into_rosu() {
+        let attrs = osu::PerformanceAttributes {
+            pp: 250.0,
+            difficulty: osu::DifficultyAttributes {
+                stars: 5.0,
+                flashlight_rating: 1.5,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let rosu = RosuPerformanceAttributes::from(attrs);
+
+        assert_eq!(rosu.pp, 250.0);
+        assert_eq!(rosu.difficulty.stars, 5.0);
+        assert_eq!(rosu.difficulty.flashlight_rating, 1.5);
+        assert_eq!(rosu.difficulty.speed_note_count, 0.0);
+        assert_eq!(rosu.difficulty.slider_factor, 0.0);
+    }
+}
@@ -0,0 +1,52 @@
+use rosu_pp::Beatmap;
+
+use crate::{AnyDifficultyAttributes, AnyPP, Error, Version};
+
+/// Calculate older versions directly on a [`Beatmap`].
+///
+/// Named differently than rosu-pp's `BeatmapExt` so that both can be imported at once.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "osu-2015-april")]
+/// # {
+/// use rosu_pp::Beatmap;
+/// use rosu_pp_older::{OlderBeatmapExt, Version};
+///
+/// # /*
+/// let map: Beatmap = ...
+/// # */
+/// # let map = Beatmap::default();
+///
+/// let stars = map.stars_older(Version::Osu2015April, 8 + 64).unwrap().stars();
+///
+/// let attrs = map
+///     .pp_older(Version::Osu2015April)
+///     .mods(8 + 64)
+///     .accuracy(98.5)
+///     .calculate()
+///     .unwrap();
+/// # }
+/// ```
+pub trait OlderBeatmapExt {
+    /// Calculate the difficulty of the map with the given version.
+    ///
+    /// Returns [`Error::UnsupportedMode`] if the map can not be calculated by the version.
+    fn stars_older(&self, version: Version, mods: u32) -> Result<AnyDifficultyAttributes, Error>;
+
+    /// Create a performance calculator of the given version for the map.
+    fn pp_older(&self, version: Version) -> AnyPP<'_>;
+}
+
+impl OlderBeatmapExt for Beatmap {
+    #[inline]
+    fn stars_older(&self, version: Version, mods: u32) -> Result<AnyDifficultyAttributes, Error> {
+        crate::stars(self, mods, None, version)
+    }
+
+    #[inline]
+    fn pp_older(&self, version: Version) -> AnyPP<'_> {
+        AnyPP::new(self, version)
+    }
+}
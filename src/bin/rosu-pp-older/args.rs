@@ -0,0 +1,97 @@
+use std::{collections::HashMap, str::FromStr};
+
+/// Options that don't take a value.
+const FLAGS: [&str; 2] = ["help", "json"];
+
+/// Command line arguments split into positional arguments and `--name value` options.
+#[derive(Debug, Default)]
+pub struct Args {
+    positional: Vec<String>,
+    options: HashMap<String, String>,
+}
+
+impl Args {
+    pub fn parse(args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut parsed = Self::default();
+        let mut args = args.peekable();
+
+        while let Some(arg) = args.next() {
+            let name = match arg.strip_prefix("--") {
+                Some(name) => name.to_owned(),
+                None if arg == "-h" => "help".to_owned(),
+                None => {
+                    parsed.positional.push(arg);
+
+                    continue;
+                }
+            };
+
+            let value = if FLAGS.contains(&name.as_str()) {
+                String::new()
+            } else {
+                args.next()
+                    .ok_or_else(|| format!("missing value for `--{}`", name))?
+            };
+
+            parsed.options.insert(name, value);
+        }
+
+        Ok(parsed)
+    }
+
+    /// The positional argument at the given index.
+    pub fn positional(&self, idx: usize) -> Option<&str> {
+        self.positional.get(idx).map(String::as_str)
+    }
+
+    /// Whether the flag was specified.
+    pub fn flag(&self, name: &str) -> bool {
+        self.options.contains_key(name)
+    }
+
+    /// The raw value of an option.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.options.get(name).map(String::as_str)
+    }
+
+    /// The parsed value of an option.
+    pub fn value<T: FromStr>(&self, name: &str) -> Result<Option<T>, String> {
+        self.get(name)
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|_| format!("invalid value `{}` for `--{}`", value, name))
+            })
+            .transpose()
+    }
+}
+
+/// The score related options shared by all subcommands.
+#[derive(Clone, Debug, Default)]
+pub struct ScoreArgs {
+    pub mods: Option<String>,
+    pub acc: Option<f64>,
+    pub combo: Option<usize>,
+    pub misses: Option<usize>,
+    pub n300: Option<usize>,
+    pub n100: Option<usize>,
+    pub n50: Option<usize>,
+    pub score: Option<u32>,
+    pub passed_objects: Option<usize>,
+}
+
+impl ScoreArgs {
+    pub fn new(args: &Args) -> Result<Self, String> {
+        Ok(Self {
+            mods: args.get("mods").map(str::to_owned),
+            acc: args.value("acc")?,
+            combo: args.value("combo")?,
+            misses: args.value("misses")?,
+            n300: args.value("n300")?,
+            n100: args.value("n100")?,
+            n50: args.value("n50")?,
+            score: args.value("score")?,
+            passed_objects: args.value("passed")?,
+        })
+    }
+}
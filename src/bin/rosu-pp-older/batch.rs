@@ -0,0 +1,281 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    fs,
+    hash::{Hash, Hasher},
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use rosu_pp::Beatmap;
+use rosu_pp_older::{AnyAttributeStore, Version};
+use serde::Serialize;
+
+use crate::{
+    args::{Args, ScoreArgs},
+    output::Output,
+    version, CliResult,
+};
+
+/// The output format of a batch.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Format {
+    Csv,
+    Json,
+}
+
+/// The calculation of one map for one version and mod combination.
+#[derive(Clone, Debug, Serialize)]
+struct Record {
+    map: String,
+    mode: String,
+    mods: String,
+    #[serde(flatten)]
+    output: Output,
+}
+
+/// Calculate all maps of a directory for the requested versions and mods.
+pub async fn run(args: &Args) -> CliResult<()> {
+    let dir = args.positional(1).ok_or("missing directory")?;
+
+    let format = match args.get("format") {
+        _ if args.flag("json") => Format::Json,
+        None | Some("csv") => Format::Csv,
+        Some("json") => Format::Json,
+        Some(other) => return Err(format!("unknown format `{}`", other).into()),
+    };
+
+    let versions = args.get("versions").map(parse_versions).transpose()?;
+
+    let mods: Vec<String> = args
+        .get("mods")
+        .unwrap_or("NM")
+        .split(',')
+        .map(str::to_owned)
+        .collect();
+
+    let score = ScoreArgs::new(args)?;
+
+    let mut paths = Vec::new();
+    collect_maps(Path::new(dir), &mut paths)?;
+    paths.sort_unstable();
+
+    // Shared by all maps so that duplicate map files are only calculated once
+    let store = Arc::new(AnyAttributeStore::new());
+
+    let handles: Vec<_> = paths
+        .into_iter()
+        .map(|path| {
+            let versions = versions.clone();
+            let mods = mods.clone();
+            let score = score.clone();
+            let store = Arc::clone(&store);
+
+            tokio::spawn(async move {
+                let records = process(&path, versions.as_deref(), &mods, score, &store).await;
+
+                (path, records)
+            })
+        })
+        .collect();
+
+    let mut records = Vec::new();
+
+    for handle in handles {
+        match handle.await? {
+            (_, Ok(map_records)) => records.extend(map_records),
+            (path, Err(err)) => eprintln!("skipping {}: {}", path.display(), err),
+        }
+    }
+
+    match args.get("output") {
+        Some(path) => {
+            let file = BufWriter::new(fs::File::create(path)?);
+            write_records(&records, format, file)?;
+        }
+        None => {
+            let stdout = io::stdout();
+            write_records(&records, format, stdout.lock())?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Parse comma separated versions with the same spellings as `--version`.
+///
+/// Names within a mode, e.g. `2018`, select the versions of all modes with that name.
+fn parse_versions(names: &str) -> Result<Vec<Version>, String> {
+    let mut versions = Vec::new();
+
+    for name in names.split(',').map(str::trim) {
+        let len = versions.len();
+
+        versions.extend(
+            Version::ALL
+                .iter()
+                .copied()
+                .filter(|version| version.name() == name),
+        );
+
+        if versions.len() == len {
+            let version = name
+                .parse()
+                .map_err(|_| format!("unknown version `{}`", name))?;
+
+            versions.push(version);
+        }
+    }
+
+    Ok(versions)
+}
+
+fn collect_maps(dir: &Path, paths: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+
+        if path.is_dir() {
+            collect_maps(&path, paths)?;
+        } else if path.extension().map_or(false, |ext| ext == "osu") {
+            paths.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+async fn process(
+    path: &Path,
+    versions: Option<&[Version]>,
+    mods: &[String],
+    score: ScoreArgs,
+    store: &AnyAttributeStore,
+) -> Result<Vec<Record>, String> {
+    let bytes = tokio::fs::read(path).await.map_err(|err| err.to_string())?;
+
+    let map = Beatmap::parse(bytes.as_slice())
+        .await
+        .map_err(|err| err.to_string())?;
+
+    // Maps are keyed by their content since .osu files don't necessarily contain their id
+    let mut hasher = DefaultHasher::new();
+    bytes.hash(&mut hasher);
+    let key = hasher.finish();
+
+    calculate_map(path, &map, key, versions, mods, score, store)
+}
+
+/// Calculate a parsed map for the requested versions and mods.
+fn calculate_map(
+    path: &Path,
+    map: &Beatmap,
+    key: u64,
+    versions: Option<&[Version]>,
+    mods: &[String],
+    mut score: ScoreArgs,
+    store: &AnyAttributeStore,
+) -> Result<Vec<Record>, String> {
+    let versions = Version::versions(map.mode)
+        .filter(|version| versions.map_or(true, |versions| versions.contains(version)));
+
+    let mut records = Vec::new();
+
+    for version in versions {
+        for combo in mods {
+            score.mods = Some(combo.to_owned());
+
+            let output = version::calculator(version, map, &score)
+                .and_then(|calc| calc.store(store, key).calculate())
+                .map(|attrs| version::from_attributes(version, attrs))
+                .map_err(|err| err.to_string())?;
+
+            records.push(Record {
+                map: path.display().to_string(),
+                mode: format!("{:?}", version.mode()),
+                mods: combo.to_owned(),
+                output,
+            });
+        }
+    }
+
+    Ok(records)
+}
+
+fn write_records(records: &[Record], format: Format, mut writer: impl Write) -> CliResult<()> {
+    match format {
+        Format::Csv => {
+            writeln!(
+                writer,
+                "map,mode,version,mods,stars,pp,pp_aim,pp_speed,pp_acc,pp_flashlight,pp_strain"
+            )?;
+
+            for record in records {
+                let output = &record.output;
+                let opt = |pp: Option<f64>| pp.map_or_else(String::new, |pp| pp.to_string());
+
+                writeln!(
+                    writer,
+                    "\"{}\",{},{},{},{},{},{},{},{},{},{}",
+                    record.map.replace('"', "\"\""),
+                    record.mode,
+                    output.version.name(),
+                    record.mods,
+                    output.stars,
+                    output.pp,
+                    opt(output.pp_aim),
+                    opt(output.pp_speed),
+                    opt(output.pp_acc),
+                    opt(output.pp_flashlight),
+                    opt(output.pp_strain),
+                )?;
+            }
+        }
+        Format::Json => {
+            serde_json::to_writer_pretty(&mut writer, records)?;
+            writeln!(writer)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use rosu_pp::GameMode;
+
+    use super::*;
+
+    #[test]
+    fn all_osu_versions() {
+        let map = Beatmap::default();
+        let store = AnyAttributeStore::new();
+        let mods = ["NM".to_owned(), "HDDT".to_owned()];
+
+        let records = calculate_map(
+            Path::new("map.osu"),
+            &map,
+            0,
+            None,
+            &mods,
+            ScoreArgs::default(),
+            &store,
+        )
+        .unwrap();
+
+        let n_versions = Version::versions(GameMode::Osu).count();
+        assert_eq!(records.len(), n_versions * mods.len());
+        assert_eq!(records[0].output.version, Version::Osu2012Tp);
+    }
+
+    #[test]
+    fn version_spellings() {
+        let versions = parse_versions("2015-april,osu_2019,taiko_2021").unwrap();
+        let expected = [Version::Osu2015April, Version::Osu2019, Version::Taiko2020];
+        assert_eq!(versions, expected);
+
+        let versions = parse_versions("2018").unwrap();
+        assert_eq!(versions, [Version::Osu2018, Version::Mania2018]);
+
+        assert!(parse_versions("osu_2016_april").is_err());
+    }
+}
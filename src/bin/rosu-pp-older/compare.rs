@@ -0,0 +1,70 @@
+use rosu_pp_older::Version;
+
+use crate::{
+    args::{Args, ScoreArgs},
+    output::Output,
+    version, CliResult,
+};
+
+/// Print the stars and pp of one map for all versions of its mode.
+///
+/// The map is only parsed once and then shared by all versions.
+pub async fn run(args: &Args) -> CliResult<()> {
+    let path = args.positional(1).ok_or("missing map path")?;
+    let map = crate::load_map(path).await?;
+    let score = ScoreArgs::new(args)?;
+
+    let versions = Version::versions(map.mode);
+
+    if args.flag("json") {
+        let outputs: Vec<_> = versions
+            .filter_map(|version| match version::calculate(version, &map, &score) {
+                Ok(output) => Some(output),
+                Err(err) => {
+                    eprintln!("{}: {}", version.name(), err);
+
+                    None
+                }
+            })
+            .collect();
+
+        return crate::print_json(&outputs);
+    }
+
+    let mut header = format!("{:<16}{:>10}{:>10}", "Version", "Stars", "PP");
+
+    for name in Output::COMPONENT_NAMES.iter() {
+        header.push_str(&format!("{:>12}", name));
+    }
+
+    println!("{}", header);
+
+    for version in versions {
+        let output = match version::calculate(version, &map, &score) {
+            Ok(output) => output,
+            Err(err) => {
+                println!("{:<16}{}", version.name(), err);
+
+                continue;
+            }
+        };
+
+        let mut row = format!(
+            "{:<16}{:>10.2}{:>10.2}",
+            version.name(),
+            output.stars,
+            output.pp
+        );
+
+        for pp in output.components().iter() {
+            match pp {
+                Some(pp) => row.push_str(&format!("{:>12.2}", pp)),
+                None => row.push_str(&format!("{:>12}", "-")),
+            }
+        }
+
+        println!("{}", row);
+    }
+
+    Ok(())
+}
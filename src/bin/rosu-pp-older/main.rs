@@ -0,0 +1,151 @@
+//! Command line interface to calculate stars and pp of previous pp versions.
+
+mod args;
+mod batch;
+mod compare;
+mod output;
+mod profile;
+mod repl;
+mod replay;
+mod strains;
+mod version;
+mod watch;
+
+use std::{env, error::Error, process};
+
+use rosu_pp::Beatmap;
+use rosu_pp_older::Version;
+use serde::Serialize;
+
+use self::args::{Args, ScoreArgs};
+
+type CliResult<T> = Result<T, Box<dyn Error>>;
+
+const USAGE: &str = "\
+Usage:
+    rosu-pp-older <MAP> [OPTIONS]
+    rosu-pp-older batch <DIR> [OPTIONS]
+    rosu-pp-older compare <MAP> [OPTIONS]
+    rosu-pp-older profile <SCORES> [--version <VERSION>]
+    rosu-pp-older repl <MAP> [OPTIONS]
+    rosu-pp-older replay <REPLAY> <MAP> [--version <VERSION>]
+    rosu-pp-older strains <MAP> [--version <VERSION>] [--mods <MODS>] [STRAINS OPTIONS]
+    rosu-pp-older watch <MAP> [--mods <MODS>] [--interval <MS>]
+
+Calculate the stars and pp of a .osu file for a previous pp version.
+A map path of `-` reads the map from stdin, except for `repl` and `watch`.
+
+Subcommands:
+    batch      calculate all .osu files of a directory, see the batch options
+    compare    print a table of all versions of the map's mode
+    profile    print the weighted total pp of a file of scores, one
+               `map,mods,acc,combo,misses,date` per line with paths relative to the file
+    repl       keep the map loaded and recalculate it whenever an option is changed
+    replay     calculate an .osr file with the version that was live at its date
+    strains    dump the aim and speed strain peak of each section, see the strains options
+    watch      re-print the stars of all versions whenever the map is saved,
+               checking for changes every 500ms unless specified otherwise
+
+Options:
+    --version <VERSION>    e.g. `2015-april`, defaults to the latest version of the map's mode
+    --mods <MODS>          mod acronyms like `HDDT` or the mods' bit value
+    --acc <ACC>            accuracy between 0 and 100
+    --combo <COMBO>        the score's max combo
+    --misses <MISSES>      amount of misses
+    --n300 <N300>          amount of 300s
+    --n100 <N100>          amount of 100s
+    --n50 <N50>            amount of 50s
+    --score <SCORE>        the score of osu!mania plays before the 2022 version
+    --passed <OBJECTS>     amount of passed objects for failed plays
+    --json                 print the full attributes as JSON instead of a table
+    -h, --help             print this message
+
+Batch options:
+    --versions <VERSIONS>  comma separated versions, defaults to all versions of each map's mode
+    --mods <MODS>          comma separated mod combinations, e.g. `NM,HR,HDDT`
+    --format <FORMAT>      `csv` (default) or `json`
+    --output <FILE>        write to a file instead of stdout
+
+Strains options:
+    --format <FORMAT>      `json` (default) or `csv`
+    --output <FILE>        write to a file instead of stdout
+    --svg <FILE>           additionally draw a sparkline of both skills into an SVG file";
+
+fn main() {
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_time()
+        .build()
+        .expect("failed to build the async runtime");
+
+    if let Err(err) = runtime.block_on(run()) {
+        eprintln!("error: {}", err);
+        process::exit(1);
+    }
+}
+
+async fn run() -> CliResult<()> {
+    let args = Args::parse(env::args().skip(1))?;
+
+    if args.flag("help") {
+        println!("{}", USAGE);
+
+        return Ok(());
+    }
+
+    match args.positional(0) {
+        Some("batch") => batch::run(&args).await,
+        Some("compare") => compare::run(&args).await,
+        Some("profile") => profile::run(&args).await,
+        Some("repl") => repl::run(&args).await,
+        Some("replay") => replay::run(&args).await,
+        Some("strains") => strains::run(&args).await,
+        Some("watch") => watch::run(&args).await,
+        _ => calculate(&args).await,
+    }
+}
+
+async fn calculate(args: &Args) -> CliResult<()> {
+    let path = args
+        .positional(0)
+        .ok_or_else(|| format!("missing map path\n\n{}", USAGE))?;
+
+    let map = crate::load_map(path).await?;
+    let version = select_version(&map, args)?;
+    let score = ScoreArgs::new(args)?;
+
+    let output = version::calculate(version, &map, &score)?;
+
+    if args.flag("json") {
+        print_json(&output)?;
+    } else {
+        println!("{}", output);
+    }
+
+    Ok(())
+}
+
+fn print_json(value: &impl Serialize) -> CliResult<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+
+    Ok(())
+}
+
+/// Parse the map at the path or read it from stdin if the path is `-`.
+async fn load_map(path: &str) -> CliResult<Beatmap> {
+    let map = if path == "-" {
+        Beatmap::parse(tokio::io::stdin()).await?
+    } else {
+        Beatmap::from_path(path).await?
+    };
+
+    Ok(map)
+}
+
+fn select_version(map: &Beatmap, args: &Args) -> CliResult<Version> {
+    let version = match args.get("version") {
+        Some(name) => version::from_name(map.mode, name)?,
+        None => version::latest(map.mode)?,
+    };
+
+    Ok(version)
+}
@@ -0,0 +1,75 @@
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use rosu_pp_older::Version;
+use serde::Serialize;
+
+/// The stars and pp breakdown of a calculation.
+#[derive(Clone, Debug, Serialize)]
+pub struct Output {
+    #[serde(serialize_with = "crate::version::serialize")]
+    pub version: Version,
+    pub stars: f64,
+    pub pp: f64,
+    pub pp_aim: Option<f64>,
+    pub pp_speed: Option<f64>,
+    pub pp_acc: Option<f64>,
+    pub pp_flashlight: Option<f64>,
+    pub pp_strain: Option<f64>,
+    /// The full performance attributes of the version.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attributes: Option<serde_json::Value>,
+}
+
+impl Output {
+    pub fn new(version: Version) -> Self {
+        Self {
+            version,
+            stars: 0.0,
+            pp: 0.0,
+            pp_aim: None,
+            pp_speed: None,
+            pp_acc: None,
+            pp_flashlight: None,
+            pp_strain: None,
+            attributes: None,
+        }
+    }
+
+    /// The names of the pp components in the order of [`components`](Self::components).
+    pub const COMPONENT_NAMES: [&'static str; 5] =
+        ["Aim", "Speed", "Accuracy", "Flashlight", "Strain"];
+
+    /// The values of the pp components.
+    pub fn components(&self) -> [Option<f64>; 5] {
+        [
+            self.pp_aim,
+            self.pp_speed,
+            self.pp_acc,
+            self.pp_flashlight,
+            self.pp_strain,
+        ]
+    }
+}
+
+impl Display for Output {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        writeln!(
+            f,
+            "Version:      {} ({:?})",
+            self.version.name(),
+            self.version.mode()
+        )?;
+        writeln!(f, "Stars:        {:.2}", self.stars)?;
+        write!(f, "PP:           {:.2}", self.pp)?;
+
+        let components = Self::COMPONENT_NAMES.iter().zip(self.components().iter());
+
+        for (name, pp) in components {
+            if let Some(pp) = pp {
+                write!(f, "\n  {:<12}{:.2}", format!("{}:", name), pp)?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,140 @@
+use std::{fs, path::Path};
+
+use rosu_pp_older::{date, Version};
+use serde::Serialize;
+
+use crate::{
+    args::{Args, ScoreArgs},
+    version, CliResult,
+};
+
+/// The factor by which each score's weight decreases in the total pp.
+const WEIGHT_DECAY: f64 = 0.95;
+
+/// One calculated score of a profile.
+#[derive(Clone, Debug, Serialize)]
+struct Score {
+    map: String,
+    mods: String,
+    #[serde(serialize_with = "version::serialize")]
+    version: Version,
+    pp: f64,
+    weight: f64,
+}
+
+/// The JSON output of a profile.
+#[derive(Serialize)]
+struct Profile<'s> {
+    total_pp: f64,
+    scores: &'s [Score],
+}
+
+/// Print the weighted total pp of a file of scores.
+///
+/// Each line of the file is formatted as `map,mods,acc,combo,misses,date`
+/// where everything but the map path is optional. Map paths are relative to
+/// the file. Each score is calculated with the version specified through
+/// `--version` or the one that was live at the score's date.
+pub async fn run(args: &Args) -> CliResult<()> {
+    let path = args.positional(1).ok_or("missing scores file")?;
+    let content = fs::read_to_string(path)?;
+    let dir = Path::new(path).parent().unwrap_or_else(|| Path::new(""));
+
+    let mut scores = Vec::new();
+
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') || line.starts_with("map,") {
+            continue;
+        }
+
+        let score = calculate_line(line, dir, args)
+            .await
+            .map_err(|err| format!("line {}: {}", i + 1, err))?;
+
+        scores.push(score);
+    }
+
+    scores.sort_unstable_by(|a, b| b.pp.partial_cmp(&a.pp).unwrap_or(std::cmp::Ordering::Equal));
+
+    let mut total_pp = 0.0;
+    let mut weight = 1.0;
+
+    for score in scores.iter_mut() {
+        score.weight = weight;
+        total_pp += score.pp * weight;
+        weight *= WEIGHT_DECAY;
+    }
+
+    if args.flag("json") {
+        return crate::print_json(&Profile {
+            total_pp,
+            scores: &scores,
+        });
+    }
+
+    println!(
+        "{:<40}{:<10}{:<16}{:>10}{:>10}",
+        "Map", "Mods", "Version", "PP", "Weighted"
+    );
+
+    for score in scores.iter() {
+        println!(
+            "{:<40}{:<10}{:<16}{:>10.2}{:>10.2}",
+            score.map,
+            score.mods,
+            score.version.name(),
+            score.pp,
+            score.pp * score.weight
+        );
+    }
+
+    println!("\nTotal pp: {:.2}", total_pp);
+
+    Ok(())
+}
+
+async fn calculate_line(line: &str, dir: &Path, args: &Args) -> CliResult<Score> {
+    let mut fields = line.split(',').map(str::trim);
+    let map_path = fields.next().unwrap_or_default();
+
+    let mut field = || fields.next().filter(|field| !field.is_empty());
+
+    let mods = field();
+    let acc: Option<f64> = field().map(str::parse).transpose()?;
+    let combo: Option<usize> = field().map(str::parse).transpose()?;
+    let misses: Option<usize> = field().map(str::parse).transpose()?;
+    let days = field()
+        .map(|ymd| {
+            date::parse_days(ymd)
+                .ok_or_else(|| format!("invalid date `{}`, expected YYYY-MM-DD", ymd))
+        })
+        .transpose()?;
+
+    let map = crate::load_map(&dir.join(map_path).to_string_lossy()).await?;
+
+    let version = match (args.get("version"), days) {
+        (Some(name), _) => version::from_name(map.mode, name)?,
+        (None, Some(days)) => version::at(map.mode, days)?,
+        (None, None) => version::latest(map.mode)?,
+    };
+
+    let score = ScoreArgs {
+        mods: mods.map(str::to_owned),
+        acc,
+        combo,
+        misses,
+        ..Default::default()
+    };
+
+    let output = version::calculate(version, &map, &score)?;
+
+    Ok(Score {
+        map: map_path.to_owned(),
+        mods: mods.unwrap_or("NM").to_owned(),
+        version,
+        pp: output.pp,
+        weight: 1.0,
+    })
+}
@@ -0,0 +1,124 @@
+use std::{
+    io::{self, BufRead, Write},
+    str::FromStr,
+};
+
+use rosu_pp::Beatmap;
+use rosu_pp_older::Version;
+
+use crate::{
+    args::{Args, ScoreArgs},
+    version, CliResult,
+};
+
+const HELP: &str = "\
+Commands:
+    <OPTION> [VALUE]    set an option or reset it by omitting the value
+    reset               reset all options except the version
+    help                print this message
+    quit                exit the repl
+
+Options:
+    version, mods, acc, combo, misses, n300, n100, n50, score, passed";
+
+/// Keep a map loaded and recalculate it whenever an option is changed.
+pub async fn run(args: &Args) -> CliResult<()> {
+    let path = args.positional(1).ok_or("missing map path")?;
+
+    if path == "-" {
+        return Err("the repl reads commands from stdin so the map must be a file".into());
+    }
+
+    let map = Beatmap::from_path(path).await?;
+    let mut version = crate::select_version(&map, args)?;
+    let mut score = ScoreArgs::new(args)?;
+    let json = args.flag("json");
+
+    println!("Loaded {}, type `help` for a list of commands.\n", path);
+    print_output(&map, version, &score, json);
+
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+
+    loop {
+        print!("> ");
+        io::stdout().flush()?;
+
+        let line = match lines.next() {
+            Some(line) => line?,
+            None => return Ok(()),
+        };
+
+        let mut words = line.split_whitespace();
+
+        let command = match words.next() {
+            Some(command) => command,
+            None => continue,
+        };
+
+        let value = words.next();
+
+        let res = match command {
+            "quit" | "exit" => return Ok(()),
+            "help" => {
+                println!("{}", HELP);
+
+                continue;
+            }
+            "reset" => {
+                score = ScoreArgs::default();
+
+                Ok(())
+            }
+            "version" => match value {
+                Some(name) => version::from_name(map.mode, name),
+                None => version::latest(map.mode),
+            }
+            .map(|new| version = new),
+            _ => set_option(&mut score, command, value),
+        };
+
+        match res {
+            Ok(()) => print_output(&map, version, &score, json),
+            Err(err) => println!("error: {}", err),
+        }
+    }
+}
+
+fn set_option(score: &mut ScoreArgs, name: &str, value: Option<&str>) -> Result<(), String> {
+    match name {
+        "mods" => score.mods = value.map(str::to_owned),
+        "acc" => score.acc = parse(name, value)?,
+        "combo" => score.combo = parse(name, value)?,
+        "misses" => score.misses = parse(name, value)?,
+        "n300" => score.n300 = parse(name, value)?,
+        "n100" => score.n100 = parse(name, value)?,
+        "n50" => score.n50 = parse(name, value)?,
+        "score" => score.score = parse(name, value)?,
+        "passed" => score.passed_objects = parse(name, value)?,
+        _ => return Err(format!("unknown command `{}`", name)),
+    }
+
+    Ok(())
+}
+
+fn parse<T: FromStr>(name: &str, value: Option<&str>) -> Result<Option<T>, String> {
+    value
+        .map(|value| {
+            value
+                .parse()
+                .map_err(|_| format!("invalid value `{}` for `{}`", value, name))
+        })
+        .transpose()
+}
+
+fn print_output(map: &Beatmap, version: Version, score: &ScoreArgs, json: bool) {
+    match version::calculate(version, map, score) {
+        Ok(output) if json => match serde_json::to_string(&output) {
+            Ok(line) => println!("{}", line),
+            Err(err) => println!("error: {}", err),
+        },
+        Ok(output) => println!("{}\n", output),
+        Err(err) => println!("error: {}\n", err),
+    }
+}
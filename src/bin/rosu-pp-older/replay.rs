@@ -0,0 +1,253 @@
+use std::{
+    convert::TryInto,
+    fs,
+    io::{Error as IoError, ErrorKind, Result as IoResult},
+};
+
+use rosu_pp::GameMode;
+use rosu_pp_older::date;
+use serde::Serialize;
+
+use crate::{
+    args::{Args, ScoreArgs},
+    output::Output,
+    version, CliResult,
+};
+
+/// Windows ticks, i.e. 100 nanoseconds, per second.
+const TICKS_PER_SECOND: i64 = 10_000_000;
+
+/// Seconds between 0001-01-01 and 1970-01-01.
+const UNIX_EPOCH_SECONDS: i64 = 62_135_596_800;
+
+/// The JSON output of a replay calculation.
+#[derive(Serialize)]
+struct ReplayOutput {
+    date: String,
+    #[serde(flatten)]
+    output: Output,
+}
+
+/// The score related header of an .osr file.
+#[derive(Clone, Debug)]
+pub struct ReplayHeader {
+    pub mode: GameMode,
+    pub n300: u16,
+    pub n100: u16,
+    pub n50: u16,
+    pub n_geki: u16,
+    pub n_katu: u16,
+    pub n_misses: u16,
+    pub score: u32,
+    pub max_combo: u16,
+    pub mods: u32,
+    /// The unix timestamp in seconds at which the replay was set.
+    pub timestamp: i64,
+}
+
+impl ReplayHeader {
+    /// Parse the header of an .osr file's content.
+    pub fn parse(bytes: &[u8]) -> IoResult<Self> {
+        let mut reader = Reader(bytes);
+
+        let mode = match reader.u8()? {
+            0 => GameMode::Osu,
+            1 => GameMode::Taiko,
+            2 => GameMode::Catch,
+            3 => GameMode::Mania,
+            mode => return Err(invalid(format!("invalid mode {}", mode))),
+        };
+
+        let _game_version = reader.u32()?;
+        let _map_md5 = reader.string()?;
+        let _player = reader.string()?;
+        let _replay_md5 = reader.string()?;
+
+        let n300 = reader.u16()?;
+        let n100 = reader.u16()?;
+        let n50 = reader.u16()?;
+        let n_geki = reader.u16()?;
+        let n_katu = reader.u16()?;
+        let n_misses = reader.u16()?;
+        let score = reader.u32()?;
+        let max_combo = reader.u16()?;
+        let _perfect = reader.u8()?;
+        let mods = reader.u32()?;
+        let _life_bar = reader.string()?;
+        let timestamp = reader.i64()? / TICKS_PER_SECOND - UNIX_EPOCH_SECONDS;
+
+        Ok(Self {
+            mode,
+            n300,
+            n100,
+            n50,
+            n_geki,
+            n_katu,
+            n_misses,
+            score,
+            max_combo,
+            mods,
+            timestamp,
+        })
+    }
+
+    /// The score of the replay as calculator input.
+    pub fn score_args(&self) -> ScoreArgs {
+        let mut score = ScoreArgs {
+            mods: Some(self.mods.to_string()),
+            combo: Some(self.max_combo as usize),
+            misses: Some(self.n_misses as usize),
+            ..Default::default()
+        };
+
+        match self.mode {
+            GameMode::Osu => {
+                score.n300 = Some(self.n300 as usize);
+                score.n100 = Some(self.n100 as usize);
+                score.n50 = Some(self.n50 as usize);
+            }
+            GameMode::Taiko => {
+                score.n300 = Some(self.n300 as usize);
+                score.n100 = Some(self.n100 as usize);
+            }
+            GameMode::Catch => {
+                let hits = self.n300 as f64 + self.n100 as f64 + self.n50 as f64;
+                let total = hits + self.n_katu as f64 + self.n_misses as f64;
+
+                score.acc = Some(100.0 * hits / total.max(1.0));
+            }
+            GameMode::Mania => {
+                score.score = Some(self.score);
+
+                // The accuracy as rated by the 2022 version, i.e. 320s are worth more than 300s
+                let points = 320.0 * self.n_geki as f64
+                    + 300.0 * self.n300 as f64
+                    + 200.0 * self.n_katu as f64
+                    + 100.0 * self.n100 as f64
+                    + 50.0 * self.n50 as f64;
+
+                let total = self.n_geki as f64
+                    + self.n300 as f64
+                    + self.n_katu as f64
+                    + self.n100 as f64
+                    + self.n50 as f64
+                    + self.n_misses as f64;
+
+                score.acc = Some(100.0 * points / (320.0 * total).max(1.0));
+            }
+        }
+
+        score
+    }
+}
+
+/// Print the pp of a replay for the version that was live at the replay's date.
+pub async fn run(args: &Args) -> CliResult<()> {
+    let replay_path = args.positional(1).ok_or("missing replay path")?;
+    let map_path = args.positional(2).ok_or("missing map path")?;
+
+    let replay = ReplayHeader::parse(&fs::read(replay_path)?)?;
+    let map = crate::load_map(map_path).await?;
+
+    if replay.mode != map.mode {
+        return Err(format!(
+            "the replay is for {:?} but the map is for {:?}",
+            replay.mode, map.mode
+        )
+        .into());
+    }
+
+    let days = date::timestamp_days(replay.timestamp);
+
+    let version = match args.get("version") {
+        Some(name) => version::from_name(map.mode, name)?,
+        None => version::at(map.mode, days)?,
+    };
+
+    let output = version::calculate(version, &map, &replay.score_args())?;
+    let (year, month, day) = date::civil_from_days(days);
+    let date = format!("{:04}-{:02}-{:02}", year, month, day);
+
+    if args.flag("json") {
+        return crate::print_json(&ReplayOutput { date, output });
+    }
+
+    println!("Date:         {}", date);
+    println!("{}", output);
+
+    Ok(())
+}
+
+fn invalid(msg: String) -> IoError {
+    IoError::new(ErrorKind::InvalidData, msg)
+}
+
+struct Reader<'b>(&'b [u8]);
+
+impl Reader<'_> {
+    fn take<const N: usize>(&mut self) -> IoResult<[u8; N]> {
+        if self.0.len() < N {
+            return Err(IoError::from(ErrorKind::UnexpectedEof));
+        }
+
+        let (bytes, rest) = self.0.split_at(N);
+        self.0 = rest;
+
+        Ok(bytes.try_into().unwrap())
+    }
+
+    fn u8(&mut self) -> IoResult<u8> {
+        self.take::<1>().map(|[byte]| byte)
+    }
+
+    fn u16(&mut self) -> IoResult<u16> {
+        self.take().map(u16::from_le_bytes)
+    }
+
+    fn u32(&mut self) -> IoResult<u32> {
+        self.take().map(u32::from_le_bytes)
+    }
+
+    fn i64(&mut self) -> IoResult<i64> {
+        self.take().map(i64::from_le_bytes)
+    }
+
+    fn uleb128(&mut self) -> IoResult<usize> {
+        let mut value = 0;
+        let mut shift = 0;
+
+        loop {
+            let byte = self.u8()?;
+            value |= ((byte & 0x7F) as usize) << shift;
+
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+
+            shift += 7;
+
+            if shift >= usize::BITS {
+                return Err(invalid("invalid string length".to_owned()));
+            }
+        }
+    }
+
+    fn string(&mut self) -> IoResult<String> {
+        match self.u8()? {
+            0x00 => Ok(String::new()),
+            0x0B => {
+                let len = self.uleb128()?;
+
+                if self.0.len() < len {
+                    return Err(IoError::from(ErrorKind::UnexpectedEof));
+                }
+
+                let (bytes, rest) = self.0.split_at(len);
+                self.0 = rest;
+
+                String::from_utf8(bytes.to_vec()).map_err(|err| invalid(err.to_string()))
+            }
+            byte => Err(invalid(format!("invalid string indicator {}", byte))),
+        }
+    }
+}
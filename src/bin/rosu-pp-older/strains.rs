@@ -0,0 +1,123 @@
+use std::{
+    fmt::Write as _,
+    fs,
+    io::{self, BufWriter, Write},
+};
+
+use rosu_pp_older::Version;
+use serde::Serialize;
+
+use crate::{args::Args, version, CliResult};
+
+const SVG_WIDTH: f64 = 600.0;
+const SVG_HEIGHT: f64 = 100.0;
+
+/// The section strain peaks of a map for one version.
+#[derive(Clone, Debug, Serialize)]
+pub struct Strains {
+    #[serde(serialize_with = "version::serialize")]
+    pub version: Version,
+    pub section_len: f64,
+    pub aim: Vec<f64>,
+    pub speed: Vec<f64>,
+}
+
+/// Dump the aim and speed strain peaks of each section of a map.
+pub async fn run(args: &Args) -> CliResult<()> {
+    let path = args.positional(1).ok_or("missing map path")?;
+    let map = crate::load_map(path).await?;
+    let version = crate::select_version(&map, args)?;
+
+    let mods = match args.get("mods") {
+        Some(mods) => version::parse_mods(version, mods)?,
+        None => 0,
+    };
+
+    let strains = version::strains(version, &map, mods)
+        .ok_or("strains are only available for osu!standard versions")?;
+
+    let csv = match args.get("format") {
+        _ if args.flag("json") => false,
+        None | Some("json") => false,
+        Some("csv") => true,
+        Some(other) => return Err(format!("unknown format `{}`", other).into()),
+    };
+
+    match args.get("output") {
+        Some(path) => {
+            let file = BufWriter::new(fs::File::create(path)?);
+            write_strains(&strains, csv, file)?;
+        }
+        None => {
+            let stdout = io::stdout();
+            write_strains(&strains, csv, stdout.lock())?;
+        }
+    }
+
+    if let Some(path) = args.get("svg") {
+        fs::write(path, sparkline(&strains))?;
+    }
+
+    Ok(())
+}
+
+fn write_strains(strains: &Strains, csv: bool, mut writer: impl Write) -> CliResult<()> {
+    if !csv {
+        serde_json::to_writer_pretty(&mut writer, strains)?;
+        writeln!(writer)?;
+
+        return Ok(());
+    }
+
+    writeln!(writer, "section,time,aim,speed")?;
+
+    let sections = strains.aim.iter().zip(strains.speed.iter());
+
+    for (i, (aim, speed)) in sections.enumerate() {
+        let time = i as f64 * strains.section_len;
+        writeln!(writer, "{},{},{},{}", i, time, aim, speed)?;
+    }
+
+    Ok(())
+}
+
+/// Draw the aim and speed strains as two polylines of a small SVG image.
+fn sparkline(strains: &Strains) -> String {
+    let max = strains
+        .aim
+        .iter()
+        .chain(strains.speed.iter())
+        .copied()
+        .fold(0.0, f64::max);
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {} {}\">\n",
+        SVG_WIDTH, SVG_HEIGHT
+    );
+
+    for (peaks, color) in [(&strains.aim, "#e74c3c"), (&strains.speed, "#3498db")].iter() {
+        let step = SVG_WIDTH / (peaks.len().max(2) - 1) as f64;
+        let mut points = String::new();
+
+        for (i, peak) in peaks.iter().enumerate() {
+            let y = if max > 0.0 {
+                SVG_HEIGHT * (1.0 - peak / max)
+            } else {
+                SVG_HEIGHT
+            };
+
+            let _ = write!(points, "{:.1},{:.1} ", i as f64 * step, y);
+        }
+
+        let _ = writeln!(
+            svg,
+            "  <polyline fill=\"none\" stroke=\"{}\" points=\"{}\"/>",
+            color,
+            points.trim_end()
+        );
+    }
+
+    svg.push_str("</svg>\n");
+
+    svg
+}
@@ -0,0 +1,226 @@
+use rosu_pp::{Beatmap, GameMode};
+use rosu_pp_older::*;
+use serde::Serializer;
+
+use crate::{args::ScoreArgs, output::Output, strains::Strains};
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// The version of a mode as specified through `--version`.
+///
+/// Besides the name within the mode, e.g. `2015-april`, the version may be specified
+/// through its module name or a date, see [`Version`]'s `FromStr` implementation.
+/// Custom versions are not registered by the binary so they are never found.
+pub fn from_name(mode: GameMode, name: &str) -> Result<Version, String> {
+    let unknown = || format!("unknown version `{}` for {:?} maps", name, mode);
+
+    match Version::versions(mode).find(|version| version.name() == name) {
+        Some(version) => Ok(version),
+        None => match name.parse::<Version>() {
+            Ok(version) if version.mode() == mode => Ok(version),
+            _ => Err(unknown()),
+        },
+    }
+}
+
+/// The version of a mode that was live at the given days since 1970-01-01.
+pub fn at(mode: GameMode, days: i64) -> Result<Version, String> {
+    Version::from_date(mode, days * SECONDS_PER_DAY)
+        .ok_or_else(|| format!("no version for {:?} maps", mode))
+}
+
+/// The most recent version of a mode.
+pub fn latest(mode: GameMode) -> Result<Version, String> {
+    Version::latest(mode).ok_or_else(|| format!("no version for {:?} maps", mode))
+}
+
+/// Parse mods through either their bit value or their acronyms.
+pub fn parse_mods(version: Version, mods: &str) -> Result<u32, Error> {
+    match mods.parse() {
+        Ok(bits) => Ok(bits),
+        Err(_) => version.parse_mods(mods),
+    }
+}
+
+/// Serialize a version through its name within its mode.
+pub fn serialize<S: Serializer>(version: &Version, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(version.name())
+}
+
+/// The section strain peaks of the map, `None` for versions other than osu!standard.
+pub fn strains(version: Version, map: &Beatmap, mods: u32) -> Option<Strains> {
+    macro_rules! osu {
+        ($module:ident) => {{
+            let strains = $module::strains(map, mods);
+
+            Strains {
+                version,
+                section_len: strains.section_len,
+                aim: strains.aim,
+                speed: strains.speed,
+            }
+        }};
+    }
+
+    let strains = match version {
+        Version::Osu2012Tp => osu!(osu_2012_tp),
+        Version::Osu2014February => osu!(osu_2014_february),
+        Version::Osu2014May => osu!(osu_2014_may),
+        Version::Osu2014July => osu!(osu_2014_july),
+        Version::Osu2015February => osu!(osu_2015_february),
+        Version::Osu2015April => osu!(osu_2015_april),
+        Version::Osu2018 => osu!(osu_2018),
+        Version::Osu2019 => osu!(osu_2019),
+        Version::Osu2021January => osu!(osu_2021_january),
+        Version::Osu2021July => osu!(osu_2021_july),
+        Version::Osu2021November => osu!(osu_2021_november),
+        Version::Osu2022September => osu!(osu_2022_september),
+        _ => return None,
+    };
+
+    Some(strains)
+}
+
+/// The stars of the map for the version and the mods of the score.
+pub fn stars(version: Version, map: &Beatmap, score: &ScoreArgs) -> Result<f64, Error> {
+    let mut calc = AnyStars::new(map).version(version);
+
+    if let Some(ref mods) = score.mods {
+        calc = calc.mods(parse_mods(version, mods)?);
+    }
+
+    if let Some(passed_objects) = score.passed_objects {
+        calc = calc.passed_objects(passed_objects);
+    }
+
+    calc.calculate().map(|attrs| attrs.stars())
+}
+
+/// Calculate the score with the version's pp calculator.
+pub fn calculate(version: Version, map: &Beatmap, score: &ScoreArgs) -> Result<Output, Error> {
+    let attrs = calculator(version, map, score)?.calculate()?;
+
+    Ok(from_attributes(version, attrs))
+}
+
+/// The version's pp calculator for the score.
+pub fn calculator<'map>(
+    version: Version,
+    map: &'map Beatmap,
+    score: &ScoreArgs,
+) -> Result<AnyPP<'map>, Error> {
+    let mut calc = AnyPP::new(map, version);
+
+    if let Some(ref mods) = score.mods {
+        calc = calc.mods(parse_mods(version, mods)?);
+    }
+
+    if let Some(passed_objects) = score.passed_objects {
+        calc = calc.passed_objects(passed_objects);
+    }
+
+    if let Some(combo) = score.combo {
+        calc = calc.combo(combo);
+    }
+
+    if let Some(n300) = score.n300 {
+        calc = calc.n300(n300);
+    }
+
+    if let Some(n100) = score.n100 {
+        calc = calc.n100(n100);
+    }
+
+    if let Some(n50) = score.n50 {
+        calc = calc.n50(n50);
+    }
+
+    if let Some(misses) = score.misses {
+        calc = calc.misses(misses);
+    }
+
+    if let Some(points) = score.score {
+        calc = calc.score(points);
+    }
+
+    if let Some(acc) = score.acc {
+        calc = calc.accuracy(acc);
+    }
+
+    Ok(calc)
+}
+
+/// The output of the version's performance attributes.
+pub fn from_attributes(version: Version, attrs: AnyPerformanceAttributes) -> Output {
+    let output = Output {
+        stars: attrs.stars(),
+        pp: attrs.pp(),
+        ..Output::new(version)
+    };
+
+    macro_rules! osu {
+        ($attrs:ident) => {
+            Output {
+                pp_aim: Some($attrs.pp_aim),
+                pp_speed: Some($attrs.pp_speed),
+                pp_acc: Some($attrs.pp_acc),
+                pp_flashlight: Some($attrs.pp_flashlight),
+                attributes: serde_json::to_value(&$attrs).ok(),
+                ..output
+            }
+        };
+    }
+
+    macro_rules! taiko {
+        ($attrs:ident) => {
+            Output {
+                pp_acc: Some($attrs.pp_acc),
+                pp_strain: Some($attrs.pp_strain),
+                attributes: serde_json::to_value(&$attrs).ok(),
+                ..output
+            }
+        };
+    }
+
+    match attrs {
+        AnyPerformanceAttributes::Osu2012Tp(attrs) => osu!(attrs),
+        AnyPerformanceAttributes::Osu2014February(attrs) => osu!(attrs),
+        AnyPerformanceAttributes::Osu2014May(attrs) => osu!(attrs),
+        AnyPerformanceAttributes::Osu2014July(attrs) => osu!(attrs),
+        AnyPerformanceAttributes::Osu2015February(attrs) => osu!(attrs),
+        AnyPerformanceAttributes::Osu2015April(attrs) => osu!(attrs),
+        AnyPerformanceAttributes::Osu2018(attrs) => osu!(attrs),
+        AnyPerformanceAttributes::Osu2019(attrs) => osu!(attrs),
+        AnyPerformanceAttributes::Osu2021January(attrs) => osu!(attrs),
+        AnyPerformanceAttributes::Osu2021July(attrs) => osu!(attrs),
+        AnyPerformanceAttributes::Osu2021November(attrs) => osu!(attrs),
+        AnyPerformanceAttributes::Osu2022September(attrs) => osu!(attrs),
+        AnyPerformanceAttributes::TaikoPpv1(attrs) => taiko!(attrs),
+        AnyPerformanceAttributes::Taiko2020(attrs) => taiko!(attrs),
+        AnyPerformanceAttributes::Taiko2022(attrs) => taiko!(attrs),
+        // Both mania versions before 2022 share their pp components with taiko
+        AnyPerformanceAttributes::ManiaPpv1(attrs) => taiko!(attrs),
+        AnyPerformanceAttributes::Mania2018(attrs) => taiko!(attrs),
+        AnyPerformanceAttributes::Mania2022(attrs) => Output {
+            pp_strain: Some(attrs.pp_difficulty),
+            attributes: serde_json::to_value(&attrs).ok(),
+            ..output
+        },
+        // The catch attributes are rosu-pp's own and can't be serialized
+        AnyPerformanceAttributes::FruitsPpv1(attrs)
+        | AnyPerformanceAttributes::Catch2020(attrs) => Output {
+            attributes: Some(serde_json::json!({
+                "difficulty": {
+                    "stars": attrs.difficulty.stars,
+                    "ar": attrs.difficulty.ar,
+                    "n_fruits": attrs.difficulty.n_fruits,
+                    "n_droplets": attrs.difficulty.n_droplets,
+                    "n_tiny_droplets": attrs.difficulty.n_tiny_droplets,
+                    "max_combo": attrs.difficulty.max_combo(),
+                },
+                "pp": attrs.pp,
+            })),
+            ..output
+        },
+    }
+}
@@ -0,0 +1,68 @@
+use std::time::Duration;
+
+use rosu_pp::Beatmap;
+use rosu_pp_older::Version;
+
+use crate::{
+    args::{Args, ScoreArgs},
+    version, CliResult,
+};
+
+/// Re-print the stars of all versions whenever the map file changes.
+pub async fn run(args: &Args) -> CliResult<()> {
+    let path = args.positional(1).ok_or("missing map path")?;
+
+    if path == "-" {
+        return Err("only map files can be watched".into());
+    }
+
+    let interval = Duration::from_millis(args.value("interval")?.unwrap_or(500));
+    let score = ScoreArgs::new(args)?;
+    let json = args.flag("json");
+
+    let mut last_modified = None;
+
+    loop {
+        let modified = tokio::fs::metadata(path).await?.modified()?;
+
+        if last_modified != Some(modified) {
+            last_modified = Some(modified);
+
+            match Beatmap::from_path(path).await {
+                Ok(map) => print_stars(&map, &score, json),
+                Err(err) => eprintln!("failed to parse {}: {}", path, err),
+            }
+        }
+
+        tokio::time::sleep(interval).await;
+    }
+}
+
+fn print_stars(map: &Beatmap, score: &ScoreArgs, json: bool) {
+    let versions = Version::versions(map.mode);
+
+    // One line per save so the output can be consumed as a JSON stream
+    if json {
+        let outputs: Vec<_> = versions
+            .filter_map(|version| version::calculate(version, map, score).ok())
+            .collect();
+
+        match serde_json::to_string(&outputs) {
+            Ok(line) => println!("{}", line),
+            Err(err) => eprintln!("failed to serialize: {}", err),
+        }
+
+        return;
+    }
+
+    println!("{:<16}{:>10}", "Version", "Stars");
+
+    for version in versions {
+        match version::stars(version, map, score) {
+            Ok(stars) => println!("{:<16}{:>10.2}", version.name(), stars),
+            Err(err) => println!("{:<16}{}", version.name(), err),
+        }
+    }
+
+    println!();
+}
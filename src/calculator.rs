@@ -0,0 +1,124 @@
+use rosu_pp::{Beatmap, GameMode};
+
+use crate::{AnyDifficultyAttributes, AnyStars, Error, Version};
+
+/// Object-safe difficulty calculation so that the version can be chosen at runtime,
+/// e.g. by holding a `Box<dyn DifficultyCalculator>` per era.
+///
+/// Implemented by [`Version`] and by the calculator of each version module,
+/// e.g. [`osu_2019::OsuCalculator`](crate::osu_2019::OsuCalculator).
+pub trait DifficultyCalculator: Send + Sync {
+    /// The mode that the calculator calculates.
+    fn mode(&self) -> GameMode;
+
+    /// Calculate the difficulty attributes of a map.
+    ///
+    /// In case of a partial play, e.g. a fail, one can specify the amount of passed objects.
+    fn calculate(
+        &self,
+        map: &Beatmap,
+        mods: u32,
+        passed_objects: Option<usize>,
+    ) -> Result<AnyDifficultyAttributes, Error>;
+}
+
+impl DifficultyCalculator for Version {
+    #[inline]
+    fn mode(&self) -> GameMode {
+        Version::mode(*self)
+    }
+
+    fn calculate(
+        &self,
+        map: &Beatmap,
+        mods: u32,
+        passed_objects: Option<usize>,
+    ) -> Result<AnyDifficultyAttributes, Error> {
+        let calculator = AnyStars::new(map).version(*self).mods(mods);
+
+        match passed_objects {
+            Some(passed_objects) => calculator.passed_objects(passed_objects).calculate(),
+            None => calculator.calculate(),
+        }
+    }
+}
+
+/// Declare the zero-sized calculator of a version module.
+macro_rules! difficulty_calculator {
+    ($calculator:ident, $version:ident) => {
+        /// The difficulty calculator of this version as
+        /// [`DifficultyCalculator`](crate::DifficultyCalculator).
+        #[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+        pub struct $calculator;
+
+        impl crate::DifficultyCalculator for $calculator {
+            #[inline]
+            fn mode(&self) -> rosu_pp::GameMode {
+                MODE
+            }
+
+            #[inline]
+            fn calculate(
+                &self,
+                map: &rosu_pp::Beatmap,
+                mods: u32,
+                passed_objects: Option<usize>,
+            ) -> Result<crate::AnyDifficultyAttributes, crate::Error> {
+                crate::DifficultyCalculator::calculate(
+                    &crate::Version::$version,
+                    map,
+                    mods,
+                    passed_objects,
+                )
+            }
+        }
+    };
+}
+
+pub(crate) use difficulty_calculator;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn boxed() {
+        let calculators: Vec<Box<dyn DifficultyCalculator>> = Version::osu_versions()
+            .map(|version| Box::new(version) as Box<dyn DifficultyCalculator>)
+            .collect();
+
+        let map = Beatmap::default();
+
+        for calculator in calculators {
+            let attrs = calculator.calculate(&map, 0, None).unwrap();
+
+            assert_eq!(attrs.mode(), calculator.mode());
+        }
+    }
+
+    #[cfg(all(feature = "osu-2019", feature = "taiko-2022", feature = "catch-2020"))]
+    #[test]
+    fn module_calculators() {
+        use crate::{
+            catch_2020::CatchCalculator, osu_2019::OsuCalculator, taiko_2022::TaikoCalculator,
+        };
+
+        let calculators: Vec<(Box<dyn DifficultyCalculator>, Version)> = vec![
+            (Box::new(OsuCalculator), Version::Osu2019),
+            (Box::new(TaikoCalculator), Version::Taiko2022),
+            (Box::new(CatchCalculator), Version::Catch2020),
+        ];
+
+        let map = Beatmap::default();
+
+        for (calculator, version) in calculators {
+            assert_eq!(calculator.mode(), version.mode());
+
+            let attrs = calculator.calculate(&map, 0, None).unwrap();
+            let expected = DifficultyCalculator::calculate(&version, &map, 0, None).unwrap();
+
+            assert_eq!(attrs.version(), expected.version());
+            assert_eq!(attrs.stars(), expected.stars());
+        }
+    }
+}
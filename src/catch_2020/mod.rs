@@ -0,0 +1,547 @@
+mod catch_object;
+mod difficulty_object;
+mod movement;
+mod pp;
+
+use std::{iter::Map, vec::IntoIter};
+
+use catch_object::CatchObject;
+use difficulty_object::DifficultyObject;
+use movement::Movement;
+pub use pp::*;
+
+use rosu_pp::{
+    catch::{CatchDifficultyAttributes, CatchPerformanceAttributes, CatchScoreState},
+    parse::{HitObjectKind, Pos2},
+    Beatmap, GameMode, Mods,
+};
+
+use crate::{
+    mods::{self, ScoreMultipliers},
+    util::{
+        curve::{Curve, CurveBuffers},
+        mode::check_mode,
+    },
+    Error,
+};
+
+const SECTION_LENGTH: f64 = 750.0;
+const STAR_SCALING_FACTOR: f64 = 0.153;
+
+const CATCHER_SIZE: f32 = 106.75;
+
+const LEGACY_LAST_TICK_OFFSET: f64 = 36.0;
+const BASE_SCORING_DISTANCE: f64 = 100.0;
+
+/// The approximate date at which this version went live, formatted as `YYYY-MM-DD`.
+pub const VERSION_DATE: &str = "2020-01-01";
+
+/// The name of this version within its mode.
+pub const VERSION_NAME: &str = "2020";
+
+/// A short description of what changed in this version.
+pub const VERSION_DESCRIPTION: &str =
+    "Strains are capped at 375 BPM streams and the length bonus is rebalanced.";
+
+/// The mode this version calculates, i.e. osu!ctb.
+pub const MODE: GameMode = GameMode::Catch;
+
+crate::calculator::difficulty_calculator!(CatchCalculator, Catch2020);
+
+/// Parse mod acronyms like `"HDDT"` or `"HD,DT"` into the mods value of this version.
+///
+/// Mods that did not exist yet at the time of this version cause an error.
+#[inline]
+pub fn parse_mods(acronyms: &str) -> Result<u32, Error> {
+    mods::parse_available(acronyms, mods::BEFORE_MIRROR)
+}
+
+/// The score multiplier of the mods in this version.
+#[inline]
+pub fn score_multiplier(mods: u32) -> f32 {
+    ScoreMultipliers::CATCH.multiplier(mods)
+}
+
+/// Calculate the difficulty and the performance of a score in one pass.
+///
+/// Calling [`stars`] and then [`CatchPP`] without passing the attributes along calculates
+/// the difficulty twice, this function avoids that.
+pub fn stars_and_pp(
+    map: &Beatmap,
+    mods: u32,
+    state: CatchScoreState,
+) -> (CatchDifficultyAttributes, CatchPerformanceAttributes) {
+    let CatchScoreState {
+        max_combo,
+        n_fruits,
+        n_droplets,
+        n_tiny_droplets,
+        n_tiny_droplet_misses,
+        n_misses,
+    } = state;
+
+    let performance = CatchPP::new(map)
+        .mods(mods)
+        .combo(max_combo)
+        .fruits(n_fruits)
+        .droplets(n_droplets)
+        .tiny_droplets(n_tiny_droplets)
+        .tiny_droplet_misses(n_tiny_droplet_misses)
+        .misses(n_misses)
+        .calculate();
+
+    (performance.difficulty.clone(), performance)
+}
+
+/// Difficulty calculator on osu!ctb maps.
+///
+/// # Example
+///
+/// ```
+/// use rosu_pp::Beatmap;
+/// use rosu_pp_older::catch_2020::CatchStars;
+///
+/// # /*
+/// let map: Beatmap = ...
+/// # */
+/// # let map = Beatmap::default();
+///
+/// let difficulty_attrs = CatchStars::new(&map)
+///     .mods(8 + 64) // HDDT
+///     .calculate();
+///
+/// println!("Stars: {}", difficulty_attrs.stars);
+/// ```
+#[derive(Clone, Debug)]
+pub struct CatchStars<'map> {
+    map: &'map Beatmap,
+    mods: u32,
+    passed_objects: Option<usize>,
+    clock_rate: Option<f64>,
+}
+
+impl<'map> CatchStars<'map> {
+    /// Create a new difficulty calculator for osu!ctb maps.
+    #[inline]
+    pub fn new(map: &'map Beatmap) -> Self {
+        Self {
+            map,
+            mods: 0,
+            passed_objects: None,
+            clock_rate: None,
+        }
+    }
+
+    /// Specify mods through their bit values.
+    ///
+    /// See [https://github.com/ppy/osu-api/wiki#mods](https://github.com/ppy/osu-api/wiki#mods)
+    #[inline]
+    pub fn mods(mut self, mods: u32) -> Self {
+        self.mods = mods;
+
+        self
+    }
+
+    /// Specify mods through their acronyms, e.g. `"HDDT"`.
+    ///
+    /// Returns an error for unknown acronyms and for mods
+    /// that did not exist yet at the time of this version.
+    #[inline]
+    pub fn mods_str(self, acronyms: &str) -> Result<Self, Error> {
+        parse_mods(acronyms).map(|mods| self.mods(mods))
+    }
+
+    /// Amount of passed objects for partial plays, e.g. a fail.
+    #[inline]
+    pub fn passed_objects(mut self, passed_objects: usize) -> Self {
+        self.passed_objects = Some(passed_objects);
+
+        self
+    }
+
+    /// Adjust the clock rate used in the calculation, e.g. `1.4` for a lazer DT
+    /// score that was set at 1.4x.
+    ///
+    /// If none is specified, the clock rate of the mods is used.
+    #[inline]
+    pub fn clock_rate(mut self, clock_rate: f64) -> Self {
+        self.clock_rate = Some(clock_rate);
+
+        self
+    }
+
+    /// Calculate all difficulty related values, including stars.
+    ///
+    /// Maps of other modes, except for converted osu!standard maps, result in default attributes.
+    #[inline]
+    pub fn calculate(self) -> CatchDifficultyAttributes {
+        let clock_rate = self.clock_rate.unwrap_or_else(|| self.mods.clock_rate());
+
+        stars_with_clock_rate(self.map, self.mods, self.passed_objects, clock_rate)
+    }
+}
+
+/// Star calculation for osu!ctb maps
+///
+/// In case of a partial play, e.g. a fail, one can specify the amount of passed objects.
+///
+/// Maps of other modes, except for converted osu!standard maps, result in default attributes.
+pub fn stars(map: &Beatmap, mods: u32, passed_objects: Option<usize>) -> CatchDifficultyAttributes {
+    stars_with_clock_rate(map, mods, passed_objects, mods.clock_rate())
+}
+
+/// Star calculation for osu!ctb maps with a custom clock rate.
+///
+/// The clock rate replaces the one of DT or HT, e.g. `1.4` for a lazer DT
+/// score that was set at 1.4x.
+pub fn stars_with_clock_rate(
+    map: &Beatmap,
+    mods: u32,
+    passed_objects: Option<usize>,
+    clock_rate: f64,
+) -> CatchDifficultyAttributes {
+    if map.hit_objects.len() < 2 || check_mode(map.mode, MODE).is_err() {
+        return CatchDifficultyAttributes::default();
+    }
+
+    let take = passed_objects.unwrap_or(usize::MAX);
+    let map_attributes = map.attributes().mods(mods).clock_rate(clock_rate).build();
+
+    let attributes = CatchDifficultyAttributes {
+        ar: map_attributes.ar,
+        ..Default::default()
+    };
+
+    let mut params = FruitParams {
+        attributes,
+        curve_bufs: CurveBuffers::default(),
+        last_pos: None,
+        last_time: 0.0,
+        ticks: Vec::new(), // using the same buffer for all sliders
+        with_hr: mods.hr(),
+    };
+
+    // BUG: Incorrect object order on 2B maps that have fruits within sliders
+    let mut hit_objects = map
+        .hit_objects
+        .iter()
+        .filter_map(|h| match &h.kind {
+            HitObjectKind::Circle => {
+                let mut h = CatchObject::new((h.pos, h.start_time));
+
+                if params.with_hr {
+                    h = h.with_hr(&mut params);
+                }
+
+                params.attributes.n_fruits += 1;
+
+                Some(FruitOrJuice::Fruit(Some(h)))
+            }
+            HitObjectKind::Slider {
+                pixel_len,
+                repeats,
+                control_points,
+                ..
+            } => {
+                // HR business
+                params.last_pos = Some(h.pos.x + control_points[control_points.len() - 1].pos.x);
+                params.last_time = h.start_time;
+
+                let span_count = (*repeats + 1) as f64;
+
+                let mut tick_dist = 100.0 * map.slider_mult / map.tick_rate;
+
+                let timing_point = map.timing_point_at(h.start_time);
+
+                let difficulty_point = map.difficulty_point_at(h.start_time).unwrap_or_default();
+
+                if map.version >= 8 {
+                    tick_dist /= (100.0 / difficulty_point.slider_vel).clamp(10.0, 1000.0) / 100.0;
+                }
+
+                // Build the curve w.r.t. the control points
+                let curve = Curve::new(control_points, *pixel_len, &mut params.curve_bufs);
+
+                let velocity =
+                    (BASE_SCORING_DISTANCE * map.slider_mult * difficulty_point.slider_vel)
+                        / timing_point.beat_len;
+
+                let end_time = h.start_time + span_count * curve.dist() / velocity;
+                let duration = end_time - h.start_time;
+                let span_duration = duration / span_count;
+
+                // * A very lenient maximum length of a slider for ticks to be generated.
+                // * This exists for edge cases such as /b/1573664 where the beatmap has
+                // * been edited by the user, and should never be reached in normal usage.
+                let max_len = 100_000.0;
+
+                let len = curve.dist().min(max_len);
+                tick_dist = tick_dist.clamp(0.0, len);
+                let min_dist_from_end = velocity * 10.0;
+
+                let mut curr_dist = tick_dist;
+                let pixel_len = pixel_len.unwrap_or(0.0);
+                let time_add = duration * tick_dist / (pixel_len * span_count);
+
+                let target = pixel_len - tick_dist / 8.0;
+
+                params.ticks.reserve((target / tick_dist) as usize);
+
+                // Tick of the first span
+                while curr_dist < len - min_dist_from_end {
+                    let progress = curr_dist / len;
+                    let pos = h.pos + curve.position_at(progress);
+                    let time = h.start_time + progress * span_duration;
+                    params.ticks.push((pos, time));
+                    curr_dist += tick_dist;
+                }
+
+                params.attributes.n_tiny_droplets += tiny_droplet_count(
+                    h.start_time,
+                    time_add,
+                    duration,
+                    span_count as usize,
+                    &params.ticks,
+                );
+
+                let mut slider_objects =
+                    Vec::with_capacity(span_count as usize * (params.ticks.len() + 1));
+                slider_objects.push((h.pos, h.start_time));
+
+                // Other spans
+                if *repeats == 0 {
+                    slider_objects.append(&mut params.ticks); // automatically empties buffer for next slider
+                } else {
+                    slider_objects.extend(&params.ticks);
+
+                    for span_idx in 1..=*repeats {
+                        let progress = (span_idx % 2 == 1) as u8 as f64;
+                        let pos = h.pos + curve.position_at(progress);
+                        let time_offset = span_duration * span_idx as f64;
+
+                        // Reverse tick
+                        slider_objects.push((pos, h.start_time + time_offset));
+
+                        let new_ticks = params.ticks.iter().enumerate().map(|(i, (pos, time))| {
+                            (*pos, *time + time_offset + time_add * i as f64)
+                        });
+
+                        // Actual ticks
+                        if span_idx & 1 == 1 {
+                            slider_objects.extend(new_ticks.rev());
+                        } else {
+                            slider_objects.extend(new_ticks);
+                        }
+                    }
+
+                    params.ticks.clear();
+                }
+
+                // Slider tail
+                let progress = (*repeats % 2 == 0) as u8 as f64;
+                let pos = h.pos + curve.position_at(progress);
+                slider_objects.push((pos, h.start_time + duration));
+
+                let new_fruits = *repeats + 2;
+                params.attributes.n_fruits += new_fruits;
+                params.attributes.n_droplets += slider_objects.len() - new_fruits;
+
+                let iter = slider_objects
+                    .into_iter()
+                    .map(CatchObject::new as fn(_) -> _);
+
+                Some(FruitOrJuice::Juice(iter))
+            }
+            HitObjectKind::Spinner { .. } | HitObjectKind::Hold { .. } => None,
+        })
+        .flatten()
+        .take(take);
+
+    // Hyper dash business
+    let base_size = calculate_catch_width(map_attributes.cs as f32) * 0.5;
+    let catcher_size = base_size;
+
+    // * For circle sizes above 5.5, reduce the catcher width further to simulate imperfect gameplay.
+    let half_catcher_width = base_size * (1.0 - (map_attributes.cs as f32 - 5.5).max(0.0) * 0.0625);
+
+    let mut last_direction = 0;
+    let mut last_excess = catcher_size;
+
+    // Strain business
+    let mut movement = Movement::new(map_attributes.clock_rate);
+    let section_len = SECTION_LENGTH * map_attributes.clock_rate;
+    let mut current_section_end =
+        (map.hit_objects[0].start_time / section_len).ceil() * section_len;
+
+    let mut prev = hit_objects.next().unwrap();
+    let mut curr = hit_objects.next().unwrap();
+
+    prev.init_hyper_dash(catcher_size, &curr, &mut last_direction, &mut last_excess);
+
+    // Handle second object separately to remove later if-branching
+    let next = hit_objects.next().unwrap();
+    curr.init_hyper_dash(catcher_size, &next, &mut last_direction, &mut last_excess);
+
+    let h = DifficultyObject::new(&curr, &prev, half_catcher_width, map_attributes.clock_rate);
+
+    while h.base.time > current_section_end {
+        current_section_end += section_len;
+    }
+
+    movement.process(&h);
+
+    prev = curr;
+    curr = next;
+
+    // Handle all other objects
+    for next in hit_objects {
+        curr.init_hyper_dash(catcher_size, &next, &mut last_direction, &mut last_excess);
+
+        let h = DifficultyObject::new(&curr, &prev, half_catcher_width, map_attributes.clock_rate);
+
+        while h.base.time > current_section_end {
+            movement.save_current_peak();
+            movement.start_new_section_from(current_section_end);
+            current_section_end += section_len;
+        }
+
+        movement.process(&h);
+
+        prev = curr;
+        curr = next;
+    }
+
+    // Same as in loop but without init_hyper_dash because `curr` is the last element
+    let h = DifficultyObject::new(&curr, &prev, half_catcher_width, map_attributes.clock_rate);
+
+    while h.base.time > current_section_end {
+        movement.save_current_peak();
+        movement.start_new_section_from(current_section_end);
+
+        current_section_end += section_len;
+    }
+
+    movement.process(&h);
+    movement.save_current_peak();
+
+    params.attributes.stars = movement.difficulty_value().sqrt() * STAR_SCALING_FACTOR;
+
+    params.attributes
+}
+
+// BUG: Sometimes there are off-by-one errors,
+// presumably caused by floating point inaccuracies
+fn tiny_droplet_count(
+    start_time: f64,
+    time_between_ticks: f64,
+    duration: f64,
+    span_count: usize,
+    ticks: &[(Pos2, f64)],
+) -> usize {
+    // tiny droplets preceeding a _tick_
+    let per_tick = if !ticks.is_empty() && time_between_ticks > 80.0 {
+        let time_between_tiny = shrink_down(time_between_ticks);
+
+        // add a little for floating point inaccuracies
+        let start = time_between_tiny + 0.001;
+
+        count_iterations(start, time_between_tiny, time_between_ticks)
+    } else {
+        0
+    };
+
+    // tiny droplets preceeding a _reverse_
+    let last = ticks.last().map_or(start_time, |(_, last)| *last);
+    let repeat_time = start_time + duration / span_count as f64;
+    let since_last_tick = repeat_time - last;
+
+    let span_last_section = if since_last_tick > 80.0 {
+        let time_between_tiny = shrink_down(since_last_tick);
+
+        count_iterations(time_between_tiny, time_between_tiny, since_last_tick)
+    } else {
+        0
+    };
+
+    // tiny droplets preceeding the slider tail
+    // necessary to handle distinctly because of the legacy last tick
+    let last = ticks.last().map_or(start_time, |(_, last)| *last);
+    let end_time = start_time + duration / span_count as f64 - LEGACY_LAST_TICK_OFFSET;
+    let since_last_tick = end_time - last;
+
+    let last_section = if since_last_tick > 80.0 {
+        let time_between_tiny = shrink_down(since_last_tick);
+
+        count_iterations(time_between_tiny, time_between_tiny, since_last_tick)
+    } else {
+        0
+    };
+
+    // Combine tiny droplets counts
+    per_tick * ticks.len() * span_count
+        + span_last_section * (span_count.saturating_sub(1))
+        + last_section
+}
+
+#[inline]
+fn shrink_down(mut val: f64) -> f64 {
+    while val > 100.0 {
+        val /= 2.0;
+    }
+
+    val
+}
+
+#[inline]
+fn count_iterations(mut start: f64, step: f64, end: f64) -> usize {
+    let mut count = 0;
+
+    while start < end {
+        count += 1;
+        start += step;
+    }
+
+    count
+}
+
+#[inline]
+fn calculate_catch_width(cs: f32) -> f32 {
+    CATCHER_SIZE * (1.0 - 0.7 * (cs - 5.0) / 5.0).abs()
+}
+
+type JuiceStream = Map<IntoIter<(Pos2, f64)>, fn((Pos2, f64)) -> CatchObject>;
+
+pub(crate) enum FruitOrJuice {
+    Fruit(Option<CatchObject>),
+    Juice(JuiceStream),
+}
+
+impl Iterator for FruitOrJuice {
+    type Item = CatchObject;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Self::Fruit(fruit) => fruit.take(),
+            Self::Juice(slider) => slider.next(),
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            Self::Fruit(Some(_)) => (1, Some(1)),
+            Self::Fruit(None) => (0, Some(0)),
+            Self::Juice(slider) => slider.size_hint(),
+        }
+    }
+}
+
+pub(crate) struct FruitParams {
+    pub(crate) attributes: CatchDifficultyAttributes,
+    pub(crate) curve_bufs: CurveBuffers,
+    pub(crate) last_pos: Option<f32>,
+    pub(crate) last_time: f64,
+    pub(crate) ticks: Vec<(Pos2, f64)>,
+    pub(crate) with_hr: bool,
+}
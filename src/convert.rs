@@ -0,0 +1,229 @@
+//! Converting osu!standard maps into the other modes.
+//!
+//! The routines follow the legacy conversion that osu!lazer reproduces in its
+//! `TaikoBeatmapConverter`, `ManiaBeatmapConverter`, and `ManiaDifficultyCalculator`.
+//! How osu!stable converted maps at the time of each version is not documented, so
+//! every version of a mode shares the same routine instead of an era-specific one.
+//! osu!catch needs no routine because its versions generate fruits, droplets, and
+//! bananas from the osu!standard objects directly.
+
+use std::borrow::Cow;
+
+use rosu_pp::{
+    parse::{HitObject, HitObjectKind},
+    Beatmap, GameMode,
+};
+
+use crate::{
+    util::{
+        curve::{Curve, CurveBuffers},
+        mods::key_count,
+    },
+    Error,
+};
+
+const LEGACY_VELOCITY_MULTIPLIER: f64 = 1.4;
+const OSU_BASE_SCORING_DISTANCE: f64 = 100.0;
+
+/// Convert an osu!standard map into an osu!taiko map.
+///
+/// Sliders that are short enough turn into a stream of circles, all other sliders
+/// become drumrolls. Maps of any other mode are returned as they are.
+pub(crate) fn taiko(map: &Beatmap) -> Cow<'_, Beatmap> {
+    if map.mode != GameMode::Osu {
+        return Cow::Borrowed(map);
+    }
+
+    let mut hit_objects = Vec::with_capacity(map.hit_objects.len());
+    let mut sounds = Vec::with_capacity(map.sounds.len());
+    let mut curve_bufs = CurveBuffers::default();
+
+    for (h, &sound) in map.hit_objects.iter().zip(map.sounds.iter()) {
+        match taiko_slider_hits(h, map, &mut curve_bufs) {
+            Some((duration, tick_spacing)) => {
+                let edge_sounds = match &h.kind {
+                    HitObjectKind::Slider { edge_sounds, .. } => edge_sounds.as_slice(),
+                    _ => &[],
+                };
+
+                let end_time = h.start_time + duration + tick_spacing / 8.0;
+                let mut time = h.start_time;
+                let mut i = 0;
+
+                while time <= end_time {
+                    hit_objects.push(HitObject {
+                        pos: h.pos,
+                        start_time: time,
+                        kind: HitObjectKind::Circle,
+                    });
+
+                    let sound = if edge_sounds.is_empty() {
+                        sound
+                    } else {
+                        edge_sounds[i % edge_sounds.len()]
+                    };
+
+                    sounds.push(sound);
+                    time += tick_spacing;
+                    i += 1;
+                }
+            }
+            None => {
+                hit_objects.push(h.clone());
+                sounds.push(sound);
+            }
+        }
+    }
+
+    let n_circles = hit_objects.iter().filter(|h| h.is_circle()).count();
+    let n_sliders = hit_objects.iter().filter(|h| h.is_slider()).count();
+
+    Cow::Owned(Beatmap {
+        mode: GameMode::Taiko,
+        hit_objects,
+        sounds,
+        n_circles: n_circles as _,
+        n_sliders: n_sliders as _,
+        ..map.clone()
+    })
+}
+
+/// The duration and the tick spacing of a slider if it should be converted
+/// into circles instead of a drumroll.
+fn taiko_slider_hits(
+    h: &HitObject,
+    map: &Beatmap,
+    curve_bufs: &mut CurveBuffers,
+) -> Option<(f64, f64)> {
+    let (pixel_len, repeats, control_points) = match &h.kind {
+        HitObjectKind::Slider {
+            pixel_len,
+            repeats,
+            control_points,
+            ..
+        } => (*pixel_len, *repeats, control_points),
+        _ => return None,
+    };
+
+    let spans = (repeats + 1) as f64;
+    let len = match pixel_len {
+        Some(len) => len,
+        None => Curve::new(control_points, None, curve_bufs).dist(),
+    };
+
+    let distance = len * spans * LEGACY_VELOCITY_MULTIPLIER;
+
+    let timing_beat_len = map.timing_point_at(h.start_time).beat_len;
+    let slider_vel = map
+        .difficulty_point_at(h.start_time)
+        .map_or(1.0, |point| point.slider_vel);
+
+    let mut beat_len = timing_beat_len / slider_vel;
+
+    let taiko_vel = OSU_BASE_SCORING_DISTANCE * map.slider_mult * LEGACY_VELOCITY_MULTIPLIER;
+    let taiko_duration = (distance / taiko_vel * beat_len).trunc();
+
+    let osu_vel = taiko_vel * 1000.0 / beat_len;
+
+    // * osu-stable always uses the speed-adjusted beatlength to determine the osu! velocity,
+    // * but only uses it for conversion if beatmap version < 8
+    if map.version >= 8 {
+        beat_len = timing_beat_len;
+    }
+
+    // * If the drum roll is to be split into hit circles, assume the ticks are 1/8 spaced within the duration of one beat
+    let tick_spacing = (beat_len / map.tick_rate).min(taiko_duration / spans);
+
+    let convert = tick_spacing > 0.0 && distance / osu_vel * 1000.0 < 2.0 * beat_len;
+
+    convert.then(|| (taiko_duration, tick_spacing))
+}
+
+/// The amount of columns of a map in osu!mania.
+///
+/// Converts pick their column count from the ratio of sliders and spinners and the OD,
+/// unless a key mod is applied.
+///
+/// Returns an error if the map is neither an osu!standard nor an osu!mania map.
+pub(crate) fn mania_columns(map: &Beatmap, mods: u32) -> Result<u8, Error> {
+    let rounded_cs = map.cs.round();
+
+    let columns = match map.mode {
+        GameMode::Mania => rounded_cs.max(1.0) as u8,
+        GameMode::Osu => {
+            let rounded_od = map.od.round();
+
+            let n_objects = map.n_circles + map.n_sliders + map.n_spinners;
+            let slider_or_spinner_ratio = (n_objects - map.n_circles) as f32 / n_objects as f32;
+
+            // Key mods only affect converts, mania maps keep their own column count
+            if let Some(keys) = key_count(mods) {
+                keys
+            } else if slider_or_spinner_ratio < 0.2 {
+                7
+            } else if slider_or_spinner_ratio < 0.3 || rounded_cs >= 5.0 {
+                6 + (rounded_od > 5.0) as u8
+            } else if slider_or_spinner_ratio > 0.6 {
+                4 + (rounded_od > 4.0) as u8
+            } else {
+                (rounded_od as u8 + 1).clamp(4, 7)
+            }
+        }
+        mode => {
+            return Err(Error::UnsupportedMode {
+                version: GameMode::Mania,
+                map: mode,
+            })
+        }
+    };
+
+    Ok(columns)
+}
+
+/// The hit window of 300s in osu!mania in milliseconds, before applying mods.
+///
+/// Converts use a fixed window depending on the rounded OD instead of the OD itself.
+pub(crate) fn mania_hit_window(map: &Beatmap) -> f64 {
+    match map.mode {
+        GameMode::Mania => 34.0 + 3.0 * (10.0 - map.od as f64).clamp(0.0, 10.0),
+        _ if map.od.round() > 4.0 => 34.0,
+        _ => 47.0,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn mania_convert_hit_window() {
+        let mut map = Beatmap {
+            od: 8.0,
+            ..Default::default()
+        };
+
+        assert_eq!(mania_hit_window(&map), 34.0);
+
+        map.od = 4.0;
+        assert_eq!(mania_hit_window(&map), 47.0);
+
+        map.mode = GameMode::Mania;
+        assert_eq!(mania_hit_window(&map), 52.0);
+    }
+
+    #[test]
+    fn mania_columns_of_other_modes() {
+        let map = Beatmap {
+            mode: GameMode::Taiko,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            mania_columns(&map, 0),
+            Err(Error::UnsupportedMode {
+                version: GameMode::Mania,
+                map: GameMode::Taiko,
+            })
+        );
+    }
+}
@@ -0,0 +1,211 @@
+//! Plugging custom versions into the crate.
+//!
+//! Private servers often run a tweaked formula of one of the versions. Instead of
+//! patching the crate, such a formula can implement [`CustomVersion`], be registered
+//! through [`register`], and then be calculated by name through
+//! [`AnyStars::calculate_custom`](crate::AnyStars::calculate_custom) and
+//! [`AnyPP::calculate_custom`](crate::AnyPP::calculate_custom).
+//!
+//! Custom versions only exist within the program that registers them, so the
+//! `rosu-pp-older` binary does not accept them and only knows the versions of this crate.
+//!
+//! The strain based building blocks are the ones of the osu!standard versions up to
+//! 2021 July, i.e. [`Skill`] together with [`StrainKind`], [`StrainValue`], and
+//! [`StrainObject`]. The preprocessing of hit objects is provided by the
+//! `difficulty_objects` function of the osu!standard versions.
+
+use std::sync::{Arc, RwLock};
+
+use rosu_pp::{Beatmap, GameMode};
+
+use crate::Error;
+
+pub use crate::util::strain::{
+    weighted_sum, Skill, StrainKind, StrainObject, StrainValue, DECAY_WEIGHT,
+};
+
+static REGISTRY: RwLock<Vec<Arc<dyn CustomVersion>>> = RwLock::new(Vec::new());
+
+/// The hit results of a score that is passed to a [`CustomVersion`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct CustomScore {
+    /// The maximum combo of the score, `None` if it was not specified.
+    pub max_combo: Option<usize>,
+    /// The amount of 300s.
+    pub n300: usize,
+    /// The amount of 100s.
+    pub n100: usize,
+    /// The amount of 50s.
+    pub n50: usize,
+    /// The amount of misses.
+    pub n_misses: usize,
+}
+
+/// A version that is not part of this crate, e.g. the formula of a private server.
+pub trait CustomVersion: Send + Sync + 'static {
+    /// The name of the version within its mode, must be unique among the custom versions.
+    fn name(&self) -> &str;
+
+    /// The mode that the version calculates.
+    fn mode(&self) -> GameMode;
+
+    /// The approximate date at which the version went live, formatted as `YYYY-MM-DD`.
+    fn date(&self) -> &str;
+
+    /// The star rating of a map.
+    fn stars(&self, map: &Beatmap, mods: u32) -> f64;
+
+    /// The performance of a score on a map.
+    fn pp(&self, map: &Beatmap, mods: u32, score: CustomScore) -> f64;
+}
+
+/// Register a custom version so that it can be found through [`custom_version`].
+///
+/// Returns [`Error::DuplicateVersion`] if a custom version with the same name
+/// and mode is registered already.
+pub fn register(version: impl CustomVersion) -> Result<(), Error> {
+    let mut registry = REGISTRY.write().unwrap_or_else(|e| e.into_inner());
+
+    let duplicate = registry
+        .iter()
+        .any(|custom| custom.mode() == version.mode() && custom.name() == version.name());
+
+    if duplicate {
+        return Err(Error::DuplicateVersion(version.name().to_owned()));
+    }
+
+    registry.push(Arc::new(version));
+
+    Ok(())
+}
+
+/// The registered custom version of a mode with the given name.
+pub fn custom_version(mode: GameMode, name: &str) -> Option<Arc<dyn CustomVersion>> {
+    REGISTRY
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .find(|custom| custom.mode() == mode && custom.name() == name)
+        .cloned()
+}
+
+/// All registered custom versions in the order of their registration.
+pub fn custom_versions() -> Vec<Arc<dyn CustomVersion>> {
+    REGISTRY.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::AnyStars;
+
+    struct Tweaked;
+
+    impl CustomVersion for Tweaked {
+        fn name(&self) -> &str {
+            "tweaked"
+        }
+
+        fn mode(&self) -> GameMode {
+            GameMode::Osu
+        }
+
+        fn date(&self) -> &str {
+            "2020-01-01"
+        }
+
+        fn stars(&self, _: &Beatmap, _: u32) -> f64 {
+            5.0
+        }
+
+        fn pp(&self, _: &Beatmap, _: u32, _: CustomScore) -> f64 {
+            100.0
+        }
+    }
+
+    #[test]
+    fn registration() {
+        assert!(register(Tweaked).is_ok());
+        assert_eq!(
+            register(Tweaked),
+            Err(Error::DuplicateVersion("tweaked".to_owned()))
+        );
+
+        let custom = custom_version(GameMode::Osu, "tweaked").unwrap();
+        assert_eq!(custom.stars(&Beatmap::default(), 0), 5.0);
+        assert!(custom_version(GameMode::Taiko, "tweaked").is_none());
+    }
+
+    struct Resolved;
+
+    impl CustomVersion for Resolved {
+        fn name(&self) -> &str {
+            "resolved"
+        }
+
+        fn mode(&self) -> GameMode {
+            GameMode::Osu
+        }
+
+        fn date(&self) -> &str {
+            "2020-01-01"
+        }
+
+        fn stars(&self, _: &Beatmap, mods: u32) -> f64 {
+            mods as f64
+        }
+
+        fn pp(&self, _: &Beatmap, _: u32, score: CustomScore) -> f64 {
+            score.n300 as f64
+        }
+    }
+
+    #[test]
+    fn calculate_by_name() {
+        // Registered by whichever test runs first
+        let _ = register(Resolved);
+
+        let map = Beatmap::default();
+
+        assert_eq!(
+            AnyStars::new(&map).mods(64).calculate_custom("resolved"),
+            Ok(64.0)
+        );
+        assert_eq!(
+            AnyStars::new(&map).calculate_custom("unregistered"),
+            Err(Error::UnknownVersion("unregistered".to_owned()))
+        );
+        assert_eq!(
+            AnyStars::new(&map)
+                .mode(GameMode::Taiko)
+                .calculate_custom("resolved"),
+            Err(Error::UnknownVersion("resolved".to_owned()))
+        );
+    }
+
+    #[cfg(feature = "osu-2019")]
+    #[test]
+    fn calculate_pp_by_name() {
+        use crate::{AnyPP, Version};
+
+        let _ = register(Resolved);
+
+        let map = Beatmap::default();
+
+        let pp = AnyPP::new(&map, Version::Osu2019)
+            .passed_objects(100)
+            .n100(5)
+            .misses(2)
+            .calculate_custom("resolved");
+
+        assert_eq!(pp, Ok(93.0));
+    }
+
+    #[test]
+    fn weighted_peaks() {
+        let difficulty = weighted_sum(&mut [1.0, 2.0, 4.0]);
+        let expected = 4.0 + 2.0 * DECAY_WEIGHT + 1.0 * DECAY_WEIGHT * DECAY_WEIGHT;
+
+        assert!((difficulty - expected).abs() < 1e-6);
+    }
+}
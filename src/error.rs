@@ -0,0 +1,56 @@
+use std::{error::Error as StdError, fmt};
+
+use rosu_pp::GameMode;
+
+/// Errors that can occur while calculating performance.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The mods contain mods that were never ranked and that
+    /// are not configured to be handled otherwise.
+    UnrankedMods(u32),
+    /// A mod acronym could not be recognized.
+    UnknownMod(String),
+    /// The mods did not exist yet at the time of the version.
+    UnavailableMods(u32),
+    /// The map's mode can neither be calculated by the version nor converted into its mode.
+    ///
+    /// Only osu!standard maps can be converted, e.g. an osu!mania map can not be
+    /// calculated by an osu!standard version.
+    UnsupportedMode {
+        /// The mode of the version.
+        version: GameMode,
+        /// The mode of the map.
+        map: GameMode,
+    },
+    /// A custom version with the same name and mode was registered already.
+    DuplicateVersion(String),
+    /// A version name could neither be recognized as module name nor as date.
+    UnknownVersion(String),
+    /// None of the mode's versions are enabled through the crate features.
+    DisabledMode(GameMode),
+    /// Mods in the JSON format of the osu! API v2 could not be deserialized.
+    InvalidModsJson(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnrankedMods(mods) => write!(f, "unranked mods {}", mods),
+            Self::UnknownMod(acronym) => write!(f, "unknown mod acronym `{}`", acronym),
+            Self::UnavailableMods(mods) => write!(f, "mods {} are not available", mods),
+            Self::UnsupportedMode { version, map } => write!(
+                f,
+                "{:?} maps can not be calculated by a {:?} version",
+                map, version
+            ),
+            Self::DuplicateVersion(name) => {
+                write!(f, "a custom version named `{}` is registered already", name)
+            }
+            Self::UnknownVersion(name) => write!(f, "unknown version `{}`", name),
+            Self::DisabledMode(mode) => write!(f, "no {:?} version is enabled", mode),
+            Self::InvalidModsJson(err) => write!(f, "invalid mods json: {}", err),
+        }
+    }
+}
+
+impl StdError for Error {}
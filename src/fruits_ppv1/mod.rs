@@ -11,12 +11,19 @@ use movement::Movement;
 pub use pp::*;
 
 use rosu_pp::{
-    catch::CatchDifficultyAttributes,
+    catch::{CatchDifficultyAttributes, CatchPerformanceAttributes, CatchScoreState},
     parse::{HitObjectKind, Pos2},
-    Beatmap, Mods,
+    Beatmap, GameMode, Mods,
 };
 
-use crate::util::curve::{Curve, CurveBuffers};
+use crate::{
+    mods::{self, ScoreMultipliers},
+    util::{
+        curve::{Curve, CurveBuffers},
+        mode::check_mode,
+    },
+    Error,
+};
 
 const SECTION_LENGTH: f64 = 750.0;
 const STAR_SCALING_FACTOR: f64 = 0.145;
@@ -26,16 +33,179 @@ const CATCHER_SIZE: f32 = 106.75;
 const LEGACY_LAST_TICK_OFFSET: f64 = 36.0;
 const BASE_SCORING_DISTANCE: f64 = 100.0;
 
+/// The approximate date at which this version went live, formatted as `YYYY-MM-DD`.
+pub const VERSION_DATE: &str = "2014-01-01";
+
+/// The name of this version within its mode.
+pub const VERSION_NAME: &str = "ppv1";
+
+/// A short description of what changed in this version.
+pub const VERSION_DESCRIPTION: &str =
+    "The original ppv2 osu!catch strain with accuracy, AR, and length bonuses.";
+
+/// The mode this version calculates, i.e. osu!ctb.
+pub const MODE: GameMode = GameMode::Catch;
+
+crate::calculator::difficulty_calculator!(FruitsCalculator, FruitsPpv1);
+
+/// Parse mod acronyms like `"HDDT"` or `"HD,DT"` into the mods value of this version.
+///
+/// Mods that did not exist yet at the time of this version cause an error.
+#[inline]
+pub fn parse_mods(acronyms: &str) -> Result<u32, Error> {
+    mods::parse_available(acronyms, mods::BEFORE_SCORE_V2)
+}
+
+/// The score multiplier of the mods in this version.
+#[inline]
+pub fn score_multiplier(mods: u32) -> f32 {
+    ScoreMultipliers::CATCH.multiplier(mods)
+}
+
+/// Calculate the difficulty and the performance of a score in one pass.
+///
+/// Calling [`stars`] and then [`FruitsPP`] without passing the attributes along calculates
+/// the difficulty twice, this function avoids that.
+pub fn stars_and_pp(
+    map: &Beatmap,
+    mods: u32,
+    state: CatchScoreState,
+) -> (CatchDifficultyAttributes, CatchPerformanceAttributes) {
+    let CatchScoreState {
+        max_combo,
+        n_fruits,
+        n_droplets,
+        n_tiny_droplets,
+        n_tiny_droplet_misses,
+        n_misses,
+    } = state;
+
+    let performance = FruitsPP::new(map)
+        .mods(mods)
+        .combo(max_combo)
+        .fruits(n_fruits)
+        .droplets(n_droplets)
+        .tiny_droplets(n_tiny_droplets)
+        .tiny_droplet_misses(n_tiny_droplet_misses)
+        .misses(n_misses)
+        .calculate();
+
+    (performance.difficulty.clone(), performance)
+}
+
+/// Difficulty calculator on osu!ctb maps.
+///
+/// # Example
+///
+/// ```
+/// use rosu_pp::Beatmap;
+/// use rosu_pp_older::fruits_ppv1::FruitsStars;
+///
+/// # /*
+/// let map: Beatmap = ...
+/// # */
+/// # let map = Beatmap::default();
+///
+/// let difficulty_attrs = FruitsStars::new(&map)
+///     .mods(8 + 64) // HDDT
+///     .calculate();
+///
+/// println!("Stars: {}", difficulty_attrs.stars);
+/// ```
+#[derive(Clone, Debug)]
+pub struct FruitsStars<'map> {
+    map: &'map Beatmap,
+    mods: u32,
+    passed_objects: Option<usize>,
+    clock_rate: Option<f64>,
+}
+
+impl<'map> FruitsStars<'map> {
+    /// Create a new difficulty calculator for osu!ctb maps.
+    #[inline]
+    pub fn new(map: &'map Beatmap) -> Self {
+        Self {
+            map,
+            mods: 0,
+            passed_objects: None,
+            clock_rate: None,
+        }
+    }
+
+    /// Specify mods through their bit values.
+    ///
+    /// See [https://github.com/ppy/osu-api/wiki#mods](https://github.com/ppy/osu-api/wiki#mods)
+    #[inline]
+    pub fn mods(mut self, mods: u32) -> Self {
+        self.mods = mods;
+
+        self
+    }
+
+    /// Specify mods through their acronyms, e.g. `"HDDT"`.
+    ///
+    /// Returns an error for unknown acronyms and for mods
+    /// that did not exist yet at the time of this version.
+    #[inline]
+    pub fn mods_str(self, acronyms: &str) -> Result<Self, Error> {
+        parse_mods(acronyms).map(|mods| self.mods(mods))
+    }
+
+    /// Amount of passed objects for partial plays, e.g. a fail.
+    #[inline]
+    pub fn passed_objects(mut self, passed_objects: usize) -> Self {
+        self.passed_objects = Some(passed_objects);
+
+        self
+    }
+
+    /// Adjust the clock rate used in the calculation, e.g. `1.4` for a lazer DT
+    /// score that was set at 1.4x.
+    ///
+    /// If none is specified, the clock rate of the mods is used.
+    #[inline]
+    pub fn clock_rate(mut self, clock_rate: f64) -> Self {
+        self.clock_rate = Some(clock_rate);
+
+        self
+    }
+
+    /// Calculate all difficulty related values, including stars.
+    ///
+    /// Maps of other modes, except for converted osu!standard maps, result in default attributes.
+    #[inline]
+    pub fn calculate(self) -> CatchDifficultyAttributes {
+        let clock_rate = self.clock_rate.unwrap_or_else(|| self.mods.clock_rate());
+
+        stars_with_clock_rate(self.map, self.mods, self.passed_objects, clock_rate)
+    }
+}
+
 /// Star calculation for osu!ctb maps
 ///
 /// In case of a partial play, e.g. a fail, one can specify the amount of passed objects.
+///
+/// Maps of other modes, except for converted osu!standard maps, result in default attributes.
 pub fn stars(map: &Beatmap, mods: u32, passed_objects: Option<usize>) -> CatchDifficultyAttributes {
-    if map.hit_objects.len() < 2 {
+    stars_with_clock_rate(map, mods, passed_objects, mods.clock_rate())
+}
+
+/// Star calculation for osu!ctb maps with a custom clock rate.
+///
+/// The clock rate replaces the one of DT or HT, e.g. `1.4` for a lazer DT
+/// score that was set at 1.4x.
+pub fn stars_with_clock_rate(
+    map: &Beatmap,
+    mods: u32,
+    passed_objects: Option<usize>,
+    clock_rate: f64,
+) -> CatchDifficultyAttributes {
+    if map.hit_objects.len() < 2 || check_mode(map.mode, MODE).is_err() {
         return CatchDifficultyAttributes::default();
     }
 
     let take = passed_objects.unwrap_or(usize::MAX);
-    let map_attributes = map.attributes().mods(mods).build();
+    let map_attributes = map.attributes().mods(mods).clock_rate(clock_rate).build();
 
     let attributes = CatchDifficultyAttributes {
         ar: map_attributes.ar,
@@ -1,5 +1,7 @@
 use super::DifficultyObject;
 
+use crate::util::sum::StrainSum;
+
 use std::cmp::Ordering;
 
 const ABSOLUTE_PLAYER_POSITIONING_ERROR: f32 = 16.0;
@@ -56,7 +58,7 @@ impl Movement {
     }
 
     pub(crate) fn difficulty_value(&mut self) -> f64 {
-        let mut difficulty = 0.0;
+        let mut difficulty = StrainSum::default();
         let mut weight = 1.0;
 
         self.strain_peaks
@@ -67,7 +69,7 @@ impl Movement {
             weight *= DECAY_WEIGHT;
         }
 
-        difficulty
+        difficulty.value()
     }
 
     fn strain_value_of(&mut self, current: &DifficultyObject) -> f64 {
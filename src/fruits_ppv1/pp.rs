@@ -1,10 +1,16 @@
-use super::stars;
+use super::{stars, MODE};
 
 use rosu_pp::{
     catch::{CatchDifficultyAttributes, CatchPerformanceAttributes},
     Beatmap, DifficultyAttributes, Mods, PerformanceAttributes,
 };
 
+use crate::{
+    unranked::UnrankedConfig,
+    util::{cache::DifficultyCache, mode::check_mode},
+    Error, IfFcAttributes, ScoreState,
+};
+
 /// Calculator for pp on osu!ctb maps.
 ///
 /// # Example
@@ -36,6 +42,7 @@ use rosu_pp::{
 pub struct FruitsPP<'m> {
     map: &'m Beatmap,
     attributes: Option<CatchDifficultyAttributes>,
+    cache: DifficultyCache<CatchDifficultyAttributes>,
     mods: u32,
     combo: Option<usize>,
 
@@ -45,6 +52,8 @@ pub struct FruitsPP<'m> {
     n_tiny_droplet_misses: Option<usize>,
     n_misses: usize,
     passed_objects: Option<usize>,
+
+    unranked: UnrankedConfig,
 }
 
 impl<'m> FruitsPP<'m> {
@@ -53,6 +62,7 @@ impl<'m> FruitsPP<'m> {
         Self {
             map,
             attributes: None,
+            cache: DifficultyCache::default(),
             mods: 0,
             combo: None,
 
@@ -62,6 +72,8 @@ impl<'m> FruitsPP<'m> {
             n_tiny_droplet_misses: None,
             n_misses: 0,
             passed_objects: None,
+
+            unranked: UnrankedConfig::default(),
         }
     }
 
@@ -85,10 +97,20 @@ impl<'m> FruitsPP<'m> {
     #[inline]
     pub fn mods(mut self, mods: u32) -> Self {
         self.mods = mods;
+        self.cache.reset();
 
         self
     }
 
+    /// Specify mods through their acronyms, e.g. `"HDDT"`.
+    ///
+    /// Returns an error for unknown acronyms and for mods
+    /// that did not exist yet at the time of this version.
+    #[inline]
+    pub fn mods_str(self, acronyms: &str) -> Result<Self, Error> {
+        super::parse_mods(acronyms).map(|mods| self.mods(mods))
+    }
+
     /// Specify the max combo of the play.
     #[inline]
     pub fn combo(mut self, combo: usize) -> Self {
@@ -141,6 +163,33 @@ impl<'m> FruitsPP<'m> {
     #[inline]
     pub fn passed_objects(mut self, passed_objects: usize) -> Self {
         self.passed_objects.replace(passed_objects);
+        self.cache.reset();
+
+        self
+    }
+
+    /// Provide parameters through a [`ScoreState`].
+    ///
+    /// The 300s are considered as fruits, the 100s as droplets, the 50s as tiny droplets,
+    /// and the katus as tiny droplet misses.
+    #[inline]
+    pub fn state(mut self, state: impl Into<ScoreState>) -> Self {
+        let ScoreState {
+            max_combo,
+            n300,
+            n100,
+            n50,
+            n_misses,
+            n_katu,
+            ..
+        } = state.into();
+
+        self.combo = Some(max_combo);
+        self.n_fruits = Some(n300);
+        self.n_droplets = Some(n100);
+        self.n_tiny_droplets = Some(n50);
+        self.n_tiny_droplet_misses = Some(n_katu);
+        self.n_misses = n_misses;
 
         self
     }
@@ -150,8 +199,11 @@ impl<'m> FruitsPP<'m> {
     /// Be sure to set `misses` beforehand! Also, if available, set `attributes` beforehand.
     pub fn accuracy(mut self, mut acc: f32) -> Self {
         if self.attributes.is_none() {
-            self.attributes
-                .replace(stars(self.map, self.mods, self.passed_objects));
+            let attributes = self
+                .cache
+                .get_or_insert_with(|| stars(self.map, self.mods, self.passed_objects));
+
+            self.attributes.replace(attributes);
         }
 
         let attributes = self.attributes.as_ref().unwrap();
@@ -240,13 +292,73 @@ impl<'m> FruitsPP<'m> {
         }
     }
 
+    /// Specify whether [`try_calculate`](Self::try_calculate) should accept mods
+    /// that bancho never awarded pp for, i.e. AT, CN, TP, and RX.
+    ///
+    /// Useful for private servers, defaults to `false`.
+    #[inline]
+    pub fn allow_unranked(mut self, allow_unranked: bool) -> Self {
+        self.unranked.allow_unranked = allow_unranked;
+
+        self
+    }
+
+    /// Same as [`calculate`](Self::calculate) but returns an error if the map can
+    /// not be calculated by this version or if the mods contain unranked mods.
+    pub fn try_calculate(self) -> Result<CatchPerformanceAttributes, Error> {
+        check_mode(self.map.mode, MODE)?;
+        self.unranked.check(self.mods)?;
+
+        Ok(self.calculate())
+    }
+
+    /// Calculate the performance of the score and of its full combo.
+    ///
+    /// For the full combo, the missed fruits and droplets are counted as hits and
+    /// the combo is the map's max combo. Tiny droplets are kept as they are.
+    pub fn calculate_if_fc(self) -> IfFcAttributes<CatchPerformanceAttributes> {
+        let fc = self.if_fc();
+
+        IfFcAttributes {
+            score: self.calculate(),
+            fc: fc.calculate(),
+        }
+    }
+
+    /// Same as [`calculate_if_fc`](Self::calculate_if_fc) but returns an error if the map can
+    /// not be calculated by this version or if the mods contain unranked mods.
+    pub fn try_calculate_if_fc(self) -> Result<IfFcAttributes<CatchPerformanceAttributes>, Error> {
+        check_mode(self.map.mode, MODE)?;
+        self.unranked.check(self.mods)?;
+
+        Ok(self.calculate_if_fc())
+    }
+
+    fn if_fc(&self) -> Self {
+        let mut fc = self.clone();
+        fc.combo = None;
+        fc.n_misses = 0;
+
+        fc
+    }
+
     /// Returns an object which contains the pp and [`DifficultyAttributes`](crate::catch::DifficultyAttributes)
     /// containing stars and other attributes.
+    ///
+    /// Maps of other modes, except for converted osu!standard maps, result in default attributes,
+    /// [`try_calculate`](Self::try_calculate) returns an error for them instead.
     pub fn calculate(mut self) -> CatchPerformanceAttributes {
-        let attributes = self
-            .attributes
-            .take()
-            .unwrap_or_else(|| stars(self.map, self.mods, self.passed_objects));
+        if check_mode(self.map.mode, MODE).is_err() {
+            return CatchPerformanceAttributes {
+                difficulty: CatchDifficultyAttributes::default(),
+                pp: 0.0,
+            };
+        }
+
+        let attributes = self.attributes.take().unwrap_or_else(|| {
+            self.cache
+                .get_or_insert_with(|| stars(self.map, self.mods, self.passed_objects))
+        });
 
         let max_combo = attributes.max_combo();
 
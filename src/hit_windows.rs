@@ -0,0 +1,89 @@
+use rosu_pp::Mods;
+
+use crate::util::math::difficulty_range;
+
+/// The hit windows of an osu!standard map in milliseconds.
+///
+/// Each window is the largest distance to the object's time at which a hit
+/// still counts for the respective judgement, already divided by the clock rate.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct HitWindows {
+    /// The window of a 300.
+    pub n300: f64,
+    /// The window of a 100.
+    pub n100: f64,
+    /// The window of a 50.
+    pub n50: f64,
+}
+
+impl HitWindows {
+    /// The windows as the formulas until 2018 computed them.
+    ///
+    /// Windows are whole milliseconds minus half a millisecond of leniency
+    /// and HR is not capped at OD 10 but the windows are clamped instead.
+    pub(crate) fn rounded(od: f64, mods: u32) -> Self {
+        let od = if mods.hr() {
+            od * 1.4
+        } else if mods.ez() {
+            od * 0.5
+        } else {
+            od
+        };
+
+        let window = |min: f64, max: f64, per_od: f64| {
+            (min - (per_od * od).ceil()).max(max).min(min) / mods.clock_rate()
+        };
+
+        Self {
+            n300: window(79.5, 19.5, 6.0),
+            n100: window(139.5, 59.5, 8.0),
+            n50: window(199.5, 99.5, 10.0),
+        }
+    }
+
+    /// The windows as the formulas from 2019 on computed them.
+    pub(crate) fn exact(od: f64, mods: u32) -> Self {
+        let od = if mods.hr() {
+            (od * 1.4).min(10.0)
+        } else if mods.ez() {
+            od * 0.5
+        } else {
+            od
+        };
+
+        let clock_rate = mods.clock_rate();
+
+        Self {
+            n300: difficulty_range(od, 20.0, 50.0, 80.0) / clock_rate,
+            n100: difficulty_range(od, 60.0, 100.0, 140.0) / clock_rate,
+            n50: difficulty_range(od, 100.0, 150.0, 200.0) / clock_rate,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rounded_windows() {
+        let windows = HitWindows::rounded(8.5, 0);
+
+        assert_eq!(windows.n300, 79.5 - 51.0);
+        assert_eq!(windows.n100, 139.5 - 68.0);
+        assert_eq!(windows.n50, 199.5 - 85.0);
+
+        // HR exceeds OD 10 but the windows are clamped
+        let windows = HitWindows::rounded(9.0, 16);
+        assert_eq!(windows.n300, 19.5);
+    }
+
+    #[test]
+    fn exact_windows() {
+        let windows = HitWindows::exact(8.0, 64);
+
+        assert!((windows.n300 - 32.0 / 1.5).abs() < 1e-10);
+        assert!((windows.n50 - 120.0 / 1.5).abs() < 1e-10);
+    }
+}
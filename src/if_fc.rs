@@ -0,0 +1,20 @@
+/// The performance of a score next to the performance of its full combo,
+/// see the `calculate_if_fc` method of the pp calculators.
+#[derive(Clone, Debug)]
+pub struct IfFcAttributes<A> {
+    /// The performance of the score as it was played.
+    pub score: A,
+    /// The performance of the score if it had been a full combo.
+    pub fc: A,
+}
+
+impl<A> IfFcAttributes<A> {
+    /// Convert the attributes of both the score and its full combo.
+    #[inline]
+    pub fn map<B>(self, mut f: impl FnMut(A) -> B) -> IfFcAttributes<B> {
+        IfFcAttributes {
+            score: f(self.score),
+            fc: f(self.fc),
+        }
+    }
+}
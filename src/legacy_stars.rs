@@ -0,0 +1,70 @@
+//! The star rating that the game displayed before the 2014 star rating system.
+//!
+//! Also known as "eyup" stars, it only considers the map's HP, CS, and OD as well
+//! as the note density and ranges from 0 to 5 stars. Mods have no effect on it.
+
+use rosu_pp::Beatmap;
+
+/// The note density at which the density portion of the rating is capped.
+const MAX_NOTE_DENSITY: f64 = 16.0;
+
+/// The maximum sum of HP, CS, OD, and the note density.
+const MAX_SUM: f64 = 38.0;
+
+/// The pre-2014 in-game star rating of a map between 0 and 5.
+///
+/// # Example
+///
+/// ```
+/// use rosu_pp::Beatmap;
+/// use rosu_pp_older::legacy_stars;
+///
+/// # /*
+/// let map: Beatmap = ...
+/// # */
+/// # let map = Beatmap::default();
+///
+/// println!("Eyup stars: {:.2}", legacy_stars::stars(&map));
+/// ```
+pub fn stars(map: &Beatmap) -> f64 {
+    let drain_time = drain_time(map);
+
+    let note_density = if drain_time > 0.0 {
+        (map.hit_objects.len() as f64 / drain_time * 8.0).min(MAX_NOTE_DENSITY)
+    } else {
+        0.0
+    };
+
+    let sum = map.hp as f64 + map.cs as f64 + map.od as f64 + note_density;
+
+    sum / MAX_SUM * 5.0
+}
+
+/// The time in seconds between the first and last hit object, excluding breaks.
+fn drain_time(map: &Beatmap) -> f64 {
+    let (first, last) = match (map.hit_objects.first(), map.hit_objects.last()) {
+        (Some(first), Some(last)) => (first, last),
+        _ => return 0.0,
+    };
+
+    let breaks: f64 = map.breaks.iter().map(|b| b.end_time - b.start_time).sum();
+
+    (last.end_time() - first.start_time - breaks) / 1000.0
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn without_objects() {
+        let map = Beatmap {
+            hp: 6.0,
+            cs: 4.0,
+            od: 8.0,
+            ..Default::default()
+        };
+
+        assert!((stars(&map) - 18.0 / 38.0 * 5.0).abs() < 1e-10);
+    }
+}
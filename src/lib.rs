@@ -1,19 +1,117 @@
+#[cfg(feature = "osu-2012-tp")]
+pub mod osu_2012_tp;
+#[cfg(feature = "osu-2014-february")]
+pub mod osu_2014_february;
+#[cfg(feature = "osu-2014-july")]
 pub mod osu_2014_july;
+#[cfg(feature = "osu-2014-may")]
 pub mod osu_2014_may;
+#[cfg(feature = "osu-2015-february")]
 pub mod osu_2015_february;
+#[cfg(feature = "osu-2015-april")]
 pub mod osu_2015_april;
+#[cfg(feature = "osu-2018")]
 pub mod osu_2018;
+#[cfg(feature = "osu-2019")]
 pub mod osu_2019;
+#[cfg(feature = "osu-2021-january")]
 pub mod osu_2021_january;
+#[cfg(feature = "osu-2021-july")]
 pub mod osu_2021_july;
+#[cfg(feature = "osu-2021-november")]
 pub mod osu_2021_november;
+#[cfg(feature = "osu-2022-september")]
+pub mod osu_2022_september;
 
+#[cfg(feature = "mania-2018")]
 pub mod mania_2018;
+#[cfg(feature = "mania-2022")]
+pub mod mania_2022;
+#[cfg(feature = "mania-ppv1")]
 pub mod mania_ppv1;
 
+#[cfg(feature = "catch-2020")]
+pub mod catch_2020;
+#[cfg(feature = "fruits-ppv1")]
 pub mod fruits_ppv1;
 
+#[cfg(feature = "taiko-2020")]
 pub mod taiko_2020;
+#[cfg(feature = "taiko-2022")]
+pub mod taiko_2022;
+#[cfg(feature = "taiko-ppv1")]
 pub mod taiko_ppv1;
 
+pub mod custom;
+pub mod legacy_stars;
+pub mod mods;
+pub mod osu;
+pub mod prelude;
+pub mod unranked;
+
+mod any_pp;
+mod any_stars;
+mod approx;
+mod attributes;
+mod beatmap_ext;
+mod calculator;
+mod convert;
+mod error;
+mod hit_windows;
+mod if_fc;
+mod map_attributes;
+mod object_count;
+mod score_state;
+mod section;
+#[cfg(feature = "store")]
+mod store;
+mod summary;
 mod util;
+mod version;
+
+pub use any_pp::{AnyIfFcAttributes, AnyPP, AnyPerformanceAttributes};
+pub use any_stars::AnyStars;
+pub use approx::ApproxEq;
+pub use attributes::{
+    DifficultyAttributes, PerformanceAttributes, SharedDifficultyAttributes,
+    SharedPerformanceAttributes,
+};
+pub use beatmap_ext::OlderBeatmapExt;
+pub use calculator::DifficultyCalculator;
+pub use error::Error;
+pub use hit_windows::HitWindows;
+pub use if_fc::IfFcAttributes;
+pub use map_attributes::MapAttributes;
+pub use object_count::ObjectCount;
+pub use score_state::ScoreState;
+#[cfg(feature = "experimental")]
+pub use section::SectionLen;
+#[cfg(feature = "store")]
+pub use store::{AnyAttributeStore, AttributeKey, AttributeStore, StoreStats};
+pub use summary::{summarize, VersionSummary};
+pub use util::date;
+pub use version::{stars, AnyDifficultyAttributes, Version, VersionInfo};
+
+#[cfg(not(any(
+    feature = "osu-2012-tp",
+    feature = "osu-2014-february",
+    feature = "osu-2014-may",
+    feature = "osu-2014-july",
+    feature = "osu-2015-february",
+    feature = "osu-2015-april",
+    feature = "osu-2018",
+    feature = "osu-2019",
+    feature = "osu-2021-january",
+    feature = "osu-2021-july",
+    feature = "osu-2021-november",
+    feature = "osu-2022-september",
+    feature = "taiko-ppv1",
+    feature = "taiko-2020",
+    feature = "taiko-2022",
+    feature = "mania-ppv1",
+    feature = "mania-2018",
+    feature = "mania-2022",
+    feature = "fruits-ppv1",
+    feature = "catch-2020",
+)))]
+compile_error!("at least one version feature must be enabled, e.g. the default `all` feature");
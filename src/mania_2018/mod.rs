@@ -5,9 +5,59 @@ pub use pp::*;
 use rosu_pp::{parse::HitObject, Beatmap, GameMode, Mods};
 use strain::Strain;
 
+use crate::{
+    convert,
+    mods::{self, ScoreMultipliers},
+    object_count::count_objects,
+    Error,
+};
+
 const SECTION_LEN: f64 = 400.0;
 const STAR_SCALING_FACTOR: f64 = 0.018;
 
+/// The approximate date at which this version went live, formatted as `YYYY-MM-DD`.
+pub const VERSION_DATE: &str = "2018-05-01";
+
+/// The name of this version within its mode.
+pub const VERSION_NAME: &str = "2018";
+
+/// A short description of what changed in this version.
+pub const VERSION_DESCRIPTION: &str =
+    "Both the strain and the accuracy value are derived from the score.";
+
+/// The mode this version calculates, i.e. osu!mania.
+pub const MODE: GameMode = GameMode::Mania;
+
+crate::calculator::difficulty_calculator!(ManiaCalculator, Mania2018);
+
+/// Parse mod acronyms like `"HDDT"` or `"HD,DT"` into the mods value of this version.
+///
+/// Mods that did not exist yet at the time of this version cause an error.
+#[inline]
+pub fn parse_mods(acronyms: &str) -> Result<u32, Error> {
+    mods::parse_available(acronyms, mods::BEFORE_MIRROR)
+}
+
+/// The score multiplier of the mods in this version.
+#[inline]
+pub fn score_multiplier(mods: u32) -> f32 {
+    ScoreMultipliers::MANIA.multiplier(mods)
+}
+
+/// Calculate the difficulty and the performance of a score in one pass.
+///
+/// Calling [`ManiaStars`] and then [`ManiaPP`] without passing the attributes along calculates
+/// the difficulty twice, this function avoids that.
+pub fn stars_and_pp(
+    map: &Beatmap,
+    mods: u32,
+    score: u32,
+) -> (ManiaDifficultyAttributes, ManiaPerformanceAttributes) {
+    let performance = ManiaPP::new(map).mods(mods).score(score).calculate();
+
+    (performance.difficulty, performance)
+}
+
 /// Difficulty calculator on osu!mania maps.
 ///
 /// # Example
@@ -31,6 +81,7 @@ pub struct ManiaStars<'map> {
     map: &'map Beatmap,
     mods: u32,
     passed_objects: Option<usize>,
+    clock_rate: Option<f64>,
 }
 
 impl<'map> ManiaStars<'map> {
@@ -41,6 +92,7 @@ impl<'map> ManiaStars<'map> {
             map,
             mods: 0,
             passed_objects: None,
+            clock_rate: None,
         }
     }
 
@@ -54,6 +106,15 @@ impl<'map> ManiaStars<'map> {
         self
     }
 
+    /// Specify mods through their acronyms, e.g. `"HDDT"`.
+    ///
+    /// Returns an error for unknown acronyms and for mods
+    /// that did not exist yet at the time of this version.
+    #[inline]
+    pub fn mods_str(self, acronyms: &str) -> Result<Self, Error> {
+        parse_mods(acronyms).map(|mods| self.mods(mods))
+    }
+
     /// Amount of passed objects for partial plays, e.g. a fail.
     ///
     /// If you want to calculate the difficulty after every few objects, instead of
@@ -66,49 +127,53 @@ impl<'map> ManiaStars<'map> {
         self
     }
 
+    /// Adjust the clock rate used in the calculation, e.g. `1.4` for a lazer DT
+    /// score that was set at 1.4x.
+    ///
+    /// If none is specified, the clock rate of the mods is used.
+    #[inline]
+    pub fn clock_rate(mut self, clock_rate: f64) -> Self {
+        self.clock_rate = Some(clock_rate);
+
+        self
+    }
+
     /// Calculate all difficulty related values, including stars.
+    ///
+    /// Maps of other modes, except for converted osu!standard maps, result in default attributes.
     #[inline]
     pub fn calculate(self) -> ManiaDifficultyAttributes {
-        let mut strain = calculate_strain(self);
+        let columns = match convert::mania_columns(self.map, self.mods) {
+            Ok(columns) => columns,
+            Err(_) => return ManiaDifficultyAttributes::default(),
+        };
+
+        let take = self.passed_objects.unwrap_or(self.map.hit_objects.len());
+        let (n_circles, n_sliders, n_spinners) =
+            count_objects(self.map.hit_objects.iter().take(take));
+
+        let mut strain = calculate_strain(self, columns);
 
         ManiaDifficultyAttributes {
             stars: Strain::difficulty_value(&mut strain.strain_peaks) * STAR_SCALING_FACTOR,
+            n_circles,
+            n_sliders,
+            n_spinners,
         }
     }
 }
 
-fn calculate_strain(params: ManiaStars<'_>) -> Strain {
+fn calculate_strain(params: ManiaStars<'_>, columns: u8) -> Strain {
     let ManiaStars {
         map,
         mods,
         passed_objects,
+        clock_rate,
     } = params;
 
     let take = passed_objects.unwrap_or(map.hit_objects.len());
-    let rounded_cs = map.cs.round();
-
-    let columns = match map.mode {
-        GameMode::Mania => rounded_cs.max(1.0) as u8,
-        GameMode::Osu => {
-            let rounded_od = map.od.round();
-
-            let n_objects = map.n_circles + map.n_sliders + map.n_spinners;
-            let slider_or_spinner_ratio = (n_objects - map.n_circles) as f32 / n_objects as f32;
-
-            if slider_or_spinner_ratio < 0.2 {
-                7
-            } else if slider_or_spinner_ratio < 0.3 || rounded_cs >= 5.0 {
-                6 + (rounded_od > 5.0) as u8
-            } else if slider_or_spinner_ratio > 0.6 {
-                4 + (rounded_od > 4.0) as u8
-            } else {
-                (rounded_od as u8 + 1).clamp(4, 7)
-            }
-        }
-        other => panic!("can not calculate mania difficulty on a {:?} map", other),
-    };
 
-    let clock_rate = mods.clock_rate();
+    let clock_rate = clock_rate.unwrap_or_else(|| mods.clock_rate());
     let mut strain = Strain::new(columns);
     let columns = columns as f32;
 
@@ -171,13 +236,21 @@ impl<'o> DifficultyHitObject<'o> {
 
 /// The result of a difficulty calculation on an osu!mania map.
 #[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct ManiaDifficultyAttributes {
     /// The final star rating.
     pub stars: f64,
+    /// The amount of notes.
+    pub n_circles: usize,
+    /// The amount of hold notes.
+    pub n_sliders: usize,
+    /// The amount of spinners, only present in converted maps.
+    pub n_spinners: usize,
 }
 
 /// The result of a performance calculation on an osu!mania map.
 #[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct ManiaPerformanceAttributes {
     /// The difficulty attributes that were used for the performance calculation
     pub difficulty: ManiaDifficultyAttributes,
@@ -187,6 +260,8 @@ pub struct ManiaPerformanceAttributes {
     pub pp_acc: f64,
     /// The strain portion of the final pp.
     pub pp_strain: f64,
+    /// The multiplier that was applied due to the NoFail mod.
+    pub nf_multiplier: f64,
 }
 
 impl ManiaPerformanceAttributes {
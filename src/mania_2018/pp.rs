@@ -1,6 +1,14 @@
 use rosu_pp::{Beatmap, Mods};
 
-use super::{ManiaDifficultyAttributes, ManiaPerformanceAttributes, ManiaStars};
+use crate::{
+    convert,
+    object_count::count_objects,
+    unranked::UnrankedConfig,
+    util::{cache::DifficultyCache, mode::check_mode},
+    Error, IfFcAttributes,
+};
+
+use super::{ManiaDifficultyAttributes, ManiaPerformanceAttributes, ManiaStars, MODE};
 
 /// Performance calculator on osu!mania maps.
 ///
@@ -34,9 +42,12 @@ use super::{ManiaDifficultyAttributes, ManiaPerformanceAttributes, ManiaStars};
 pub struct ManiaPP<'map> {
     map: &'map Beatmap,
     stars: Option<f64>,
+    cache: DifficultyCache<f64>,
     mods: u32,
     pub(crate) score: Option<f64>,
     passed_objects: Option<usize>,
+
+    unranked: UnrankedConfig,
 }
 
 impl<'map> ManiaPP<'map> {
@@ -46,9 +57,12 @@ impl<'map> ManiaPP<'map> {
         Self {
             map,
             stars: None,
+            cache: DifficultyCache::default(),
             mods: 0,
             score: None,
             passed_objects: None,
+
+            unranked: UnrankedConfig::default(),
         }
     }
 
@@ -70,10 +84,20 @@ impl<'map> ManiaPP<'map> {
     #[inline]
     pub fn mods(mut self, mods: u32) -> Self {
         self.mods = mods;
+        self.cache.reset();
 
         self
     }
 
+    /// Specify mods through their acronyms, e.g. `"HDDT"`.
+    ///
+    /// Returns an error for unknown acronyms and for mods
+    /// that did not exist yet at the time of this version.
+    #[inline]
+    pub fn mods_str(self, acronyms: &str) -> Result<Self, Error> {
+        super::parse_mods(acronyms).map(|mods| self.mods(mods))
+    }
+
     /// Specify the score of a play.
     /// On `NoMod` its between 0 and 1,000,000, on `Easy` between 0 and 500,000, etc.
     #[inline]
@@ -94,18 +118,67 @@ impl<'map> ManiaPP<'map> {
     #[inline]
     pub fn passed_objects(mut self, passed_objects: usize) -> Self {
         self.passed_objects.replace(passed_objects);
+        self.cache.reset();
 
         self
     }
 
+    /// Specify whether [`try_calculate`](Self::try_calculate) should accept mods
+    /// that bancho never awarded pp for, i.e. AT, CN, TP, and RX.
+    ///
+    /// Useful for private servers, defaults to `false`.
+    #[inline]
+    pub fn allow_unranked(mut self, allow_unranked: bool) -> Self {
+        self.unranked.allow_unranked = allow_unranked;
+
+        self
+    }
+
+    /// Same as [`calculate`](Self::calculate) but returns an error if the map can
+    /// not be calculated by this version or if the mods contain unranked mods.
+    pub fn try_calculate(self) -> Result<ManiaPerformanceAttributes, Error> {
+        check_mode(self.map.mode, MODE)?;
+        self.unranked.check(self.mods)?;
+
+        Ok(self.calculate())
+    }
+
+    /// Calculate the performance of the score and of its full combo.
+    ///
+    /// This version only rates the score and the accuracy which a full combo
+    /// does not change, so both performances are the same.
+    pub fn calculate_if_fc(self) -> IfFcAttributes<ManiaPerformanceAttributes> {
+        let score = self.calculate();
+
+        IfFcAttributes { score, fc: score }
+    }
+
+    /// Same as [`calculate_if_fc`](Self::calculate_if_fc) but returns an error if the map can
+    /// not be calculated by this version or if the mods contain unranked mods.
+    pub fn try_calculate_if_fc(self) -> Result<IfFcAttributes<ManiaPerformanceAttributes>, Error> {
+        check_mode(self.map.mode, MODE)?;
+        self.unranked.check(self.mods)?;
+
+        Ok(self.calculate_if_fc())
+    }
+
     /// Calculate all performance related values, including pp and stars.
+    ///
+    /// Maps of other modes, except for converted osu!standard maps, result in default attributes,
+    /// [`try_calculate`](Self::try_calculate) returns an error for them instead.
     pub fn calculate(self) -> ManiaPerformanceAttributes {
+        if check_mode(self.map.mode, MODE).is_err() {
+            return ManiaPerformanceAttributes::default();
+        }
+
         let stars = self.stars.unwrap_or_else(|| {
-            ManiaStars::new(self.map)
-                .mods(self.mods)
-                .passed_objects(self.passed_objects.unwrap_or(usize::MAX))
-                .calculate()
-                .stars
+            self.cache.get_or_insert_with(|| {
+                ManiaStars::new(self.map)
+                    .mods(self.mods)
+                    .passed_objects(self.passed_objects.unwrap_or(usize::MAX))
+                    .calculate()
+                    .stars
+            })
         });
 
         let ez = self.mods.ez();
@@ -123,14 +196,13 @@ impl<'map> ManiaPP<'map> {
             scaled_score /= percent_passed;
         }
 
-        let mut od = 34.0 + 3.0 * (10.0 - self.map.od as f64).clamp(0.0, 10.0);
+        let mut od = convert::mania_hit_window(self.map);
         let clock_rate = self.mods.clock_rate();
 
         let mut multiplier = 0.8;
 
-        if nf {
-            multiplier *= 0.9;
-        }
+        let nf_multiplier = if nf { 0.9 } else { 1.0 };
+        multiplier *= nf_multiplier;
 
         if ez {
             multiplier *= 0.5;
@@ -144,11 +216,21 @@ impl<'map> ManiaPP<'map> {
 
         let pp = (strain_value.powf(1.1) + acc_value.powf(1.1)).powf(1.0 / 1.1) * multiplier;
 
+        let take = self.passed_objects.unwrap_or(self.map.hit_objects.len());
+        let (n_circles, n_sliders, n_spinners) =
+            count_objects(self.map.hit_objects.iter().take(take));
+
         ManiaPerformanceAttributes {
-            difficulty: ManiaDifficultyAttributes { stars },
+            difficulty: ManiaDifficultyAttributes {
+                stars,
+                n_circles,
+                n_sliders,
+                n_spinners,
+            },
             pp_acc: acc_value,
             pp_strain: strain_value,
             pp,
+            nf_multiplier,
         }
     }
 
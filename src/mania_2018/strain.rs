@@ -1,5 +1,7 @@
 use super::DifficultyHitObject;
 
+use crate::util::sum::StrainSum;
+
 use std::cmp::Ordering;
 
 #[derive(Clone, Debug)]
@@ -109,7 +111,7 @@ impl Strain {
 
     #[inline]
     pub(crate) fn difficulty_value(strain_peaks: &mut [f64]) -> f64 {
-        let mut difficulty = 0.0;
+        let mut difficulty = StrainSum::default();
         let mut weight = 1.0;
 
         strain_peaks.sort_unstable_by(|a, b| b.partial_cmp(a).unwrap_or(Ordering::Equal));
@@ -119,7 +121,7 @@ impl Strain {
             weight *= DECAY_WEIGHT;
         }
 
-        difficulty
+        difficulty.value()
     }
 }
 
@@ -0,0 +1,302 @@
+mod pp;
+mod strain;
+
+pub use pp::*;
+use rosu_pp::{mania::ManiaScoreState, parse::HitObject, Beatmap, GameMode, Mods};
+use strain::Strain;
+
+use crate::{
+    convert,
+    mods::{self, ScoreMultipliers},
+    object_count::count_objects,
+    Error,
+};
+
+const SECTION_LEN: f64 = 400.0;
+const STAR_SCALING_FACTOR: f64 = 0.018;
+
+/// The approximate date at which this version went live, formatted as `YYYY-MM-DD`.
+pub const VERSION_DATE: &str = "2022-10-01";
+
+/// The name of this version within its mode.
+pub const VERSION_NAME: &str = "2022";
+
+/// A short description of what changed in this version.
+pub const VERSION_DESCRIPTION: &str =
+    "Score-independent pp based on the hit results, weighing 320s above 300s.";
+
+/// The mode this version calculates, i.e. osu!mania.
+pub const MODE: GameMode = GameMode::Mania;
+
+crate::calculator::difficulty_calculator!(ManiaCalculator, Mania2022);
+
+/// Parse mod acronyms like `"HDDT"` or `"HD,DT"` into the mods value of this version.
+///
+/// Mods that did not exist yet at the time of this version cause an error.
+#[inline]
+pub fn parse_mods(acronyms: &str) -> Result<u32, Error> {
+    mods::parse_available(acronyms, mods::ALL)
+}
+
+/// The score multiplier of the mods in this version.
+#[inline]
+pub fn score_multiplier(mods: u32) -> f32 {
+    ScoreMultipliers::MANIA.multiplier(mods)
+}
+
+/// Calculate the difficulty and the performance of a score in one pass.
+///
+/// Calling [`ManiaStars`] and then [`ManiaPP`] without passing the attributes along calculates
+/// the difficulty twice, this function avoids that.
+pub fn stars_and_pp(
+    map: &Beatmap,
+    mods: u32,
+    state: ManiaScoreState,
+) -> (ManiaDifficultyAttributes, ManiaPerformanceAttributes) {
+    let performance = ManiaPP::new(map).mods(mods).state(state).calculate();
+
+    (performance.difficulty, performance)
+}
+
+/// Difficulty calculator on osu!mania maps.
+///
+/// # Example
+///
+/// ```
+/// use rosu_pp::Beatmap;
+/// use rosu_pp_older::mania_2022::ManiaStars;
+///
+/// # /*
+/// let map: Beatmap = ...
+/// # */
+/// # let map = Beatmap::default();
+///
+/// let difficulty_attrs = ManiaStars::new(&map)
+///     .mods(8 + 64) // HDDT
+///     .calculate();
+///
+/// println!("Stars: {}", difficulty_attrs.stars);
+/// ```
+#[derive(Clone, Debug)]
+pub struct ManiaStars<'map> {
+    map: &'map Beatmap,
+    mods: u32,
+    passed_objects: Option<usize>,
+    clock_rate: Option<f64>,
+}
+
+impl<'map> ManiaStars<'map> {
+    /// Create a new difficulty calculator for osu!mania maps.
+    #[inline]
+    pub fn new(map: &'map Beatmap) -> Self {
+        Self {
+            map,
+            mods: 0,
+            passed_objects: None,
+            clock_rate: None,
+        }
+    }
+
+    /// Specify mods through their bit values.
+    ///
+    /// See [https://github.com/ppy/osu-api/wiki#mods](https://github.com/ppy/osu-api/wiki#mods)
+    #[inline]
+    pub fn mods(mut self, mods: u32) -> Self {
+        self.mods = mods;
+
+        self
+    }
+
+    /// Specify mods through their acronyms, e.g. `"HDDT"`.
+    ///
+    /// Returns an error for unknown acronyms and for mods
+    /// that did not exist yet at the time of this version.
+    #[inline]
+    pub fn mods_str(self, acronyms: &str) -> Result<Self, Error> {
+        parse_mods(acronyms).map(|mods| self.mods(mods))
+    }
+
+    /// Amount of passed objects for partial plays, e.g. a fail.
+    #[inline]
+    pub fn passed_objects(mut self, passed_objects: usize) -> Self {
+        self.passed_objects = Some(passed_objects);
+
+        self
+    }
+
+    /// Adjust the clock rate used in the calculation, e.g. `1.4` for a lazer DT
+    /// score that was set at 1.4x.
+    ///
+    /// If none is specified, the clock rate of the mods is used.
+    #[inline]
+    pub fn clock_rate(mut self, clock_rate: f64) -> Self {
+        self.clock_rate = Some(clock_rate);
+
+        self
+    }
+
+    /// Calculate all difficulty related values, including stars.
+    ///
+    /// Maps of other modes, except for converted osu!standard maps, result in default attributes.
+    #[inline]
+    pub fn calculate(self) -> ManiaDifficultyAttributes {
+        let columns = match convert::mania_columns(self.map, self.mods) {
+            Ok(columns) => columns,
+            Err(_) => return ManiaDifficultyAttributes::default(),
+        };
+
+        let take = self.passed_objects.unwrap_or(self.map.hit_objects.len());
+        let (n_circles, n_sliders, n_spinners) =
+            count_objects(self.map.hit_objects.iter().take(take));
+
+        let clock_rate = self.clock_rate.unwrap_or_else(|| self.mods.clock_rate());
+        let hit_window = hit_window(self.map, self.mods, clock_rate);
+        let mut strain = calculate_strain(self, columns);
+
+        ManiaDifficultyAttributes {
+            stars: Strain::difficulty_value(&mut strain.strain_peaks) * STAR_SCALING_FACTOR,
+            hit_window,
+            n_circles,
+            n_sliders,
+            n_spinners,
+        }
+    }
+}
+
+fn calculate_strain(params: ManiaStars<'_>, columns: u8) -> Strain {
+    let ManiaStars {
+        map,
+        mods,
+        passed_objects,
+        clock_rate,
+    } = params;
+
+    let take = passed_objects.unwrap_or(map.hit_objects.len());
+
+    let clock_rate = clock_rate.unwrap_or_else(|| mods.clock_rate());
+    let mut strain = Strain::new(columns);
+    let columns = columns as f32;
+
+    let mut hit_objects = map
+        .hit_objects
+        .iter()
+        .take(take)
+        .skip(1)
+        .zip(map.hit_objects.iter())
+        .map(|(base, prev)| DifficultyHitObject::new(base, prev, columns, clock_rate));
+
+    // Handle first object distinctly
+    let h = match hit_objects.next() {
+        Some(h) => h,
+        None => return strain,
+    };
+
+    // No strain for first object
+    let mut curr_section_end = (h.start_time / SECTION_LEN).ceil() * SECTION_LEN;
+    strain.process(&h);
+
+    // Handle all other objects
+    for h in hit_objects {
+        while h.start_time > curr_section_end {
+            strain.save_current_peak();
+            strain.start_new_section_from(curr_section_end);
+            curr_section_end += SECTION_LEN;
+        }
+
+        strain.process(&h);
+    }
+
+    strain.save_current_peak();
+
+    strain
+}
+
+/// The hit window of 320s in milliseconds.
+fn hit_window(map: &Beatmap, mods: u32, clock_rate: f64) -> f64 {
+    let mut hit_window = convert::mania_hit_window(map);
+
+    if mods.hr() {
+        hit_window /= 1.4;
+    } else if mods.ez() {
+        hit_window *= 1.4;
+    }
+
+    if mods.dt() {
+        hit_window *= 1.5;
+    } else if mods.ht() {
+        hit_window *= 0.75;
+    }
+
+    (hit_window.floor() / clock_rate).ceil()
+}
+
+#[derive(Debug)]
+pub(crate) struct DifficultyHitObject {
+    column: usize,
+    delta: f64,
+    start_time: f64,
+    end_time: f64,
+}
+
+impl DifficultyHitObject {
+    #[inline]
+    fn new(base: &HitObject, prev: &HitObject, columns: f32, clock_rate: f64) -> Self {
+        let x_divisor = 512.0 / columns;
+        let column = (base.pos.x / x_divisor).floor().min(columns - 1.0) as usize;
+
+        Self {
+            column,
+            delta: (base.start_time - prev.start_time) / clock_rate,
+            start_time: base.start_time / clock_rate,
+            end_time: base.end_time() / clock_rate,
+        }
+    }
+}
+
+/// The result of a difficulty calculation on an osu!mania map.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ManiaDifficultyAttributes {
+    /// The final star rating.
+    pub stars: f64,
+    /// The hit window of 320s in milliseconds, adjusted by the clock rate.
+    pub hit_window: f64,
+    /// The amount of notes.
+    pub n_circles: usize,
+    /// The amount of hold notes.
+    pub n_sliders: usize,
+    /// The amount of spinners, only present in converted maps.
+    pub n_spinners: usize,
+}
+
+/// The result of a performance calculation on an osu!mania map.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ManiaPerformanceAttributes {
+    /// The difficulty attributes that were used for the performance calculation
+    pub difficulty: ManiaDifficultyAttributes,
+    /// The final performance points.
+    pub pp: f64,
+    /// The difficulty portion of the final pp.
+    pub pp_difficulty: f64,
+}
+
+impl ManiaPerformanceAttributes {
+    /// Return the star value.
+    #[inline]
+    pub fn stars(&self) -> f64 {
+        self.difficulty.stars
+    }
+
+    /// Return the performance point value.
+    #[inline]
+    pub fn pp(&self) -> f64 {
+        self.pp
+    }
+}
+
+impl From<ManiaPerformanceAttributes> for ManiaDifficultyAttributes {
+    fn from(attributes: ManiaPerformanceAttributes) -> Self {
+        attributes.difficulty
+    }
+}
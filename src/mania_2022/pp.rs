@@ -0,0 +1,391 @@
+use rosu_pp::{Beatmap, Mods};
+
+use crate::{
+    object_count::ObjectCount,
+    unranked::UnrankedConfig,
+    util::{cache::DifficultyCache, mode::check_mode},
+    Error, IfFcAttributes, ScoreState,
+};
+
+use super::{ManiaDifficultyAttributes, ManiaPerformanceAttributes, ManiaStars, MODE};
+
+/// Performance calculator on osu!mania maps.
+///
+/// # Example
+///
+/// ```
+/// use rosu_pp::Beatmap;
+/// use rosu_pp_older::mania_2022::ManiaPP;
+///
+/// # /*
+/// let map: Beatmap = ...
+/// # */
+/// # let map = Beatmap::default();
+///
+/// let pp_result = ManiaPP::new(&map)
+///     .mods(64) // DT
+///     .n300(12)
+///     .n100(3)
+///     .misses(1)
+///     .calculate();
+///
+/// println!("PP: {} | Stars: {}", pp_result.pp(), pp_result.stars());
+///
+/// let next_result = ManiaPP::new(&map)
+///     .attributes(pp_result)  // reusing previous results for performance
+///     .mods(64)               // has to be the same to reuse attributes
+///     .accuracy(98.5)
+///     .calculate();
+///
+/// println!("PP: {} | Stars: {}", next_result.pp(), next_result.stars());
+/// ```
+#[derive(Clone, Debug)]
+#[allow(clippy::upper_case_acronyms)]
+pub struct ManiaPP<'map> {
+    map: &'map Beatmap,
+    attributes: Option<ManiaDifficultyAttributes>,
+    cache: DifficultyCache<ManiaDifficultyAttributes>,
+    mods: u32,
+    acc: Option<f64>,
+    passed_objects: Option<usize>,
+
+    pub(crate) n320: Option<usize>,
+    pub(crate) n300: Option<usize>,
+    pub(crate) n200: Option<usize>,
+    pub(crate) n100: Option<usize>,
+    pub(crate) n50: Option<usize>,
+    pub(crate) n_misses: usize,
+
+    unranked: UnrankedConfig,
+}
+
+impl<'map> ManiaPP<'map> {
+    /// Create a new performance calculator for osu!mania maps.
+    #[inline]
+    pub fn new(map: &'map Beatmap) -> Self {
+        Self {
+            map,
+            attributes: None,
+            cache: DifficultyCache::default(),
+            mods: 0,
+            acc: None,
+            passed_objects: None,
+            n320: None,
+            n300: None,
+            n200: None,
+            n100: None,
+            n50: None,
+            n_misses: 0,
+
+            unranked: UnrankedConfig::default(),
+        }
+    }
+
+    /// Provide the result of a previous difficulty or performance calculation.
+    /// If you already calculated the attributes for the current map-mod combination,
+    /// be sure to put them in here so that they don't have to be recalculated.
+    #[inline]
+    pub fn attributes(mut self, attributes: impl ManiaAttributeProvider) -> Self {
+        if let Some(attributes) = attributes.attributes() {
+            self.attributes.replace(attributes);
+        }
+
+        self
+    }
+
+    /// Specify mods through their bit values.
+    ///
+    /// See [https://github.com/ppy/osu-api/wiki#mods](https://github.com/ppy/osu-api/wiki#mods)
+    #[inline]
+    pub fn mods(mut self, mods: u32) -> Self {
+        self.mods = mods;
+        self.cache.reset();
+
+        self
+    }
+
+    /// Specify mods through their acronyms, e.g. `"HDDT"`.
+    ///
+    /// Returns an error for unknown acronyms and for mods
+    /// that did not exist yet at the time of this version.
+    #[inline]
+    pub fn mods_str(self, acronyms: &str) -> Result<Self, Error> {
+        super::parse_mods(acronyms).map(|mods| self.mods(mods))
+    }
+
+    /// Specify the amount of 320s of a play, i.e. n_geki.
+    #[inline]
+    pub fn n320(mut self, n320: usize) -> Self {
+        self.n320.replace(n320);
+
+        self
+    }
+
+    /// Specify the amount of 300s of a play.
+    #[inline]
+    pub fn n300(mut self, n300: usize) -> Self {
+        self.n300.replace(n300);
+
+        self
+    }
+
+    /// Specify the amount of 200s of a play, i.e. n_katu.
+    #[inline]
+    pub fn n200(mut self, n200: usize) -> Self {
+        self.n200.replace(n200);
+
+        self
+    }
+
+    /// Specify the amount of 100s of a play.
+    #[inline]
+    pub fn n100(mut self, n100: usize) -> Self {
+        self.n100.replace(n100);
+
+        self
+    }
+
+    /// Specify the amount of 50s of a play.
+    #[inline]
+    pub fn n50(mut self, n50: usize) -> Self {
+        self.n50.replace(n50);
+
+        self
+    }
+
+    /// Specify the amount of misses of the play.
+    #[inline]
+    pub fn misses(mut self, n_misses: usize) -> Self {
+        self.n_misses = n_misses;
+
+        self
+    }
+
+    /// Set the accuracy between 0.0 and 100.0.
+    ///
+    /// This version rates 320s higher than 300s so the accuracy should be
+    /// weighted accordingly, i.e. a 300 is worth 300/320 of a 320.
+    #[inline]
+    pub fn accuracy(mut self, acc: f64) -> Self {
+        self.acc = Some(acc / 100.0);
+        self.n320.take();
+        self.n300.take();
+        self.n200.take();
+        self.n100.take();
+        self.n50.take();
+
+        self
+    }
+
+    /// Amount of passed objects for partial plays, e.g. a fail.
+    #[inline]
+    pub fn passed_objects(mut self, passed_objects: usize) -> Self {
+        self.passed_objects.replace(passed_objects);
+        self.cache.reset();
+
+        self
+    }
+
+    /// Provide parameters through a [`ScoreState`].
+    ///
+    /// The gekis are considered as 320s and the katus as 200s.
+    #[inline]
+    pub fn state(mut self, state: impl Into<ScoreState>) -> Self {
+        let ScoreState {
+            n_geki,
+            n300,
+            n_katu,
+            n100,
+            n50,
+            n_misses,
+            ..
+        } = state.into();
+
+        self.n320 = Some(n_geki);
+        self.n300 = Some(n300);
+        self.n200 = Some(n_katu);
+        self.n100 = Some(n100);
+        self.n50 = Some(n50);
+        self.n_misses = n_misses;
+
+        self
+    }
+
+    /// Specify whether [`try_calculate`](Self::try_calculate) should accept mods
+    /// that bancho never awarded pp for, i.e. AT, CN, TP, and RX.
+    ///
+    /// Useful for private servers, defaults to `false`.
+    #[inline]
+    pub fn allow_unranked(mut self, allow_unranked: bool) -> Self {
+        self.unranked.allow_unranked = allow_unranked;
+
+        self
+    }
+
+    /// Same as [`calculate`](Self::calculate) but returns an error if the map can
+    /// not be calculated by this version or if the mods contain unranked mods.
+    pub fn try_calculate(self) -> Result<ManiaPerformanceAttributes, Error> {
+        check_mode(self.map.mode, MODE)?;
+        self.unranked.check(self.mods)?;
+
+        Ok(self.calculate())
+    }
+
+    /// Calculate the performance of the score and of its full combo.
+    ///
+    /// For the full combo, the misses are counted as 320s just like the hits
+    /// that were not specified. If only the accuracy was specified, it is kept as it is.
+    pub fn calculate_if_fc(self) -> IfFcAttributes<ManiaPerformanceAttributes> {
+        let fc = self.if_fc();
+
+        IfFcAttributes {
+            score: self.calculate(),
+            fc: fc.calculate(),
+        }
+    }
+
+    /// Same as [`calculate_if_fc`](Self::calculate_if_fc) but returns an error if the map can
+    /// not be calculated by this version or if the mods contain unranked mods.
+    pub fn try_calculate_if_fc(self) -> Result<IfFcAttributes<ManiaPerformanceAttributes>, Error> {
+        check_mode(self.map.mode, MODE)?;
+        self.unranked.check(self.mods)?;
+
+        Ok(self.calculate_if_fc())
+    }
+
+    fn if_fc(&self) -> Self {
+        let mut fc = self.clone();
+        fc.n_misses = 0;
+
+        fc
+    }
+
+    /// Calculate all performance related values, including pp and stars.
+    ///
+    /// Maps of other modes, except for converted osu!standard maps, result in default attributes,
+    /// [`try_calculate`](Self::try_calculate) returns an error for them instead.
+    pub fn calculate(mut self) -> ManiaPerformanceAttributes {
+        if check_mode(self.map.mode, MODE).is_err() {
+            return ManiaPerformanceAttributes::default();
+        }
+
+        let attributes = self.attributes.take().unwrap_or_else(|| {
+            self.cache.get_or_insert_with(|| {
+                ManiaStars::new(self.map)
+                    .mods(self.mods)
+                    .passed_objects(self.passed_objects.unwrap_or(usize::MAX))
+                    .calculate()
+            })
+        });
+
+        let (total_hits, acc) = self.total_hits_and_acc(&attributes);
+
+        let mut multiplier = 8.0;
+
+        if self.mods.nf() {
+            multiplier *= 0.75;
+        }
+
+        if self.mods.ez() {
+            multiplier *= 0.5;
+        }
+
+        let pp_difficulty = (attributes.stars - 0.15).max(0.05).powf(2.2)
+            // From 80% accuracy, 1/20th of total pp is awarded per additional 1% accuracy
+            * (5.0 * acc - 4.0).max(0.0)
+            // Length bonus, capped at 1500 notes
+            * (1.0 + 0.1 * (total_hits as f64 / 1500.0).min(1.0));
+
+        ManiaPerformanceAttributes {
+            difficulty: attributes,
+            pp: pp_difficulty * multiplier,
+            pp_difficulty,
+        }
+    }
+
+    /// The amount of hits and the accuracy in which 320s are worth more than 300s.
+    ///
+    /// Hits that are not specified are assumed to be 320s.
+    fn total_hits_and_acc(&self, attributes: &ManiaDifficultyAttributes) -> (usize, f64) {
+        let total = attributes.n_objects();
+
+        let hit_results = [self.n320, self.n300, self.n200, self.n100, self.n50];
+
+        if hit_results.iter().all(Option::is_none) {
+            return (total, self.acc.unwrap_or(1.0));
+        }
+
+        let n300 = self.n300.unwrap_or(0);
+        let n200 = self.n200.unwrap_or(0);
+        let n100 = self.n100.unwrap_or(0);
+        let n50 = self.n50.unwrap_or(0);
+
+        let given = self.n320.unwrap_or(0) + n300 + n200 + n100 + n50 + self.n_misses;
+        let n320 = self.n320.unwrap_or(0) + total.saturating_sub(given);
+
+        let total_hits = given.max(total);
+
+        if total_hits == 0 {
+            return (0, 0.0);
+        }
+
+        let points = 320 * n320 + 300 * n300 + 200 * n200 + 100 * n100 + 50 * n50;
+        let acc = points as f64 / (320 * total_hits) as f64;
+
+        (total_hits, acc)
+    }
+}
+
+/// Abstract type to provide flexibility when passing difficulty attributes to a performance calculation.
+pub trait ManiaAttributeProvider {
+    /// Provide the difficulty attributes.
+    fn attributes(self) -> Option<ManiaDifficultyAttributes>;
+}
+
+impl ManiaAttributeProvider for ManiaDifficultyAttributes {
+    #[inline]
+    fn attributes(self) -> Option<ManiaDifficultyAttributes> {
+        Some(self)
+    }
+}
+
+impl ManiaAttributeProvider for ManiaPerformanceAttributes {
+    #[inline]
+    fn attributes(self) -> Option<ManiaDifficultyAttributes> {
+        Some(self.difficulty)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rosu_pp::GameMode;
+
+    use super::*;
+
+    #[test]
+    fn hit_results_to_acc() {
+        let map = Beatmap::default();
+
+        let attributes = ManiaDifficultyAttributes {
+            n_circles: 10,
+            ..Default::default()
+        };
+
+        let calc = ManiaPP::new(&map).n300(4).n100(1);
+        let (total_hits, acc) = calc.total_hits_and_acc(&attributes);
+
+        assert_eq!(total_hits, 10);
+        assert!((acc - (5 * 320 + 4 * 300 + 100) as f64 / 3200.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn other_mode_defaults() {
+        let map = Beatmap {
+            mode: GameMode::Taiko,
+            ..Default::default()
+        };
+
+        assert_eq!(ManiaStars::new(&map).calculate(), Default::default());
+        assert_eq!(ManiaPP::new(&map).calculate(), Default::default());
+    }
+}
@@ -0,0 +1,150 @@
+use super::DifficultyHitObject;
+
+use crate::util::sum::StrainSum;
+
+use std::cmp::Ordering;
+
+#[derive(Clone, Debug)]
+pub(crate) struct Strain {
+    current_strain: f64,
+    pub(crate) curr_section_peak: f64,
+
+    individual_strain: f64,
+    overall_strain: f64,
+
+    start_times: Vec<f64>,
+    end_times: Vec<f64>,
+    individual_strains: Vec<f64>,
+    pub(crate) strain_peaks: Vec<f64>,
+
+    prev_time: Option<f64>,
+}
+
+const INDIVIDUAL_DECAY_BASE: f64 = 0.125;
+const OVERALL_DECAY_BASE: f64 = 0.3;
+const RELEASE_THRESHOLD: f64 = 24.0;
+
+const DECAY_WEIGHT: f64 = 0.9;
+
+impl Strain {
+    #[inline]
+    pub(crate) fn new(column_count: u8) -> Self {
+        Self {
+            current_strain: 0.0,
+            curr_section_peak: 0.0,
+
+            individual_strain: 0.0,
+            overall_strain: 1.0,
+
+            start_times: vec![0.0; column_count as usize],
+            end_times: vec![0.0; column_count as usize],
+            individual_strains: vec![0.0; column_count as usize],
+            strain_peaks: Vec::with_capacity(128),
+
+            prev_time: None,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn save_current_peak(&mut self) {
+        self.strain_peaks.push(self.curr_section_peak);
+    }
+
+    #[inline]
+    pub(crate) fn start_new_section_from(&mut self, time: f64) {
+        self.curr_section_peak = self.peak_strain(time - self.prev_time.unwrap());
+    }
+
+    #[inline]
+    fn peak_strain(&self, delta_time: f64) -> f64 {
+        apply_decay(self.individual_strain, delta_time, INDIVIDUAL_DECAY_BASE)
+            + apply_decay(self.overall_strain, delta_time, OVERALL_DECAY_BASE)
+    }
+
+    #[inline]
+    pub(crate) fn process(&mut self, current: &DifficultyHitObject) {
+        // The strain does not decay on its own, it's entirely made up of
+        // the individual and overall strain
+        self.current_strain += self.strain_value_of(current);
+        self.curr_section_peak = self.current_strain.max(self.curr_section_peak);
+        self.prev_time.replace(current.start_time);
+    }
+
+    fn strain_value_of(&mut self, current: &DifficultyHitObject) -> f64 {
+        let start_time = current.start_time;
+        let end_time = current.end_time;
+        let column = current.column;
+
+        let mut is_overlapping = false;
+
+        // * Lowest value we can assume with the current information
+        let mut closest_end_time = (end_time - start_time).abs();
+        // * Factor to all additional strains in case something else is held
+        let mut hold_factor = 1.0;
+        // * Addition to the current note in case it's a hold and has to be released awkwardly
+        let mut hold_addition = 0.0;
+
+        for &prev_end_time in self.end_times.iter() {
+            // * The current note is overlapped if a previous note or end is overlapping the current note body
+            is_overlapping |= prev_end_time > start_time + 1.0 && end_time > prev_end_time + 1.0;
+
+            // * We give a slight bonus to everything if something is held meanwhile
+            if prev_end_time > end_time + 1.0 {
+                hold_factor = 1.25;
+            }
+
+            closest_end_time = closest_end_time.min((end_time - prev_end_time).abs());
+        }
+
+        // * The hold addition is given if there was an overlap, however it is only valid if there are no other note with a similar ending.
+        // * Releasing multiple notes is just as easy as releasing 1. Nerfs the hold addition by half if the closest release is release_threshold away.
+        if is_overlapping {
+            hold_addition = 1.0 / (1.0 + (0.5 * (RELEASE_THRESHOLD - closest_end_time)).exp());
+        }
+
+        // * Decay and increase individualStrains in own column
+        self.individual_strains[column] = apply_decay(
+            self.individual_strains[column],
+            start_time - self.start_times[column],
+            INDIVIDUAL_DECAY_BASE,
+        );
+        self.individual_strains[column] += 2.0 * hold_factor;
+
+        // * For notes at the same time (in a chord), the individualStrain should be the hardest individualStrain out of those columns
+        self.individual_strain = if current.delta <= 1.0 {
+            self.individual_strain.max(self.individual_strains[column])
+        } else {
+            self.individual_strains[column]
+        };
+
+        // * Decay and increase overallStrain
+        self.overall_strain = apply_decay(self.overall_strain, current.delta, OVERALL_DECAY_BASE)
+            + (1.0 + hold_addition) * hold_factor;
+
+        self.start_times[column] = start_time;
+        self.end_times[column] = end_time;
+
+        // * By subtracting CurrentStrain, this skill effectively only considers the maximum strain of any one hitobject within each strain section.
+        self.individual_strain + self.overall_strain - self.current_strain
+    }
+
+    #[inline]
+    pub(crate) fn difficulty_value(strain_peaks: &mut [f64]) -> f64 {
+        let mut difficulty = StrainSum::default();
+        let mut weight = 1.0;
+
+        strain_peaks.sort_unstable_by(|a, b| b.partial_cmp(a).unwrap_or(Ordering::Equal));
+
+        for &strain in strain_peaks.iter() {
+            difficulty += strain * weight;
+            weight *= DECAY_WEIGHT;
+        }
+
+        difficulty.value()
+    }
+}
+
+#[inline]
+fn apply_decay(value: f64, delta_time: f64, decay_base: f64) -> f64 {
+    value * decay_base.powf(delta_time / 1000.0)
+}
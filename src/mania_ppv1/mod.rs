@@ -4,30 +4,189 @@ mod strain;
 pub use pp::*;
 use strain::Strain;
 
-use rosu_pp::{parse::HitObject, Beatmap, Mods};
+use rosu_pp::{parse::HitObject, Beatmap, GameMode, Mods};
+
+use crate::{
+    convert,
+    mods::{self, ScoreMultipliers},
+    object_count::count_objects,
+    Error,
+};
 
 const SECTION_LEN: f32 = 400.0;
 const STAR_SCALING_FACTOR: f32 = 0.018;
 
+/// The approximate date at which this version went live, formatted as `YYYY-MM-DD`.
+pub const VERSION_DATE: &str = "2014-01-01";
+
+/// The name of this version within its mode.
+pub const VERSION_NAME: &str = "ppv1";
+
+/// A short description of what changed in this version.
+pub const VERSION_DESCRIPTION: &str =
+    "The original ppv2 values of osu!mania, with the strain value based on score.";
+
+/// The mode this version calculates, i.e. osu!mania.
+pub const MODE: GameMode = GameMode::Mania;
+
+crate::calculator::difficulty_calculator!(ManiaCalculator, ManiaPpv1);
+
+/// Parse mod acronyms like `"HDDT"` or `"HD,DT"` into the mods value of this version.
+///
+/// Mods that did not exist yet at the time of this version cause an error.
+#[inline]
+pub fn parse_mods(acronyms: &str) -> Result<u32, Error> {
+    mods::parse_available(acronyms, mods::BEFORE_SCORE_V2)
+}
+
+/// The score multiplier of the mods in this version.
+#[inline]
+pub fn score_multiplier(mods: u32) -> f32 {
+    ScoreMultipliers::MANIA.multiplier(mods)
+}
+
+/// Calculate the difficulty and the performance of a score in one pass.
+///
+/// Calling [`stars`] and then [`ManiaPP`] without passing the attributes along calculates
+/// the difficulty twice, this function avoids that.
+pub fn stars_and_pp(
+    map: &Beatmap,
+    mods: u32,
+    score: u32,
+) -> (ManiaDifficultyAttributes, ManiaPerformanceAttributes) {
+    let performance = ManiaPP::new(map).mods(mods).score(score).calculate();
+
+    (performance.difficulty.clone(), performance)
+}
+
+/// Difficulty calculator on osu!mania maps.
+///
+/// # Example
+///
+/// ```
+/// use rosu_pp::Beatmap;
+/// use rosu_pp_older::mania_ppv1::ManiaStars;
+///
+/// # /*
+/// let map: Beatmap = ...
+/// # */
+/// # let map = Beatmap::default();
+///
+/// let difficulty_attrs = ManiaStars::new(&map)
+///     .mods(8 + 64) // HDDT
+///     .calculate();
+///
+/// println!("Stars: {}", difficulty_attrs.stars);
+/// ```
+#[derive(Clone, Debug)]
+pub struct ManiaStars<'map> {
+    map: &'map Beatmap,
+    mods: u32,
+    passed_objects: Option<usize>,
+    clock_rate: Option<f64>,
+}
+
+impl<'map> ManiaStars<'map> {
+    /// Create a new difficulty calculator for osu!mania maps.
+    #[inline]
+    pub fn new(map: &'map Beatmap) -> Self {
+        Self {
+            map,
+            mods: 0,
+            passed_objects: None,
+            clock_rate: None,
+        }
+    }
+
+    /// Specify mods through their bit values.
+    ///
+    /// See [https://github.com/ppy/osu-api/wiki#mods](https://github.com/ppy/osu-api/wiki#mods)
+    #[inline]
+    pub fn mods(mut self, mods: u32) -> Self {
+        self.mods = mods;
+
+        self
+    }
+
+    /// Specify mods through their acronyms, e.g. `"HDDT"`.
+    ///
+    /// Returns an error for unknown acronyms and for mods
+    /// that did not exist yet at the time of this version.
+    #[inline]
+    pub fn mods_str(self, acronyms: &str) -> Result<Self, Error> {
+        parse_mods(acronyms).map(|mods| self.mods(mods))
+    }
+
+    /// Amount of passed objects for partial plays, e.g. a fail.
+    #[inline]
+    pub fn passed_objects(mut self, passed_objects: usize) -> Self {
+        self.passed_objects = Some(passed_objects);
+
+        self
+    }
+
+    /// Adjust the clock rate used in the calculation, e.g. `1.4` for a lazer DT
+    /// score that was set at 1.4x.
+    ///
+    /// If none is specified, the clock rate of the mods is used.
+    #[inline]
+    pub fn clock_rate(mut self, clock_rate: f64) -> Self {
+        self.clock_rate = Some(clock_rate);
+
+        self
+    }
+
+    /// Calculate all difficulty related values, including stars.
+    ///
+    /// Maps of other modes, except for converted osu!standard maps, result in default attributes.
+    #[inline]
+    pub fn calculate(self) -> ManiaDifficultyAttributes {
+        let clock_rate = self.clock_rate.unwrap_or_else(|| self.mods.clock_rate());
+
+        stars_with_clock_rate(self.map, self.mods, self.passed_objects, clock_rate)
+    }
+}
+
 /// Star calculation for osu!mania maps
 ///
 /// In case of a partial play, e.g. a fail, one can specify the amount of passed objects.
-pub fn stars(
+///
+/// Maps of other modes, except for converted osu!standard maps, result in default attributes.
+pub fn stars(map: &Beatmap, mods: u32, passed_objects: Option<usize>) -> ManiaDifficultyAttributes {
+    stars_with_clock_rate(map, mods, passed_objects, mods.clock_rate())
+}
+
+/// Star calculation for osu!mania maps with a custom clock rate.
+///
+/// The clock rate replaces the one of DT or HT, e.g. `1.4` for a lazer DT
+/// score that was set at 1.4x.
+pub fn stars_with_clock_rate(
     map: &Beatmap,
-    mods: impl Mods,
+    mods: u32,
     passed_objects: Option<usize>,
+    clock_rate: f64,
 ) -> ManiaDifficultyAttributes {
+    let columns = match convert::mania_columns(map, mods) {
+        Ok(columns) => columns,
+        Err(_) => return ManiaDifficultyAttributes::default(),
+    };
+
     let take = passed_objects.unwrap_or(map.hit_objects.len());
+    let (n_circles, n_sliders, n_spinners) = count_objects(map.hit_objects.iter().take(take));
 
     if take < 2 {
-        return ManiaDifficultyAttributes::default();
+        return ManiaDifficultyAttributes {
+            stars: 0.0,
+            n_circles,
+            n_sliders,
+            n_spinners,
+        };
     }
 
-    let columns = map.cs.round().max(1.0) as u8;
-
-    let clock_rate = mods.clock_rate() as f32;
+    let clock_rate = clock_rate as f32;
     let section_len = SECTION_LEN * clock_rate;
     let mut strain = Strain::new(columns);
+    let columns = columns as f32;
 
     let mut hit_objects = map
         .hit_objects
@@ -35,7 +194,7 @@ pub fn stars(
         .take(take)
         .skip(1)
         .zip(map.hit_objects.iter())
-        .map(|(base, prev)| DifficultyHitObject::new(base, prev, map.cs, clock_rate));
+        .map(|(base, prev)| DifficultyHitObject::new(base, prev, columns, clock_rate));
 
     // No strain for first object
     let mut current_section_end =
@@ -66,7 +225,12 @@ pub fn stars(
 
     let stars = (strain.difficulty_value() * STAR_SCALING_FACTOR) as f64;
 
-    ManiaDifficultyAttributes { stars }
+    ManiaDifficultyAttributes {
+        stars,
+        n_circles,
+        n_sliders,
+        n_spinners,
+    }
 }
 
 #[derive(Debug)]
@@ -78,9 +242,9 @@ pub(crate) struct DifficultyHitObject<'o> {
 
 impl<'o> DifficultyHitObject<'o> {
     #[inline]
-    fn new(base: &'o HitObject, prev: &'o HitObject, cs: f32, clock_rate: f32) -> Self {
-        let x_divisor = 512.0 / cs;
-        let column = (base.pos.x / x_divisor).floor() as usize;
+    fn new(base: &'o HitObject, prev: &'o HitObject, columns: f32, clock_rate: f32) -> Self {
+        let x_divisor = 512.0 / columns;
+        let column = (base.pos.x / x_divisor).floor().min(columns - 1.0) as usize;
 
         Self {
             base,
@@ -90,14 +254,24 @@ impl<'o> DifficultyHitObject<'o> {
     }
 }
 
-#[derive(Default)]
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct ManiaDifficultyAttributes {
     pub stars: f64,
+    /// The amount of notes.
+    pub n_circles: usize,
+    /// The amount of hold notes.
+    pub n_sliders: usize,
+    /// The amount of spinners, only present in converted maps.
+    pub n_spinners: usize,
 }
 
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct ManiaPerformanceAttributes {
     pub difficulty: ManiaDifficultyAttributes,
     pub pp: f64,
     pub pp_acc: f64,
     pub pp_strain: f64,
+    pub nf_multiplier: f64,
 }
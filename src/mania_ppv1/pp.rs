@@ -1,7 +1,15 @@
-use super::{stars, ManiaDifficultyAttributes, ManiaPerformanceAttributes};
+use super::{stars, ManiaDifficultyAttributes, ManiaPerformanceAttributes, MODE};
 
 use rosu_pp::{Beatmap, DifficultyAttributes, Mods, PerformanceAttributes};
 
+use crate::{
+    convert,
+    object_count::count_objects,
+    unranked::UnrankedConfig,
+    util::{cache::DifficultyCache, mode::check_mode},
+    Error, IfFcAttributes, ScoreState,
+};
+
 /// Calculator for pp on osu!mania maps.
 ///
 /// # Example
@@ -31,10 +39,13 @@ use rosu_pp::{Beatmap, DifficultyAttributes, Mods, PerformanceAttributes};
 pub struct ManiaPP<'m> {
     map: &'m Beatmap,
     stars: Option<f32>,
+    cache: DifficultyCache<f32>,
     mods: u32,
     score: Option<f32>,
     acc: f32,
     passed_objects: Option<usize>,
+
+    unranked: UnrankedConfig,
 }
 
 impl<'m> ManiaPP<'m> {
@@ -43,10 +54,13 @@ impl<'m> ManiaPP<'m> {
         Self {
             map,
             stars: None,
+            cache: DifficultyCache::default(),
             mods: 0,
             score: None,
             acc: 1.0,
             passed_objects: None,
+
+            unranked: UnrankedConfig::default(),
         }
     }
 
@@ -70,10 +84,20 @@ impl<'m> ManiaPP<'m> {
     #[inline]
     pub fn mods(mut self, mods: u32) -> Self {
         self.mods = mods;
+        self.cache.reset();
 
         self
     }
 
+    /// Specify mods through their acronyms, e.g. `"HDDT"`.
+    ///
+    /// Returns an error for unknown acronyms and for mods
+    /// that did not exist yet at the time of this version.
+    #[inline]
+    pub fn mods_str(self, acronyms: &str) -> Result<Self, Error> {
+        super::parse_mods(acronyms).map(|mods| self.mods(mods))
+    }
+
     /// Specify the score of a play.
     /// On `NoMod` its between 0 and 1,000,000, on `Easy` between 0 and 500,000, etc.
     #[inline]
@@ -95,15 +119,93 @@ impl<'m> ManiaPP<'m> {
     #[inline]
     pub fn passed_objects(mut self, passed_objects: usize) -> Self {
         self.passed_objects.replace(passed_objects);
+        self.cache.reset();
+
+        self
+    }
+
+    /// Provide parameters through a [`ScoreState`].
+    ///
+    /// This version only considers the accuracy of the hits, the gekis count as 300s
+    /// and the katus as 200s. The score still needs to be specified through
+    /// [`score`](ManiaPP::score).
+    #[inline]
+    pub fn state(mut self, state: impl Into<ScoreState>) -> Self {
+        let ScoreState {
+            n_geki,
+            n300,
+            n_katu,
+            n100,
+            n50,
+            n_misses,
+            ..
+        } = state.into();
+
+        let total_hits = n_geki + n300 + n_katu + n100 + n50 + n_misses;
+
+        if total_hits > 0 {
+            let numerator = 300 * (n_geki + n300) + 200 * n_katu + 100 * n100 + 50 * n50;
+            self.acc = numerator as f32 / (300 * total_hits) as f32;
+        }
+
+        self
+    }
+
+    /// Specify whether [`try_calculate`](Self::try_calculate) should accept mods
+    /// that bancho never awarded pp for, i.e. AT, CN, TP, and RX.
+    ///
+    /// Useful for private servers, defaults to `false`.
+    #[inline]
+    pub fn allow_unranked(mut self, allow_unranked: bool) -> Self {
+        self.unranked.allow_unranked = allow_unranked;
 
         self
     }
 
+    /// Same as [`calculate`](Self::calculate) but returns an error if the map can
+    /// not be calculated by this version or if the mods contain unranked mods.
+    pub fn try_calculate(self) -> Result<ManiaPerformanceAttributes, Error> {
+        check_mode(self.map.mode, MODE)?;
+        self.unranked.check(self.mods)?;
+
+        Ok(self.calculate())
+    }
+
+    /// Calculate the performance of the score and of its full combo.
+    ///
+    /// This version only rates the score and the accuracy which a full combo
+    /// does not change, so both performances are the same.
+    pub fn calculate_if_fc(self) -> IfFcAttributes<ManiaPerformanceAttributes> {
+        let score = self.calculate();
+
+        IfFcAttributes {
+            fc: score.clone(),
+            score,
+        }
+    }
+
+    /// Same as [`calculate_if_fc`](Self::calculate_if_fc) but returns an error if the map can
+    /// not be calculated by this version or if the mods contain unranked mods.
+    pub fn try_calculate_if_fc(self) -> Result<IfFcAttributes<ManiaPerformanceAttributes>, Error> {
+        check_mode(self.map.mode, MODE)?;
+        self.unranked.check(self.mods)?;
+
+        Ok(self.calculate_if_fc())
+    }
+
     /// Returns an object which contains the pp and stars.
+    ///
+    /// Maps of other modes, except for converted osu!standard maps, result in default attributes,
+    /// [`try_calculate`](Self::try_calculate) returns an error for them instead.
     pub fn calculate(self) -> ManiaPerformanceAttributes {
-        let stars = self
-            .stars
-            .unwrap_or_else(|| stars(self.map, self.mods, self.passed_objects).stars as f32);
+        if check_mode(self.map.mode, MODE).is_err() {
+            return ManiaPerformanceAttributes::default();
+        }
+
+        let stars = self.stars.unwrap_or_else(|| {
+            self.cache
+                .get_or_insert_with(|| stars(self.map, self.mods, self.passed_objects).stars as f32)
+        });
 
         let ez = self.mods.ez();
         let nf = self.mods.nf();
@@ -122,16 +224,15 @@ impl<'m> ManiaPP<'m> {
 
         let mut multiplier = 1.1;
 
-        if nf {
-            multiplier *= 0.9;
-        }
+        let nf_multiplier = if nf { 0.9 } else { 1.0 };
+        multiplier *= nf_multiplier;
 
         if ez {
             multiplier *= 0.5;
         }
 
         let hit_window = {
-            let mut od = 34.0 + 3.0 * (10.0 - self.map.od).clamp(0.0, 10.0);
+            let mut od = convert::mania_hit_window(self.map) as f32;
 
             if ez {
                 od *= 1.4;
@@ -149,13 +250,21 @@ impl<'m> ManiaPP<'m> {
 
         let pp = (strain_value.powf(1.1) + acc_value.powf(1.1)).powf(1.0 / 1.1) * multiplier;
 
+        let take = self.passed_objects.unwrap_or(self.map.hit_objects.len());
+        let (n_circles, n_sliders, n_spinners) =
+            count_objects(self.map.hit_objects.iter().take(take));
+
         ManiaPerformanceAttributes {
             difficulty: ManiaDifficultyAttributes {
                 stars: stars as f64,
+                n_circles,
+                n_sliders,
+                n_spinners,
             },
             pp_acc: acc_value as f64,
             pp_strain: strain_value as f64,
             pp: pp as f64,
+            nf_multiplier: nf_multiplier as f64,
         }
     }
 
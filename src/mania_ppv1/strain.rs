@@ -1,5 +1,7 @@
 use super::DifficultyHitObject;
 
+use crate::util::sum::StrainSum;
+
 use std::cmp::Ordering;
 
 pub(crate) struct Strain {
@@ -108,7 +110,7 @@ impl Strain {
 
     #[inline]
     pub(crate) fn difficulty_value(&mut self) -> f32 {
-        let mut difficulty = 0.0;
+        let mut difficulty = StrainSum::default();
         let mut weight = 1.0;
 
         self.strain_peaks
@@ -119,7 +121,7 @@ impl Strain {
             weight *= DECAY_WEIGHT;
         }
 
-        difficulty
+        difficulty.value()
     }
 }
 
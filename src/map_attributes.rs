@@ -0,0 +1,46 @@
+use crate::HitWindows;
+
+/// The attributes of an osu!standard map after applying mods, the way a version computed them.
+///
+/// Unlike the difficulty attributes, these do not require processing the hit objects.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct MapAttributes {
+    /// The effective approach rate, e.g. AR 9 with DT results in AR 10.33.
+    pub ar: f64,
+    /// The effective overall difficulty, derived from the 300 hit window.
+    pub od: f64,
+    /// The circle size after applying HR or EZ.
+    pub cs: f64,
+    /// The drain rate after applying HR or EZ.
+    pub hp: f64,
+    /// The clock rate of the mods.
+    pub clock_rate: f64,
+    /// The hit windows in milliseconds, already divided by the clock rate.
+    pub hit_windows: HitWindows,
+}
+
+#[cfg(all(test, feature = "osu-2014-may", feature = "osu-2019"))]
+mod test {
+    use rosu_pp::Beatmap;
+
+    use crate::{osu_2014_may, osu_2019};
+
+    #[test]
+    fn od_per_era() {
+        let map = Beatmap {
+            od: 8.5,
+            ..Default::default()
+        };
+
+        // The rounded window of 2014 results in OD 8.5 while 2019 is exact
+        let old = osu_2014_may::map_attributes(&map, 64);
+        let new = osu_2019::map_attributes(&map, 64);
+
+        assert!((old.hit_windows.n300 - 28.5 / 1.5).abs() < 1e-10);
+        assert!((new.hit_windows.n300 - 29.0 / 1.5).abs() < 1e-10);
+        assert!((old.od - (79.5 - 19.0) / 6.0).abs() < 1e-10);
+        assert!((new.od - (80.0 - 29.0 / 1.5) / 6.0).abs() < 1e-10);
+        assert_eq!(old.clock_rate, 1.5);
+    }
+}
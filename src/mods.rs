@@ -0,0 +1,321 @@
+//! Mod related types that are not covered by [`rosu_pp::Mods`].
+
+#[cfg(feature = "serde")]
+mod api;
+
+#[cfg(feature = "serde")]
+pub use api::{ApiMod, ApiModSettings, ApiModsInput};
+
+use rosu_pp::{beatmap::BeatmapAttributesBuilder, Mods};
+
+use crate::{
+    util::mods::{AUTOPILOT, MIRROR, SCORE_V2},
+    Error,
+};
+
+const ACRONYMS: [(&str, u32); 31] = [
+    ("NF", 1),
+    ("EZ", 1 << 1),
+    ("TD", 1 << 2),
+    ("HD", 1 << 3),
+    ("HR", 1 << 4),
+    ("SD", 1 << 5),
+    ("DT", 1 << 6),
+    ("RX", 1 << 7),
+    ("HT", 1 << 8),
+    ("NC", (1 << 9) | (1 << 6)),
+    ("FL", 1 << 10),
+    ("AT", 1 << 11),
+    ("SO", 1 << 12),
+    ("AP", AUTOPILOT),
+    ("PF", (1 << 14) | (1 << 5)),
+    ("4K", 1 << 15),
+    ("5K", 1 << 16),
+    ("6K", 1 << 17),
+    ("7K", 1 << 18),
+    ("8K", 1 << 19),
+    ("FI", 1 << 20),
+    ("RD", 1 << 21),
+    ("CN", 1 << 22),
+    ("TP", 1 << 23),
+    ("9K", 1 << 24),
+    ("CO", 1 << 25),
+    ("1K", 1 << 26),
+    ("3K", 1 << 27),
+    ("2K", 1 << 28),
+    ("V2", SCORE_V2),
+    ("MR", MIRROR),
+];
+
+pub(crate) const ALL: u32 = u32::MAX;
+/// Mods available before ScoreV2 was added in 2016.
+pub(crate) const BEFORE_SCORE_V2: u32 = ALL & !(SCORE_V2 | MIRROR);
+/// Mods available before Mirror was added in 2019.
+pub(crate) const BEFORE_MIRROR: u32 = ALL & !MIRROR;
+
+/// The ScoreV1 multipliers of difficulty changing mods.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ScoreMultipliers {
+    /// The multiplier of EZ.
+    pub ez: f32,
+    /// The multiplier of NF.
+    pub nf: f32,
+    /// The multiplier of HT.
+    pub ht: f32,
+    /// The multiplier of HR.
+    pub hr: f32,
+    /// The multiplier of DT and NC.
+    pub dt: f32,
+    /// The multiplier of HD.
+    pub hd: f32,
+    /// The multiplier of FL.
+    pub fl: f32,
+    /// The multiplier of SO.
+    pub so: f32,
+}
+
+impl ScoreMultipliers {
+    /// The multipliers of osu!standard.
+    pub const OSU: Self = Self {
+        ez: 0.5,
+        nf: 0.5,
+        ht: 0.3,
+        hr: 1.06,
+        dt: 1.12,
+        hd: 1.06,
+        fl: 1.12,
+        so: 0.9,
+    };
+
+    /// The multipliers of osu!taiko.
+    pub const TAIKO: Self = Self {
+        so: 1.0,
+        ..Self::OSU
+    };
+
+    /// The multipliers of osu!ctb.
+    pub const CATCH: Self = Self {
+        hr: 1.12,
+        dt: 1.06,
+        so: 1.0,
+        ..Self::OSU
+    };
+
+    /// The multipliers of osu!mania.
+    pub const MANIA: Self = Self {
+        ez: 0.5,
+        nf: 0.5,
+        ht: 0.5,
+        hr: 1.0,
+        dt: 1.0,
+        hd: 1.0,
+        fl: 1.0,
+        so: 1.0,
+    };
+
+    /// The multipliers of osu!standard scores with ScoreV2, added in 2016.
+    pub const SCORE_V2: Self = Self {
+        nf: 1.0,
+        hr: 1.1,
+        dt: 1.2,
+        ..Self::OSU
+    };
+
+    /// The total multiplier of the mods.
+    pub fn multiplier(&self, mods: u32) -> f32 {
+        let mut multiplier = 1.0;
+
+        if mods.ez() {
+            multiplier *= self.ez;
+        }
+
+        if mods.nf() {
+            multiplier *= self.nf;
+        }
+
+        if mods.ht() {
+            multiplier *= self.ht;
+        }
+
+        if mods.hr() {
+            multiplier *= self.hr;
+        }
+
+        if mods.dt() {
+            multiplier *= self.dt;
+        }
+
+        if mods.hd() {
+            multiplier *= self.hd;
+        }
+
+        if mods.fl() {
+            multiplier *= self.fl;
+        }
+
+        if mods.so() {
+            multiplier *= self.so;
+        }
+
+        multiplier
+    }
+}
+
+/// How the aim bonus of FL scales with the length of a play.
+///
+/// Only relevant for versions in which FL was a plain multiplier on aim.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FlLengthBonus {
+    /// A flat multiplier regardless of length, used until 2015.
+    Flat,
+    /// The object-based length bonus is applied once more, used from 2015 until 2019.
+    Length,
+    /// Like [`Length`](FlLengthBonus::Length) but based on the play's combo
+    /// instead of its amount of objects.
+    Combo,
+    /// The bonus of the 2019 FL rework, growing linearly past 500 objects.
+    Objects,
+}
+
+impl FlLengthBonus {
+    pub(crate) fn multiplier(self, total_hits: f32, combo: Option<usize>) -> f32 {
+        match self {
+            Self::Flat => 1.5,
+            Self::Length => 1.45 * len_bonus(total_hits),
+            Self::Combo => 1.45 * len_bonus(combo.map_or(total_hits, |combo| combo as f32)),
+            Self::Objects => {
+                1.0 + 0.35 * (total_hits / 200.0).min(1.0)
+                    + (total_hits > 200.0) as u8 as f32
+                        * 0.3
+                        * ((total_hits - 200.0) / 300.0).min(1.0)
+                    + (total_hits > 500.0) as u8 as f32 * (total_hits - 500.0) / 1200.0
+            }
+        }
+    }
+}
+
+fn len_bonus(n: f32) -> f32 {
+    0.95 + 0.4 * (n / 2000.0).min(1.0) + (n > 2000.0) as u8 as f32 * 0.5 * (n / 2000.0).log10()
+}
+
+/// Parse mod acronyms like `"HDDT"`, `"HD,DT"`, or `"+hd dt"` into their bit value.
+///
+/// `"NM"` or an empty string parse as nomod.
+/// To also validate that the mods existed at the time of a version,
+/// use the `parse_mods` function of the version's module instead.
+pub fn parse(acronyms: &str) -> Result<u32, Error> {
+    parse_available(acronyms, ALL)
+}
+
+pub(crate) fn parse_available(acronyms: &str, available: u32) -> Result<u32, Error> {
+    let acronyms = acronyms.trim();
+
+    if acronyms.eq_ignore_ascii_case("NM") {
+        return Ok(0);
+    }
+
+    let bytes: Vec<u8> = acronyms
+        .bytes()
+        .filter(|byte| !matches!(byte, b',' | b' ' | b'+'))
+        .map(|byte| byte.to_ascii_uppercase())
+        .collect();
+
+    if bytes.len() % 2 != 0 {
+        return Err(Error::UnknownMod(acronyms.to_owned()));
+    }
+
+    let mut mods = 0;
+
+    for chunk in bytes.chunks_exact(2) {
+        let bits = ACRONYMS
+            .iter()
+            .find(|(acronym, _)| acronym.as_bytes() == chunk)
+            .map(|(_, bits)| *bits)
+            .ok_or_else(|| Error::UnknownMod(String::from_utf8_lossy(chunk).into_owned()))?;
+
+        if bits & !available > 0 {
+            return Err(Error::UnavailableMods(bits & !available));
+        }
+
+        mods |= bits;
+    }
+
+    Ok(mods)
+}
+
+/// osu!lazer's Difficulty Adjust mod.
+///
+/// Specified values replace the map's base values before
+/// the era's HR, EZ, and clock rate adjustments are applied.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct DifficultyAdjust {
+    /// The circle size.
+    pub cs: Option<f32>,
+    /// The approach rate.
+    pub ar: Option<f32>,
+    /// The overall difficulty.
+    pub od: Option<f32>,
+    /// The health drain rate.
+    pub hp: Option<f32>,
+}
+
+impl DifficultyAdjust {
+    /// Whether no value is adjusted.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.cs.is_none() && self.ar.is_none() && self.od.is_none() && self.hp.is_none()
+    }
+
+    pub(crate) fn apply<'b>(
+        &self,
+        builder: &'b mut BeatmapAttributesBuilder,
+    ) -> &'b mut BeatmapAttributesBuilder {
+        if let Some(cs) = self.cs {
+            builder.cs(cs);
+        }
+
+        if let Some(ar) = self.ar {
+            builder.ar(ar);
+        }
+
+        if let Some(od) = self.od {
+            builder.od(od);
+        }
+
+        if let Some(hp) = self.hp {
+            builder.hp(hp);
+        }
+
+        builder
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_acronyms() {
+        assert_eq!(parse("HDDT"), Ok(8 + 64));
+        assert_eq!(parse("+hd,nc"), Ok(8 + 64 + 512));
+        assert_eq!(parse("NM"), Ok(0));
+        assert_eq!(parse("HDX"), Err(Error::UnknownMod("HDX".to_owned())));
+        assert_eq!(parse("HDXY"), Err(Error::UnknownMod("XY".to_owned())));
+    }
+
+    #[test]
+    fn parse_unavailable() {
+        assert_eq!(
+            parse_available("HDV2", BEFORE_SCORE_V2),
+            Err(Error::UnavailableMods(SCORE_V2))
+        );
+    }
+
+    #[test]
+    fn score_multiplier() {
+        let hdhr = ScoreMultipliers::OSU.multiplier(8 + 16);
+        assert!((hdhr - 1.06 * 1.06).abs() < f32::EPSILON);
+
+        assert_eq!(ScoreMultipliers::MANIA.multiplier(64), 1.0);
+    }
+}
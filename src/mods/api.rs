@@ -0,0 +1,165 @@
+use std::collections::BTreeMap;
+
+use serde::{de::IgnoredAny, Deserialize};
+
+use crate::Error;
+
+use super::{parse, DifficultyAdjust};
+
+/// A mod as represented by the osu! API v2, e.g. `{"acronym": "DT", "settings": {"speed_change": 1.3}}`.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+pub struct ApiMod {
+    /// The mod's acronym.
+    pub acronym: String,
+    /// The mod's settings, if any were changed from their default.
+    #[serde(default)]
+    pub settings: ApiModSettings,
+}
+
+/// The settings of an [`ApiMod`] that affect difficulty or performance.
+///
+/// Other settings are ignored.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+pub struct ApiModSettings {
+    /// The clock rate of DT, NC, HT, and DC.
+    pub speed_change: Option<f64>,
+    /// The circle size of DA.
+    pub circle_size: Option<f32>,
+    /// The approach rate of DA.
+    pub approach_rate: Option<f32>,
+    /// The overall difficulty of DA.
+    pub overall_difficulty: Option<f32>,
+    /// The health drain rate of DA.
+    pub drain_rate: Option<f32>,
+    /// All other settings, only their names are kept.
+    #[serde(flatten)]
+    pub other: BTreeMap<String, IgnoredAny>,
+}
+
+/// Settings that do not affect difficulty or performance and thus are not reported as unsupported.
+const NO_EFFECT: &[&str] = &["adjust_pitch", "extended_limits"];
+
+/// The calculator inputs of a list of [`ApiMod`]s.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ApiModsInput {
+    /// The mods value that is accepted by the calculators.
+    pub mods: u32,
+    /// The custom clock rate, if any.
+    pub clock_rate: Option<f64>,
+    /// The values of the Difficulty Adjust mod.
+    pub difficulty_adjust: DifficultyAdjust,
+    /// The settings that the calculators can not honor as pairs of
+    /// mod acronym and setting name, e.g. `("FL", "size_multiplier")`.
+    pub unsupported: Vec<(String, String)>,
+}
+
+impl ApiModsInput {
+    /// Convert API v2 mods into calculator inputs.
+    ///
+    /// Lazer-only mods without a stable equivalent cause an error,
+    /// except for Difficulty Adjust and Classic.
+    pub fn new(api_mods: &[ApiMod]) -> Result<Self, Error> {
+        let mut input = Self::default();
+
+        for api_mod in api_mods {
+            let settings = &api_mod.settings;
+
+            match api_mod.acronym.as_str() {
+                "DA" => {
+                    input.difficulty_adjust = DifficultyAdjust {
+                        cs: settings.circle_size,
+                        ar: settings.approach_rate,
+                        od: settings.overall_difficulty,
+                        hp: settings.drain_rate,
+                    };
+                }
+                "CL" => {}
+                "DC" => input.mods |= parse("HT")?,
+                acronym => input.mods |= parse(acronym)?,
+            }
+
+            if let Some(speed_change) = settings.speed_change {
+                input.clock_rate = Some(speed_change);
+            }
+
+            let unsupported = settings
+                .other
+                .keys()
+                .filter(|setting| !NO_EFFECT.contains(&setting.as_str()))
+                .map(|setting| (api_mod.acronym.clone(), setting.clone()));
+
+            input.unsupported.extend(unsupported);
+        }
+
+        Ok(input)
+    }
+
+    /// Convert mods in the JSON format of the osu! API v2, e.g. as found in
+    /// score exports of osu!lazer, into calculator inputs.
+    ///
+    /// See [`ApiModsInput::new`] for which mods cause an error.
+    #[cfg(feature = "serde_json")]
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        let api_mods: Vec<ApiMod> =
+            serde_json::from_str(json).map_err(|err| Error::InvalidModsJson(err.to_string()))?;
+
+        Self::new(&api_mods)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn speed_change() {
+        let api_mods = [
+            ApiMod {
+                acronym: "HD".to_owned(),
+                settings: ApiModSettings::default(),
+            },
+            ApiMod {
+                acronym: "DT".to_owned(),
+                settings: ApiModSettings {
+                    speed_change: Some(1.3),
+                    ..Default::default()
+                },
+            },
+        ];
+
+        let input = ApiModsInput::new(&api_mods).unwrap();
+
+        assert_eq!(input.mods, 8 + 64);
+        assert_eq!(input.clock_rate, Some(1.3));
+    }
+
+    #[test]
+    fn unsupported_settings() {
+        let settings = |names: &[&str]| ApiModSettings {
+            other: names
+                .iter()
+                .map(|name| (name.to_string(), IgnoredAny))
+                .collect(),
+            ..Default::default()
+        };
+
+        let api_mods = [
+            ApiMod {
+                acronym: "DT".to_owned(),
+                settings: settings(&["adjust_pitch"]),
+            },
+            ApiMod {
+                acronym: "FL".to_owned(),
+                settings: settings(&["size_multiplier"]),
+            },
+        ];
+
+        let input = ApiModsInput::new(&api_mods).unwrap();
+
+        assert_eq!(input.mods, 64 + 1024);
+        assert_eq!(
+            input.unsupported,
+            vec![("FL".to_owned(), "size_multiplier".to_owned())]
+        );
+    }
+}
@@ -0,0 +1,123 @@
+//! Uniform access to the amount of hit objects across versions.
+//!
+//! Generating hit results or accuracies only depends on these counts so
+//! it can be written once against any version's attributes.
+
+use rosu_pp::parse::{HitObject, HitObjectKind};
+
+use crate::osu;
+
+/// The amount of hit objects that attributes were calculated on.
+pub trait ObjectCount {
+    /// The amount of circles, i.e. notes in osu!taiko and osu!mania.
+    fn n_circles(&self) -> usize;
+
+    /// The amount of sliders, i.e. drumrolls in osu!taiko and hold notes in osu!mania.
+    fn n_sliders(&self) -> usize;
+
+    /// The amount of spinners, i.e. swells in osu!taiko.
+    fn n_spinners(&self) -> usize;
+
+    /// The amount of all hit objects.
+    #[inline]
+    fn n_objects(&self) -> usize {
+        self.n_circles() + self.n_sliders() + self.n_spinners()
+    }
+}
+
+/// Count the circles, sliders, and spinners among the hit objects.
+///
+/// Hold notes count as sliders.
+pub(crate) fn count_objects<'h>(
+    hit_objects: impl Iterator<Item = &'h HitObject>,
+) -> (usize, usize, usize) {
+    hit_objects.fold((0, 0, 0), |(circles, sliders, spinners), h| match h.kind {
+        HitObjectKind::Circle => (circles + 1, sliders, spinners),
+        HitObjectKind::Slider { .. } | HitObjectKind::Hold { .. } => {
+            (circles, sliders + 1, spinners)
+        }
+        HitObjectKind::Spinner { .. } => (circles, sliders, spinners + 1),
+    })
+}
+
+macro_rules! impl_object_count {
+    ( $( $( #[$meta:meta] )* $difficulty:ty => $performance:ty, )* ) => {
+        $(
+            $( #[$meta] )*
+            impl ObjectCount for $difficulty {
+                #[inline]
+                fn n_circles(&self) -> usize {
+                    self.n_circles
+                }
+
+                #[inline]
+                fn n_sliders(&self) -> usize {
+                    self.n_sliders
+                }
+
+                #[inline]
+                fn n_spinners(&self) -> usize {
+                    self.n_spinners
+                }
+            }
+
+            $( #[$meta] )*
+            impl ObjectCount for $performance {
+                #[inline]
+                fn n_circles(&self) -> usize {
+                    self.difficulty.n_circles
+                }
+
+                #[inline]
+                fn n_sliders(&self) -> usize {
+                    self.difficulty.n_sliders
+                }
+
+                #[inline]
+                fn n_spinners(&self) -> usize {
+                    self.difficulty.n_spinners
+                }
+            }
+        )*
+    };
+}
+
+impl_object_count! {
+    osu::DifficultyAttributes => osu::PerformanceAttributes,
+    #[cfg(feature = "taiko-ppv1")]
+    crate::taiko_ppv1::TaikoDifficultyAttributes => crate::taiko_ppv1::TaikoPerformanceAttributes,
+    #[cfg(feature = "taiko-2020")]
+    crate::taiko_2020::TaikoDifficultyAttributes => crate::taiko_2020::TaikoPerformanceAttributes,
+    #[cfg(feature = "taiko-2022")]
+    crate::taiko_2022::TaikoDifficultyAttributes => crate::taiko_2022::TaikoPerformanceAttributes,
+    #[cfg(feature = "mania-ppv1")]
+    crate::mania_ppv1::ManiaDifficultyAttributes => crate::mania_ppv1::ManiaPerformanceAttributes,
+    #[cfg(feature = "mania-2018")]
+    crate::mania_2018::ManiaDifficultyAttributes => crate::mania_2018::ManiaPerformanceAttributes,
+    #[cfg(feature = "mania-2022")]
+    crate::mania_2022::ManiaDifficultyAttributes => crate::mania_2022::ManiaPerformanceAttributes,
+}
+
+#[cfg(all(test, feature = "mania-2018"))]
+mod test {
+    use crate::mania_2018;
+
+    use super::*;
+
+    #[test]
+    fn objects_of_performance_attributes() {
+        let difficulty = mania_2018::ManiaDifficultyAttributes {
+            n_circles: 500,
+            n_sliders: 120,
+            ..Default::default()
+        };
+
+        let performance = mania_2018::ManiaPerformanceAttributes {
+            difficulty,
+            ..Default::default()
+        };
+
+        assert_eq!(difficulty.n_objects(), 620);
+        assert_eq!(performance.n_objects(), 620);
+    }
+}
@@ -0,0 +1,178 @@
+//! The attribute types that all osu!standard versions share.
+//!
+//! Each version module re-exports them as `OsuDifficultyAttributes`,
+//! `OsuPerformanceAttributes`, and `OsuStrains`. Values that a version does not
+//! calculate keep their default, e.g. the flashlight rating before 2021 November.
+
+/// The result of a difficulty calculation on an osu!standard map.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct DifficultyAttributes {
+    /// The aim portion of the total strain.
+    pub aim_strain: f64,
+    /// The speed portion of the total strain.
+    pub speed_strain: f64,
+    /// The amount of objects weighted by how difficult they are to tap.
+    ///
+    /// Zero before 2022 September.
+    pub speed_note_count: f64,
+    /// The flashlight portion of the total strain.
+    ///
+    /// Zero before 2021 November.
+    pub flashlight_rating: f64,
+    /// The aim strain without considering sliders divided by the aim strain with sliders.
+    ///
+    /// Always present since 2021 November. 2021 July did not have this attribute yet,
+    /// it is only calculated when requested through
+    /// [`OsuStars::slider_factor`](crate::osu_2021_july::OsuStars::slider_factor)
+    /// to compare slider-heavy maps with later versions and does not affect the stars or pp.
+    pub slider_factor: Option<f64>,
+    /// The approach rate after applying mods and the clock rate.
+    pub ar: f64,
+    /// The overall difficulty after applying mods and the clock rate that the pp formula uses.
+    pub od: f64,
+    /// The approach rate of the map before applying mods and the clock rate.
+    pub base_ar: f64,
+    /// The overall difficulty of the map before applying mods and the clock rate.
+    pub base_od: f64,
+    /// The health drain rate.
+    pub hp: f64,
+    /// The amount of circles.
+    pub n_circles: usize,
+    /// The amount of sliders.
+    pub n_sliders: usize,
+    /// The amount of spinners.
+    pub n_spinners: usize,
+    /// The final star rating
+    pub stars: f64,
+    /// The maximum combo.
+    pub max_combo: usize,
+}
+
+impl DifficultyAttributes {
+    /// Return the maximum combo.
+    #[inline]
+    pub fn max_combo(&self) -> usize {
+        self.max_combo
+    }
+}
+
+/// The result of a performance calculation on an osu!standard map.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PerformanceAttributes {
+    /// The difficulty attributes that were used for the performance calculation
+    pub difficulty: DifficultyAttributes,
+    /// The final performance points.
+    pub pp: f64,
+    /// The performance points of an SS with the same mods and settings.
+    pub max_pp: f64,
+    /// The accuracy portion of the final pp.
+    pub pp_acc: f64,
+    /// The aim portion of the final pp.
+    pub pp_aim: f64,
+    /// The flashlight portion of the final pp.
+    pub pp_flashlight: f64,
+    /// The speed portion of the final pp.
+    pub pp_speed: f64,
+    /// The multiplier that was applied due to the NoFail mod.
+    pub nf_multiplier: f64,
+    /// The values that versions up to 2021 July based their pp on, `None` for later versions.
+    pub legacy: Option<LegacyPerformanceAttributes>,
+}
+
+impl PerformanceAttributes {
+    /// Return the star value.
+    #[inline]
+    pub fn stars(&self) -> f64 {
+        self.difficulty.stars
+    }
+
+    /// Return the performance point value.
+    #[inline]
+    pub fn pp(&self) -> f64 {
+        self.pp
+    }
+
+    /// Return the maximum combo of the map.
+    #[inline]
+    pub fn max_combo(&self) -> usize {
+        self.difficulty.max_combo
+    }
+}
+
+impl From<PerformanceAttributes> for DifficultyAttributes {
+    fn from(attributes: PerformanceAttributes) -> Self {
+        attributes.difficulty
+    }
+}
+
+/// The section strain peaks of each skill, e.g. to draw a difficulty graph.
+///
+/// Skills that a version does not have are empty so graphs can be drawn the
+/// same way for every version.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Strains {
+    /// The length of a section in milliseconds of the unmodified map.
+    pub section_len: f64,
+    /// The aim strain peak of each section.
+    pub aim: Vec<f64>,
+    /// The aim strain peak of each section without considering sliders.
+    ///
+    /// Empty before 2021 November.
+    pub aim_no_sliders: Vec<f64>,
+    /// The speed strain peak of each section, empty for Relax since 2021 November.
+    pub speed: Vec<f64>,
+    /// The flashlight strain peak of each section.
+    ///
+    /// Empty without Flashlight and before 2021 November.
+    pub flashlight: Vec<f64>,
+}
+
+/// The performance values of versions up to 2021 July.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct LegacyPerformanceAttributes {
+    /// The aim multiplier of FL depending on the length of the map.
+    pub fl_bonus: f64,
+    /// The amount of spinners among the judged objects.
+    pub n_spinners: usize,
+    /// The accuracy that the accuracy pp are based on.
+    ///
+    /// Sliders and spinners are assumed to be 300s and deducted from the 300s before the
+    /// accuracy on circles is evaluated so spinner-heavy maps are very punishing. Spinners
+    /// are not rewarded in any other way. With ScoreV2 this is the overall accuracy instead.
+    pub acc_on_circles: f64,
+    /// The terms of the flashlight bonus, only present for 2021 July.
+    pub flashlight: Option<FlashlightBonus>,
+}
+
+/// The terms from which the flashlight bonus of a play was built.
+///
+/// 2021 July has no flashlight skill. Instead, FL multiplies the aim
+/// value by a length based bonus if that is larger than the AR bonus.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct FlashlightBonus {
+    /// The aim multiplier of FL depending on the length of the map, `1.0` without FL.
+    pub length_bonus: f64,
+    /// The aim multiplier of AR that competes with the FL bonus.
+    pub ar_bonus: f64,
+    /// The accuracy multiplier of FL, `1.0` without FL.
+    pub acc_bonus: f64,
+}
+
+impl FlashlightBonus {
+    /// The aim multiplier that was applied, i.e. the larger of the FL and AR bonus.
+    #[inline]
+    pub fn aim_multiplier(&self) -> f64 {
+        self.length_bonus.max(self.ar_bonus)
+    }
+
+    /// Whether the FL bonus exceeded the AR bonus and thus affected the aim value.
+    #[inline]
+    pub fn is_fl_applied(&self) -> bool {
+        self.length_bonus > self.ar_bonus
+    }
+}
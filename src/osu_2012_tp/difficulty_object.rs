@@ -0,0 +1,44 @@
+use super::{
+    osu_object::{LazySlider, OsuObjectKind},
+    OsuObject,
+};
+
+pub struct DifficultyObject<'h> {
+    pub base: &'h OsuObject,
+    pub prev_slider: LazySlider,
+    pub dist: f32,
+    pub elapsed: f32,
+    pub delta: f32,
+}
+
+impl<'h> DifficultyObject<'h> {
+    pub fn new(
+        base: &'h OsuObject,
+        prev: &OsuObject,
+        clock_rate: f32,
+        scaling_factor: f32,
+    ) -> Self {
+        let elapsed = (base.time - prev.time) / clock_rate;
+
+        // Filter out objects that are too close to be played anyway
+        let delta = elapsed.max(50.0);
+
+        let prev_slider = match prev.kind {
+            OsuObjectKind::Slider(slider) => slider,
+            OsuObjectKind::Circle | OsuObjectKind::Spinner => LazySlider {
+                span_count: 1,
+                ..Default::default()
+            },
+        };
+
+        let dist = (base.pos - prev.end_pos).length() * scaling_factor;
+
+        Self {
+            base,
+            prev_slider,
+            dist,
+            elapsed,
+            delta,
+        }
+    }
+}
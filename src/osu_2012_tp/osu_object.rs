@@ -0,0 +1,214 @@
+use crate::util::curve::{Curve, CurveBuffers};
+
+use super::stars::OsuDifficultyAttributes;
+
+use rosu_pp::{
+    parse::{HitObject, HitObjectKind, Pos2},
+    Beatmap,
+};
+
+const BASE_SCORING_DISTANCE: f64 = 100.0;
+
+// The smaller the value, the more accurate sliders are approximated
+const LAZY_SLIDER_STEP_LEN: f64 = 10.0;
+
+pub struct OsuObject {
+    pub time: f32,
+    pub pos: Pos2,
+    pub end_pos: Pos2,
+    pub kind: OsuObjectKind,
+}
+
+#[derive(Copy, Clone)]
+pub enum OsuObjectKind {
+    Circle,
+    Slider(LazySlider),
+    Spinner,
+}
+
+/// The lazy cursor movement on a slider.
+#[derive(Copy, Clone, Default)]
+pub struct LazySlider {
+    /// The lazy travel distance of the first span.
+    pub first_len: f32,
+    /// The lazy travel distance of each subsequent span.
+    pub subsequent_len: f32,
+    pub span_count: usize,
+}
+
+impl OsuObject {
+    pub(crate) fn new(
+        h: &HitObject,
+        map: &Beatmap,
+        radius: f32,
+        scaling_factor: f32,
+        attrs: &mut OsuDifficultyAttributes,
+        curve_bufs: &mut CurveBuffers,
+    ) -> Self {
+        match &h.kind {
+            HitObjectKind::Circle => {
+                attrs.n_circles += 1;
+                attrs.max_combo += 1;
+
+                Self {
+                    time: h.start_time as f32,
+                    pos: h.pos,
+                    end_pos: h.pos,
+                    kind: OsuObjectKind::Circle,
+                }
+            }
+            HitObjectKind::Slider {
+                pixel_len,
+                repeats,
+                control_points,
+                ..
+            } => {
+                attrs.n_sliders += 1;
+
+                let curve = Curve::new(control_points, *pixel_len, curve_bufs);
+                attrs.max_combo += slider_combo(h, map, *repeats, curve.dist());
+
+                let timing_point = map.timing_point_at(h.start_time);
+                let difficulty_point = map.difficulty_point_at(h.start_time).unwrap_or_default();
+
+                let vel = BASE_SCORING_DISTANCE * map.slider_mult * difficulty_point.slider_vel
+                    / timing_point.beat_len;
+
+                let span_count = *repeats + 1;
+                let span_duration = curve.dist() / vel;
+
+                // Slider lengths were whole milliseconds
+                let segment_len = span_duration.floor();
+
+                let follow_circle_radius = radius * 3.0;
+                let mut cursor_pos = h.pos;
+
+                // Moves the cursor just as far as needed to stay in the follow circle
+                // for each step of the span and returns the distance it moved
+                let mut lazy_span = |span_start: f64| {
+                    let mut lazy_len = 0.0;
+                    let mut time = span_start + LAZY_SLIDER_STEP_LEN;
+
+                    while time < span_start + segment_len {
+                        let mut progress = (time - h.start_time) / span_duration;
+
+                        if progress % 2.0 >= 1.0 {
+                            progress = 1.0 - progress % 1.0;
+                        } else {
+                            progress %= 1.0;
+                        }
+
+                        let diff = h.pos + curve.position_at(progress) - cursor_pos;
+                        let mut dist = diff.length();
+
+                        if dist > follow_circle_radius {
+                            dist -= follow_circle_radius;
+                            cursor_pos += diff.normalize() * dist;
+                            lazy_len += dist;
+                        }
+
+                        time += LAZY_SLIDER_STEP_LEN;
+                    }
+
+                    lazy_len * scaling_factor
+                };
+
+                let first_len = lazy_span(h.start_time);
+
+                // Subsequent spans only differ in their start position so
+                // the second span is representative for all of them
+                let subsequent_len = if span_count > 1 {
+                    lazy_span(h.start_time + segment_len)
+                } else {
+                    0.0
+                };
+
+                Self {
+                    time: h.start_time as f32,
+                    pos: h.pos,
+                    end_pos: cursor_pos,
+                    kind: OsuObjectKind::Slider(LazySlider {
+                        first_len,
+                        subsequent_len,
+                        span_count,
+                    }),
+                }
+            }
+            // Holds only appear in osu!mania maps which are never calculated
+            HitObjectKind::Spinner { .. } | HitObjectKind::Hold { .. } => {
+                attrs.n_spinners += 1;
+                attrs.max_combo += 1;
+
+                Self {
+                    time: h.start_time as f32,
+                    pos: h.pos,
+                    end_pos: h.pos,
+                    kind: OsuObjectKind::Spinner,
+                }
+            }
+        }
+    }
+}
+
+/// The combo of a hit object, i.e. `1` for circles and spinners and the head, ticks,
+/// repeats, and tail for sliders.
+///
+/// Counts the same nested objects as [`OsuObject::new`] without the lazy movement.
+pub(crate) fn object_combo(h: &HitObject, map: &Beatmap, curve_bufs: &mut CurveBuffers) -> usize {
+    match &h.kind {
+        HitObjectKind::Slider {
+            pixel_len,
+            repeats,
+            control_points,
+            ..
+        } => {
+            let curve = Curve::new(control_points, *pixel_len, curve_bufs);
+
+            slider_combo(h, map, *repeats, curve.dist())
+        }
+        _ => 1,
+    }
+}
+
+/// The combo of a slider including its head, ticks, repeats, and tail.
+fn slider_combo(h: &HitObject, map: &Beatmap, repeats: usize, curve_len: f64) -> usize {
+    let timing_point = map.timing_point_at(h.start_time);
+    let difficulty_point = map.difficulty_point_at(h.start_time).unwrap_or_default();
+
+    let scoring_dist = BASE_SCORING_DISTANCE * map.slider_mult * difficulty_point.slider_vel;
+    let vel = scoring_dist / timing_point.beat_len;
+
+    let tick_dist_mult = if map.version < 8 {
+        difficulty_point.slider_vel.recip()
+    } else {
+        1.0
+    };
+
+    let tick_dist = if difficulty_point.generate_ticks {
+        scoring_dist / map.tick_rate * tick_dist_mult
+    } else {
+        f64::INFINITY
+    };
+
+    let len = curve_len.min(100_000.0);
+    let tick_dist = tick_dist.clamp(0.0, len);
+
+    // Head and tail
+    let mut combo = 2;
+
+    // Repeat points are only added alongside ticks
+    if tick_dist != 0.0 {
+        let min_dist_from_end = vel * 10.0;
+        let mut curr_dist = tick_dist;
+        let mut n_ticks = 0;
+
+        while curr_dist < len - min_dist_from_end {
+            n_ticks += 1;
+            curr_dist += tick_dist;
+        }
+
+        combo += n_ticks + repeats * (1 + n_ticks);
+    }
+
+    combo
+}
@@ -0,0 +1,870 @@
+use super::{
+    stars::{
+        calculate_stars, calculate_stars_with_strains, OsuDifficultyAttributes,
+        OsuPerformanceAttributes, OsuStrains,
+    },
+    Beatmap, Mods, MODE,
+};
+
+use crate::{
+    mods::{DifficultyAdjust, FlLengthBonus},
+    osu::LegacyPerformanceAttributes,
+    section::SectionLen,
+    unranked::{AutopilotMode, PpComponents, RelaxMode, UnrankedConfig},
+    util::{cache::DifficultyCache, hitresults, mode::check_mode},
+    Error, IfFcAttributes, ScoreState,
+};
+
+/// Calculator for pp on osu!standard maps.
+///
+/// This is osu!tp's score rating by Tom94 which ppv2 adopted when it launched, so the
+/// formula matches the one of `osu_2014_february` but rates the aim and speed strains
+/// of osu!tp's difficulty calculation.
+///
+/// # Example
+///
+/// ```
+/// # use rosu_pp::{OsuPP, Beatmap};
+/// # /*
+/// let map: Beatmap = ...
+/// # */
+/// # let map = Beatmap::default();
+/// let attrs = OsuPP::new(&map)
+///     .mods(8 + 64) // HDDT
+///     .combo(1234)
+///     .misses(1)
+///     .accuracy(98.5) // should be set last
+///     .calculate();
+///
+/// println!("PP: {} | Stars: {}", attrs.pp(), attrs.stars());
+///
+/// let next_result = OsuPP::new(&map)
+///     .attributes(attrs) // reusing previous results for performance
+///     .mods(8 + 64)      // has to be the same to reuse attributes
+///     .accuracy(99.5)
+///     .calculate();
+///
+/// println!("PP: {} | Stars: {}", next_result.pp(), next_result.stars());
+/// ```
+#[derive(Clone, Debug)]
+pub struct OsuPP<'m> {
+    map: &'m Beatmap,
+    attributes: Option<OsuDifficultyAttributes>,
+    cache: DifficultyCache<OsuDifficultyAttributes>,
+    mods: u32,
+    combo: Option<usize>,
+    acc: Option<f32>,
+
+    n300: Option<usize>,
+    n100: Option<usize>,
+    n50: Option<usize>,
+    n_misses: usize,
+    estimate_hitresults: bool,
+    passed_objects: Option<usize>,
+    clock_rate: Option<f64>,
+    difficulty_adjust: DifficultyAdjust,
+    section_len: Option<SectionLen>,
+    fl_length_bonus: FlLengthBonus,
+
+    unranked: UnrankedConfig,
+}
+
+impl<'m> OsuPP<'m> {
+    #[inline]
+    pub fn new(map: &'m Beatmap) -> Self {
+        Self {
+            map,
+            attributes: None,
+            cache: DifficultyCache::default(),
+            mods: 0,
+            combo: None,
+            acc: None,
+
+            n300: None,
+            n100: None,
+            n50: None,
+            n_misses: 0,
+            estimate_hitresults: false,
+            passed_objects: None,
+            clock_rate: None,
+            difficulty_adjust: DifficultyAdjust::default(),
+            section_len: None,
+            fl_length_bonus: FlLengthBonus::Flat,
+
+            unranked: UnrankedConfig::default(),
+        }
+    }
+
+    /// [`OsuAttributeProvider`] is implemented by [`DifficultyAttributes`](crate::osu::DifficultyAttributes)
+    /// and by [`PpResult`](crate::PpResult) meaning you can give the
+    /// result of a star calculation or a pp calculation.
+    /// If you already calculated the attributes for the current map-mod combination,
+    /// be sure to put them in here so that they don't have to be recalculated.
+    #[inline]
+    pub fn attributes(mut self, attributes: impl OsuAttributeProvider) -> Self {
+        if let Some(attributes) = attributes.attributes() {
+            self.attributes.replace(attributes);
+        }
+
+        self
+    }
+
+    /// Specify mods through their bit values.
+    ///
+    /// See [https://github.com/ppy/osu-api/wiki#mods](https://github.com/ppy/osu-api/wiki#mods)
+    #[inline]
+    pub fn mods(mut self, mods: u32) -> Self {
+        self.mods = mods;
+        self.cache.reset();
+
+        self
+    }
+
+    /// Specify mods through their acronyms, e.g. `"HDDT"`.
+    ///
+    /// Returns an error for unknown acronyms and for mods
+    /// that did not exist yet at the time of this version.
+    #[inline]
+    pub fn mods_str(self, acronyms: &str) -> Result<Self, Error> {
+        super::parse_mods(acronyms).map(|mods| self.mods(mods))
+    }
+
+    /// Specify the max combo of the play.
+    #[inline]
+    pub fn combo(mut self, combo: usize) -> Self {
+        self.combo.replace(combo);
+
+        self
+    }
+
+    /// Specify the amount of 300s of a play.
+    #[inline]
+    pub fn n300(mut self, n300: usize) -> Self {
+        self.n300.replace(n300);
+
+        self
+    }
+
+    /// Specify the amount of 100s of a play.
+    #[inline]
+    pub fn n100(mut self, n100: usize) -> Self {
+        self.n100.replace(n100);
+
+        self
+    }
+
+    /// Specify the amount of 50s of a play.
+    #[inline]
+    pub fn n50(mut self, n50: usize) -> Self {
+        self.n50.replace(n50);
+
+        self
+    }
+
+    /// Specify the amount of misses of a play.
+    #[inline]
+    pub fn misses(mut self, n_misses: usize) -> Self {
+        self.n_misses = n_misses;
+
+        self
+    }
+
+    /// Amount of passed objects for partial plays, e.g. a fail.
+    #[inline]
+    pub fn passed_objects(mut self, passed_objects: usize) -> Self {
+        self.passed_objects.replace(passed_objects);
+        self.cache.reset();
+
+        self
+    }
+
+    /// Specify a custom clock rate, e.g. `1.4` for a lazer DT score that was set at 1.4x.
+    ///
+    /// The clock rate replaces the one of DT or HT.
+    #[inline]
+    pub fn clock_rate(mut self, clock_rate: f64) -> Self {
+        self.clock_rate.replace(clock_rate);
+        self.cache.reset();
+
+        self
+    }
+
+    /// Specify the values of lazer's Difficulty Adjust mod.
+    ///
+    /// The values replace the map's base values before HR, EZ, and the clock rate are applied.
+    #[inline]
+    pub fn difficulty_adjust(mut self, difficulty_adjust: DifficultyAdjust) -> Self {
+        self.difficulty_adjust = difficulty_adjust;
+        self.cache.reset();
+
+        self
+    }
+
+    /// Override the map's approach rate, see [`difficulty_adjust`](Self::difficulty_adjust).
+    #[inline]
+    pub fn ar(mut self, ar: f32) -> Self {
+        self.difficulty_adjust.ar = Some(ar);
+        self.cache.reset();
+
+        self
+    }
+
+    /// Override the map's circle size, see [`difficulty_adjust`](Self::difficulty_adjust).
+    #[inline]
+    pub fn cs(mut self, cs: f32) -> Self {
+        self.difficulty_adjust.cs = Some(cs);
+        self.cache.reset();
+
+        self
+    }
+
+    /// Override the map's overall difficulty, see [`difficulty_adjust`](Self::difficulty_adjust).
+    #[inline]
+    pub fn od(mut self, od: f32) -> Self {
+        self.difficulty_adjust.od = Some(od);
+        self.cache.reset();
+
+        self
+    }
+
+    /// Override the map's drain rate, see [`difficulty_adjust`](Self::difficulty_adjust).
+    #[inline]
+    pub fn hp(mut self, hp: f32) -> Self {
+        self.difficulty_adjust.hp = Some(hp);
+        self.cache.reset();
+
+        self
+    }
+
+    /// Override the length of the sections in which strain peaks are collected.
+    ///
+    /// Only meant for research, see [`SectionLen`].
+    #[cfg(feature = "experimental")]
+    #[inline]
+    pub fn section_len(mut self, section_len: SectionLen) -> Self {
+        self.section_len.replace(section_len);
+        self.cache.reset();
+
+        self
+    }
+
+    /// Specify how the FL aim bonus scales with the length of the play.
+    ///
+    /// Defaults to the way this version did it, i.e. [`FlLengthBonus::Flat`].
+    #[inline]
+    pub fn fl_length_bonus(mut self, fl_length_bonus: FlLengthBonus) -> Self {
+        self.fl_length_bonus = fl_length_bonus;
+
+        self
+    }
+
+    /// Specify how the Relax mod should be handled.
+    ///
+    /// By default, Relax is treated like nomod by [`calculate`](Self::calculate)
+    /// and rejected by [`try_calculate`](Self::try_calculate).
+    #[inline]
+    pub fn relax(mut self, relax: RelaxMode) -> Self {
+        self.unranked.relax.replace(relax);
+
+        self
+    }
+
+    /// Specify whether [`try_calculate`](Self::try_calculate) should accept mods
+    /// that bancho never awarded pp for, i.e. AT, CN, TP, and unconfigured RX.
+    ///
+    /// Useful for private servers, defaults to `false`.
+    #[inline]
+    pub fn allow_unranked(mut self, allow_unranked: bool) -> Self {
+        self.unranked.allow_unranked = allow_unranked;
+
+        self
+    }
+
+    /// Specify how the Autopilot mod should be handled.
+    ///
+    /// By default, Autopilot plays are rejected by [`try_calculate`](Self::try_calculate)
+    /// and are worth 0 pp with [`calculate`](Self::calculate).
+    #[inline]
+    pub fn autopilot(mut self, autopilot: AutopilotMode) -> Self {
+        self.unranked.autopilot = autopilot;
+
+        self
+    }
+
+    /// Provide parameters through a [`ScoreState`].
+    #[inline]
+    pub fn state(mut self, state: impl Into<ScoreState>) -> Self {
+        let ScoreState {
+            max_combo,
+            n300,
+            n100,
+            n50,
+            n_misses,
+            ..
+        } = state.into();
+
+        self.combo = Some(max_combo);
+        self.n300 = Some(n300);
+        self.n100 = Some(n100);
+        self.n50 = Some(n50);
+        self.n_misses = n_misses;
+
+        self
+    }
+
+    /// Generate the hit results with respect to the given accuracy between `0` and `100`.
+    ///
+    /// Be sure to set `misses` beforehand!
+    /// In case of a partial play, be also sure to set `passed_objects` beforehand!
+    pub fn accuracy(mut self, acc: f32) -> Self {
+        let n_objects = self.passed_objects.unwrap_or(self.map.hit_objects.len());
+
+        let acc = acc / 100.0;
+
+        if self.n100.or(self.n50).is_some() {
+            let mut n100 = self.n100.unwrap_or(0);
+            let mut n50 = self.n50.unwrap_or(0);
+
+            let placed_points = 2 * n100 + n50 + self.n_misses;
+            let missing_objects = n_objects
+                .saturating_sub(n100)
+                .saturating_sub(n50)
+                .saturating_sub(self.n_misses);
+            let missing_points =
+                ((6.0 * acc * n_objects as f32).round() as usize).saturating_sub(placed_points);
+
+            let mut n300 = missing_objects.min(missing_points / 6);
+            n50 += missing_objects - n300;
+
+            if let Some(orig_n50) = self.n50.filter(|_| self.n100.is_none()) {
+                // Only n50s were changed, try to load some off again onto n100s
+                let difference = n50 - orig_n50;
+                let n = n300.min(difference / 4);
+
+                n300 -= n;
+                n100 += 5 * n;
+                n50 -= 4 * n;
+            }
+
+            self.n300.replace(n300);
+            self.n100.replace(n100);
+            self.n50.replace(n50);
+        } else {
+            let misses = self.n_misses.min(n_objects);
+            let remaining = n_objects - misses;
+
+            // Between all 50s and all 300s for the objects that were not missed
+            let target_total =
+                ((acc * n_objects as f32 * 6.0).round() as usize).clamp(remaining, 6 * remaining);
+            let delta = target_total - remaining;
+
+            let mut n300 = delta / 5;
+            let mut n100 = (delta % 5).min(remaining.saturating_sub(n300));
+            let mut n50 = remaining.saturating_sub(n300).saturating_sub(n100);
+
+            // Sacrifice n300s to transform n50s into n100s
+            let n = n300.min(n50 / 4);
+            n300 -= n;
+            n100 += 5 * n;
+            n50 -= 4 * n;
+
+            self.n300.replace(n300);
+            self.n100.replace(n100);
+            self.n50.replace(n50);
+        }
+
+        let acc = (6 * self.n300.unwrap() + 2 * self.n100.unwrap() + self.n50.unwrap()) as f32
+            / (6 * n_objects) as f32;
+
+        self.acc.replace(acc);
+
+        self
+    }
+
+    /// Estimate the hitresults from the [`combo`](Self::combo) and the [`misses`](Self::misses)
+    /// alone, e.g. for scores of old private servers that lack their 100s and 50s.
+    ///
+    /// The map is assumed to break as rarely as the combo allows. Each break that the
+    /// misses do not account for is counted as a 100, e.g. a dropped slider end, and
+    /// all other hits are 300s. Previously specified hitresults and accuracy are ignored.
+    #[inline]
+    pub fn estimate_hitresults(mut self) -> Self {
+        self.estimate_hitresults = true;
+
+        self
+    }
+
+    fn assert_hitresults(&mut self) {
+        if self.estimate_hitresults {
+            let n_objects = self.passed_objects.unwrap_or(self.map.hit_objects.len());
+            let max_combo = self.attributes.as_ref().unwrap().max_combo;
+            let (n300, n100, n50) =
+                hitresults::estimate_osu(n_objects, max_combo, self.combo, self.n_misses);
+
+            self.n300.replace(n300);
+            self.n100.replace(n100);
+            self.n50.replace(n50);
+            self.acc.take();
+        }
+
+        if self.acc.is_none() {
+            let n_objects = self.passed_objects.unwrap_or(self.map.hit_objects.len());
+
+            let remaining = n_objects
+                .saturating_sub(self.n300.unwrap_or(0))
+                .saturating_sub(self.n100.unwrap_or(0))
+                .saturating_sub(self.n50.unwrap_or(0))
+                .saturating_sub(self.n_misses);
+
+            if remaining > 0 {
+                if self.n300.is_none() {
+                    self.n300.replace(remaining);
+                    self.n100.get_or_insert(0);
+                    self.n50.get_or_insert(0);
+                } else if self.n100.is_none() {
+                    self.n100.replace(remaining);
+                    self.n50.get_or_insert(0);
+                } else if self.n50.is_none() {
+                    self.n50.replace(remaining);
+                } else {
+                    *self.n300.as_mut().unwrap() += remaining;
+                }
+            } else {
+                self.n300.get_or_insert(0);
+                self.n100.get_or_insert(0);
+                self.n50.get_or_insert(0);
+            }
+
+            let numerator = self.n50.unwrap() + self.n100.unwrap() * 2 + self.n300.unwrap() * 6;
+            self.acc.replace(numerator as f32 / n_objects as f32 / 6.0);
+        }
+    }
+
+    /// Same as [`calculate`](Self::calculate) but returns an error if the map can
+    /// not be calculated by this version or if the mods contain unranked mods that
+    /// are not configured to be handled.
+    pub fn try_calculate(self) -> Result<OsuPerformanceAttributes, Error> {
+        check_mode(self.map.mode, MODE)?;
+        self.unranked.check(self.mods)?;
+
+        Ok(self.calculate())
+    }
+
+    /// Calculate the performance of the score and of its full combo.
+    ///
+    /// For the full combo, the misses are counted as 300s and the combo is the map's
+    /// max combo. Hit results that were generated through [`accuracy`](Self::accuracy)
+    /// are generated again for the same accuracy without misses instead.
+    pub fn calculate_if_fc(self) -> IfFcAttributes<OsuPerformanceAttributes> {
+        let fc = self.if_fc();
+
+        IfFcAttributes {
+            score: self.calculate(),
+            fc: fc.calculate(),
+        }
+    }
+
+    /// Same as [`calculate_if_fc`](Self::calculate_if_fc) but returns an error if the map can
+    /// not be calculated by this version or if the mods contain unranked mods that
+    /// are not configured to be handled.
+    pub fn try_calculate_if_fc(self) -> Result<IfFcAttributes<OsuPerformanceAttributes>, Error> {
+        check_mode(self.map.mode, MODE)?;
+        self.unranked.check(self.mods)?;
+
+        Ok(self.calculate_if_fc())
+    }
+
+    fn if_fc(&self) -> Self {
+        let mut fc = self.clone();
+        fc.combo = None;
+        fc.n_misses = 0;
+
+        if let Some(acc) = self.acc {
+            fc.n300 = None;
+            fc.n100 = None;
+            fc.n50 = None;
+
+            return fc.accuracy(100.0 * acc);
+        }
+
+        if let Some(ref mut n300) = fc.n300 {
+            *n300 += self.n_misses;
+        }
+
+        fc
+    }
+
+    /// Calculate the performance attributes together with the strain peaks of each section,
+    /// e.g. to draw a difficulty graph next to the pp.
+    ///
+    /// Difficulty attributes passed via [`attributes`](Self::attributes) are ignored since
+    /// the strains are only available while calculating the difficulty.
+    pub fn calculate_with_strains(mut self) -> (OsuPerformanceAttributes, OsuStrains) {
+        let clock_rate = self.clock_rate.unwrap_or_else(|| self.mods.clock_rate());
+
+        let (attributes, strains) = calculate_stars_with_strains(
+            self.map,
+            self.mods,
+            self.passed_objects,
+            clock_rate,
+            &self.difficulty_adjust,
+            self.section_len,
+        );
+
+        self.cache.insert(attributes.clone());
+        self.attributes.replace(attributes);
+
+        (self.calculate(), strains)
+    }
+
+    /// Returns an object which contains the pp and [`DifficultyAttributes`](crate::osu::DifficultyAttributes)
+    /// containing stars and other attributes.
+    ///
+    /// Maps of other modes can not be calculated and result in default attributes,
+    /// [`try_calculate`](Self::try_calculate) returns an error for them instead.
+    pub fn calculate(mut self) -> OsuPerformanceAttributes {
+        if check_mode(self.map.mode, MODE).is_err() {
+            return OsuPerformanceAttributes::default();
+        }
+
+        if self.attributes.is_none() {
+            let attributes = self.cache.get_or_insert_with(|| {
+                let clock_rate = self.clock_rate.unwrap_or_else(|| self.mods.clock_rate());
+
+                calculate_stars(
+                    self.map,
+                    self.mods,
+                    self.passed_objects,
+                    clock_rate,
+                    &self.difficulty_adjust,
+                    self.section_len,
+                )
+            });
+
+            self.attributes.replace(attributes);
+        }
+
+        let max_pp = self.ss().calculate_performance().pp;
+        let mut performance = self.calculate_performance();
+        performance.max_pp = max_pp;
+
+        performance
+    }
+
+    /// The same calculator for an SS, i.e. full combo without 100s, 50s, or misses.
+    fn ss(&self) -> Self {
+        Self {
+            combo: None,
+            acc: None,
+            n300: None,
+            n100: None,
+            n50: None,
+            n_misses: 0,
+            estimate_hitresults: false,
+            ..self.clone()
+        }
+    }
+
+    fn calculate_performance(mut self) -> OsuPerformanceAttributes {
+        // Make sure the hitresults and accuracy are set
+        self.assert_hitresults();
+
+        let total_hits = self.total_hits() as f32;
+        let mut multiplier = 1.1;
+
+        // NF penalty
+        let nf_multiplier = if self.mods.nf() { 0.9 } else { 1.0 };
+        multiplier *= nf_multiplier;
+
+        // SO penalty
+        if self.mods.so() {
+            multiplier *= 0.95;
+        }
+
+        let fl_bonus = self.fl_bonus(total_hits);
+        let mut aim_value = self.compute_aim_value(total_hits);
+        let mut speed_value = self.compute_speed_value();
+        let (mut acc_value, acc_on_circles) = self.compute_accuracy_value(total_hits);
+        let n_spinners = self.attributes.as_ref().unwrap().n_spinners;
+
+        // Unranked mods
+        if self.unranked.applies_to(self.mods) {
+            let mut components = PpComponents {
+                aim: aim_value as f64,
+                speed: speed_value as f64,
+                acc: acc_value as f64,
+                flashlight: 0.0,
+            };
+
+            self.unranked.adjust(self.mods, &mut components);
+
+            aim_value = components.aim as f32;
+            speed_value = components.speed as f32;
+            acc_value = components.acc as f32;
+        }
+
+        let pp = (aim_value.powf(1.1) + speed_value.powf(1.1) + acc_value.powf(1.1))
+            .powf(1.0 / 1.1)
+            * multiplier;
+
+        OsuPerformanceAttributes {
+            difficulty: self.attributes.unwrap(),
+            pp_acc: acc_value as f64,
+            pp_aim: aim_value as f64,
+            pp_flashlight: 0.0,
+            pp_speed: speed_value as f64,
+            pp: pp as f64,
+            max_pp: 0.0,
+            nf_multiplier: nf_multiplier as f64,
+            legacy: Some(LegacyPerformanceAttributes {
+                fl_bonus: fl_bonus as f64,
+                n_spinners,
+                acc_on_circles: acc_on_circles as f64,
+                flashlight: None,
+            }),
+        }
+    }
+
+    fn compute_aim_value(&self, total_hits: f32) -> f32 {
+        let attributes = self.attributes.as_ref().unwrap();
+
+        let mut aim_value =
+            (5.0 * (attributes.aim_strain as f32 / 0.0445).max(1.0) - 4.0).powi(3) / 100_000.0;
+
+        // Penalize misses
+        aim_value *= 0.97_f32.powi(self.n_misses as i32);
+
+        // Combo scaling
+        if let Some(combo) = self.combo.filter(|_| attributes.max_combo > 0) {
+            aim_value *= ((combo as f32 / attributes.max_combo as f32).powf(0.8)).min(1.0);
+        }
+
+        // AR bonus
+        let mut ar_factor = 1.0;
+        if attributes.ar > 10.00 {
+            ar_factor += 0.30 * (attributes.ar - 10.00);
+        } else if attributes.ar < 8.0 {
+            if self.mods.hd() {
+                ar_factor += 0.02 * (8.0 - attributes.ar);
+            } else {
+                ar_factor += 0.01 * (8.0 - attributes.ar);
+            }
+        }
+        aim_value *= ar_factor as f32;
+
+        // HD bonus
+        if self.mods.hd() {
+            aim_value *= 1.18;
+        }
+
+        // FL bonus
+        aim_value *= self.fl_bonus(total_hits);
+
+        // Scale with accuracy
+        aim_value *= 0.5 + self.acc.unwrap() / 2.0;
+
+        aim_value
+    }
+
+    fn compute_speed_value(&self) -> f32 {
+        let attributes = self.attributes.as_ref().unwrap();
+
+        let mut speed_value =
+            (5.0 * (attributes.speed_strain as f32 / 0.045).max(1.0) - 4.0).powi(3) / 100_000.0;
+
+        // Penalize misses
+        speed_value *= 0.97_f32.powi(self.n_misses as i32);
+
+        // Combo scaling
+        if let Some(combo) = self.combo.filter(|_| attributes.max_combo > 0) {
+            speed_value *= ((combo as f32 / attributes.max_combo as f32).powf(0.8)).min(1.0);
+        }
+
+        // Scale with accuracy
+        speed_value *= 0.5 + self.acc.unwrap() / 2.0;
+
+        speed_value
+    }
+
+    fn compute_accuracy_value(&self, total_hits: f32) -> (f32, f32) {
+        let attributes = self.attributes.as_ref().unwrap();
+        let n_circles = attributes.n_circles as f32;
+        let n300 = self.n300.unwrap_or(0) as f32;
+        let n100 = self.n100.unwrap_or(0) as f32;
+        let n50 = self.n50.unwrap_or(0) as f32;
+
+        let better_acc_percentage = (n_circles > 0.0) as u8 as f32
+            * (((n300 - (total_hits - n_circles)) * 6.0 + n100 * 2.0 + n50) / (n_circles * 6.0))
+                .max(0.0);
+
+        let mut acc_value =
+            1.52163_f32.powf(attributes.od as f32) * better_acc_percentage.powi(24) * 2.738;
+
+        // Bonus for many hitcircles
+        acc_value *= ((n_circles / 1000.0).powf(0.3)).min(1.15);
+
+        // HD bonus
+        if self.mods.hd() {
+            acc_value *= 1.02;
+        }
+
+        // FL bonus
+        if self.mods.fl() {
+            acc_value *= 1.02;
+        }
+
+        (acc_value, better_acc_percentage)
+    }
+
+    /// The aim multiplier of FL.
+    fn fl_bonus(&self, total_hits: f32) -> f32 {
+        if self.mods.fl() {
+            self.fl_length_bonus.multiplier(total_hits, self.combo)
+        } else {
+            1.0
+        }
+    }
+
+    #[inline]
+    fn total_hits(&self) -> usize {
+        let n_objects = self.passed_objects.unwrap_or(self.map.hit_objects.len());
+
+        (self.n300.unwrap_or(0) + self.n100.unwrap_or(0) + self.n50.unwrap_or(0) + self.n_misses)
+            .min(n_objects)
+    }
+}
+
+pub trait OsuAttributeProvider {
+    fn attributes(self) -> Option<OsuDifficultyAttributes>;
+}
+
+impl OsuAttributeProvider for OsuDifficultyAttributes {
+    #[inline]
+    fn attributes(self) -> Option<OsuDifficultyAttributes> {
+        Some(self)
+    }
+}
+
+impl OsuAttributeProvider for OsuPerformanceAttributes {
+    #[inline]
+    fn attributes(self) -> Option<OsuDifficultyAttributes> {
+        Some(self.difficulty)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rosu_pp::Beatmap;
+
+    #[test]
+    fn osu_only_accuracy() {
+        let map = Beatmap::default();
+
+        let total_objects = 1234;
+        let target_acc = 97.5;
+
+        let calculator = OsuPP::new(&map)
+            .passed_objects(total_objects)
+            .accuracy(target_acc);
+
+        let numerator = 6 * calculator.n300.unwrap_or(0)
+            + 2 * calculator.n100.unwrap_or(0)
+            + calculator.n50.unwrap_or(0);
+        let denominator = 6 * total_objects;
+        let acc = 100.0 * numerator as f32 / denominator as f32;
+
+        assert!(
+            (target_acc - acc).abs() < 1.0,
+            "Expected: {} | Actual: {}",
+            target_acc,
+            acc
+        );
+    }
+
+    #[test]
+    fn osu_accuracy_and_n50() {
+        let map = Beatmap::default();
+
+        let total_objects = 1234;
+        let target_acc = 97.5;
+        let n50 = 30;
+
+        let calculator = OsuPP::new(&map)
+            .passed_objects(total_objects)
+            .n50(n50)
+            .accuracy(target_acc);
+
+        assert!(
+            (calculator.n50.unwrap() as i32 - n50 as i32).abs() <= 4,
+            "Expected: {} | Actual: {}",
+            n50,
+            calculator.n50.unwrap()
+        );
+
+        let numerator = 6 * calculator.n300.unwrap_or(0)
+            + 2 * calculator.n100.unwrap_or(0)
+            + calculator.n50.unwrap_or(0);
+        let denominator = 6 * total_objects;
+        let acc = 100.0 * numerator as f32 / denominator as f32;
+
+        assert!(
+            (target_acc - acc).abs() < 1.0,
+            "Expected: {} | Actual: {}",
+            target_acc,
+            acc
+        );
+    }
+
+    #[test]
+    fn osu_missing_objects() {
+        let map = Beatmap::default();
+
+        let total_objects = 1234;
+        let n300 = 1000;
+        let n100 = 200;
+        let n50 = 30;
+
+        let mut calculator = OsuPP::new(&map)
+            .passed_objects(total_objects)
+            .n300(n300)
+            .n100(n100)
+            .n50(n50);
+
+        calculator.assert_hitresults();
+
+        let n_objects = calculator.n300.unwrap()
+            + calculator.n100.unwrap()
+            + calculator.n50.unwrap()
+            + calculator.n_misses;
+
+        assert_eq!(
+            total_objects, n_objects,
+            "Expected: {} | Actual: {}",
+            total_objects, n_objects
+        );
+    }
+
+    #[test]
+    fn osu_score_v2_bits_ignored() {
+        let map = Beatmap::default();
+
+        let attributes = OsuDifficultyAttributes {
+            od: 8.0,
+            n_circles: 8,
+            ..Default::default()
+        };
+
+        let calculator = OsuPP::new(&map)
+            .attributes(attributes)
+            .passed_objects(10)
+            .n100(2);
+
+        // ScoreV2 did not exist yet so its bit must not affect the accuracy
+        let (acc_value, acc) = calculator.clone().compute_accuracy_value(10.0);
+        let (v2_acc_value, v2_acc) = calculator.mods(1 << 29).compute_accuracy_value(10.0);
+
+        assert_eq!(acc_value, v2_acc_value);
+        assert_eq!(acc, v2_acc);
+    }
+}
@@ -0,0 +1,49 @@
+use crate::util::strain::{self, StrainKind, StrainObject, StrainValue};
+
+use super::{DifficultyObject, SkillKind};
+
+const SPEED_SKILL_MULTIPLIER: f32 = 1400.0;
+const SPEED_STRAIN_DECAY_BASE: f32 = 0.3;
+
+const AIM_SKILL_MULTIPLIER: f32 = 26.25;
+const AIM_STRAIN_DECAY_BASE: f32 = 0.15;
+
+pub type Skill = strain::Skill<SkillKind>;
+
+impl StrainKind for SkillKind {
+    #[inline]
+    fn skill_multiplier(self) -> f32 {
+        match self {
+            SkillKind::Aim => AIM_SKILL_MULTIPLIER,
+            SkillKind::Speed => SPEED_SKILL_MULTIPLIER,
+        }
+    }
+
+    #[inline]
+    fn strain_decay_base(self) -> f32 {
+        match self {
+            SkillKind::Aim => AIM_STRAIN_DECAY_BASE,
+            SkillKind::Speed => SPEED_STRAIN_DECAY_BASE,
+        }
+    }
+}
+
+impl StrainValue<DifficultyObject<'_>> for SkillKind {
+    #[inline]
+    fn strain_value(self, current: &DifficultyObject<'_>) -> f32 {
+        self.strain_value_of(current)
+    }
+}
+
+impl StrainObject for DifficultyObject<'_> {
+    #[inline]
+    fn time(&self) -> f32 {
+        self.base.time
+    }
+
+    #[inline]
+    fn decay_delta(&self) -> f32 {
+        // Unlike the addition, the decay is not affected by the minimum delta
+        self.elapsed
+    }
+}
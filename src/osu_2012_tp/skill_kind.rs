@@ -0,0 +1,62 @@
+use super::{osu_object::OsuObjectKind, DifficultyObject};
+
+const SINGLE_SPACING_TRESHOLD: f32 = 125.0;
+const STREAM_SPACING_TRESHOLD: f32 = 110.0;
+const ALMOST_DIAMETER: f32 = 90.0;
+
+#[derive(Copy, Clone)]
+pub enum SkillKind {
+    Aim,
+    Speed,
+}
+
+impl SkillKind {
+    pub fn strain_value_of(self, current: &DifficultyObject) -> f32 {
+        let value = match current.base.kind {
+            OsuObjectKind::Circle => self.spacing_weight(current.dist),
+            // The lazy movement is taken from the previous object but only
+            // considered if the current object is a slider as well
+            OsuObjectKind::Slider(_) => {
+                let slider = current.prev_slider;
+                let subsequent_spans = (slider.span_count - 1) as f32;
+
+                match self {
+                    // The whole slider is a single spacing entity
+                    Self::Speed => self.spacing_weight(
+                        slider.first_len + slider.subsequent_len * subsequent_spans + current.dist,
+                    ),
+                    // Each span and the jump after the slider are separate jumps
+                    Self::Aim => {
+                        self.spacing_weight(slider.first_len)
+                            + self.spacing_weight(slider.subsequent_len) * subsequent_spans
+                            + self.spacing_weight(current.dist)
+                    }
+                }
+            }
+            OsuObjectKind::Spinner => 0.0,
+        };
+
+        value / current.delta
+    }
+
+    fn spacing_weight(self, dist: f32) -> f32 {
+        match self {
+            Self::Aim => dist.powf(0.99),
+            Self::Speed => {
+                if dist > SINGLE_SPACING_TRESHOLD {
+                    2.5
+                } else if dist > STREAM_SPACING_TRESHOLD {
+                    1.6 + 0.9 * (dist - STREAM_SPACING_TRESHOLD)
+                        / (SINGLE_SPACING_TRESHOLD - STREAM_SPACING_TRESHOLD)
+                } else if dist > ALMOST_DIAMETER {
+                    1.2 + 0.4 * (dist - ALMOST_DIAMETER)
+                        / (STREAM_SPACING_TRESHOLD - ALMOST_DIAMETER)
+                } else if dist > ALMOST_DIAMETER / 2.0 {
+                    0.95 + 0.25 * (dist - ALMOST_DIAMETER / 2.0) / (ALMOST_DIAMETER / 2.0)
+                } else {
+                    0.95
+                }
+            }
+        }
+    }
+}
@@ -1,14 +1,14 @@
 use super::OsuObject;
 
-pub(crate) struct DifficultyObject<'h> {
-    pub(crate) base: &'h OsuObject,
-    pub(crate) travel_dist: f32,
-    pub(crate) dist: f32,
-    pub(crate) delta: f32,
+pub struct DifficultyObject<'h> {
+    pub base: &'h OsuObject,
+    pub travel_dist: f32,
+    pub dist: f32,
+    pub delta: f32,
 }
 
 impl<'h> DifficultyObject<'h> {
-    pub(crate) fn new(
+    pub fn new(
         base: &'h OsuObject,
         prev: &OsuObject,
         clock_rate: f32,
@@ -2,9 +2,19 @@
 //! This means the jump distance inbetween notes might be slightly off, resulting in small inaccuracies.
 //! Since calculating these offsets is relatively expensive though, this version is faster than `all_included`.
 
-use crate::util::curve::CurveBuffers;
+use crate::{
+    mods::DifficultyAdjust,
+    section::SectionLen,
+    util::{curve::CurveBuffers, math::stable_cs, mode::check_mode},
+    Error,
+};
 
-use super::{DifficultyObject, OsuObject, Skill, SkillKind};
+pub use crate::osu::{
+    DifficultyAttributes as OsuDifficultyAttributes,
+    PerformanceAttributes as OsuPerformanceAttributes, Strains as OsuStrains,
+};
+
+use super::{object_combo, DifficultyObject, OsuObject, Skill, SkillKind, MODE};
 
 use rosu_pp::{Beatmap, Mods};
 
@@ -26,10 +36,286 @@ const OD_MAX: f64 = 19.5;
 /// processing stack leniency is relatively expensive.
 ///
 /// In case of a partial play, e.g. a fail, one can specify the amount of passed objects.
+///
+/// Maps of other modes can not be calculated and result in default attributes.
 pub fn stars(map: &Beatmap, mods: u32, passed_objects: Option<usize>) -> OsuDifficultyAttributes {
+    stars_with_clock_rate(map, mods, passed_objects, mods.clock_rate())
+}
+
+/// Star calculation for osu!standard maps with a custom clock rate.
+///
+/// The clock rate replaces the one of DT or HT, e.g. `1.4` for a lazer DT
+/// score that was set at 1.4x, and applies to both the map attributes and
+/// the strain timing.
+pub fn stars_with_clock_rate(
+    map: &Beatmap,
+    mods: u32,
+    passed_objects: Option<usize>,
+    clock_rate: f64,
+) -> OsuDifficultyAttributes {
+    calculate_stars(
+        map,
+        mods,
+        passed_objects,
+        clock_rate,
+        &DifficultyAdjust::default(),
+        None,
+    )
+}
+
+/// Difficulty calculator on osu!standard maps.
+///
+/// # Example
+///
+/// ```
+/// use rosu_pp::Beatmap;
+/// use rosu_pp_older::osu_2014_july::OsuStars;
+///
+/// # /*
+/// let map: Beatmap = ...
+/// # */
+/// # let map = Beatmap::default();
+///
+/// let difficulty_attrs = OsuStars::new(&map)
+///     .mods(8 + 64) // HDDT
+///     .calculate();
+///
+/// println!("Stars: {}", difficulty_attrs.stars);
+/// ```
+#[derive(Clone, Debug)]
+pub struct OsuStars<'map> {
+    map: &'map Beatmap,
+    mods: u32,
+    passed_objects: Option<usize>,
+    clock_rate: Option<f64>,
+    difficulty_adjust: DifficultyAdjust,
+}
+
+impl<'map> OsuStars<'map> {
+    /// Create a new difficulty calculator for osu!standard maps.
+    #[inline]
+    pub fn new(map: &'map Beatmap) -> Self {
+        Self {
+            map,
+            mods: 0,
+            passed_objects: None,
+            clock_rate: None,
+            difficulty_adjust: DifficultyAdjust::default(),
+        }
+    }
+
+    /// Specify mods through their bit values.
+    ///
+    /// See [https://github.com/ppy/osu-api/wiki#mods](https://github.com/ppy/osu-api/wiki#mods)
+    #[inline]
+    pub fn mods(mut self, mods: u32) -> Self {
+        self.mods = mods;
+
+        self
+    }
+
+    /// Specify mods through their acronyms, e.g. `"HDDT"`.
+    ///
+    /// Returns an error for unknown acronyms and for mods
+    /// that did not exist yet at the time of this version.
+    #[inline]
+    pub fn mods_str(self, acronyms: &str) -> Result<Self, Error> {
+        super::parse_mods(acronyms).map(|mods| self.mods(mods))
+    }
+
+    /// Amount of passed objects for partial plays, e.g. a fail.
+    #[inline]
+    pub fn passed_objects(mut self, passed_objects: usize) -> Self {
+        self.passed_objects = Some(passed_objects);
+
+        self
+    }
+
+    /// Adjust the clock rate used in the calculation, e.g. `1.4` for a lazer DT
+    /// score that was set at 1.4x.
+    ///
+    /// If none is specified, the clock rate of the mods is used.
+    #[inline]
+    pub fn clock_rate(mut self, clock_rate: f64) -> Self {
+        self.clock_rate = Some(clock_rate);
+
+        self
+    }
+
+    /// Specify the values of lazer's Difficulty Adjust mod.
+    ///
+    /// The values replace the map's base values before HR, EZ, and the clock rate are applied.
+    #[inline]
+    pub fn difficulty_adjust(mut self, difficulty_adjust: DifficultyAdjust) -> Self {
+        self.difficulty_adjust = difficulty_adjust;
+
+        self
+    }
+
+    /// Override the map's approach rate, see [`difficulty_adjust`](Self::difficulty_adjust).
+    #[inline]
+    pub fn ar(mut self, ar: f32) -> Self {
+        self.difficulty_adjust.ar = Some(ar);
+
+        self
+    }
+
+    /// Override the map's circle size, see [`difficulty_adjust`](Self::difficulty_adjust).
+    #[inline]
+    pub fn cs(mut self, cs: f32) -> Self {
+        self.difficulty_adjust.cs = Some(cs);
+
+        self
+    }
+
+    /// Override the map's overall difficulty, see [`difficulty_adjust`](Self::difficulty_adjust).
+    #[inline]
+    pub fn od(mut self, od: f32) -> Self {
+        self.difficulty_adjust.od = Some(od);
+
+        self
+    }
+
+    /// Override the map's drain rate, see [`difficulty_adjust`](Self::difficulty_adjust).
+    #[inline]
+    pub fn hp(mut self, hp: f32) -> Self {
+        self.difficulty_adjust.hp = Some(hp);
+
+        self
+    }
+
+    /// Calculate all difficulty related values, including stars.
+    ///
+    /// Maps of other modes can not be calculated and result in default attributes.
+    #[inline]
+    pub fn calculate(self) -> OsuDifficultyAttributes {
+        let clock_rate = self.clock_rate.unwrap_or_else(|| self.mods.clock_rate());
+
+        calculate_stars(
+            self.map,
+            self.mods,
+            self.passed_objects,
+            clock_rate,
+            &self.difficulty_adjust,
+            None,
+        )
+    }
+}
+
+pub(crate) fn calculate_stars(
+    map: &Beatmap,
+    mods: u32,
+    passed_objects: Option<usize>,
+    clock_rate: f64,
+    difficulty_adjust: &DifficultyAdjust,
+    section_len: Option<SectionLen>,
+) -> OsuDifficultyAttributes {
+    calculate_stars_with_strains(
+        map,
+        mods,
+        passed_objects,
+        clock_rate,
+        difficulty_adjust,
+        section_len,
+    )
+    .0
+}
+
+/// Calculate the difficulty attributes while keeping the section strain peaks.
+pub(crate) fn calculate_stars_with_strains(
+    map: &Beatmap,
+    mods: u32,
+    passed_objects: Option<usize>,
+    clock_rate: f64,
+    difficulty_adjust: &DifficultyAdjust,
+    section_len: Option<SectionLen>,
+) -> (OsuDifficultyAttributes, OsuStrains) {
+    if check_mode(map.mode, MODE).is_err() {
+        return Default::default();
+    }
+
+    let (mut aim, mut speed, mut diff_attrs) = calculate_skills(
+        map,
+        mods,
+        passed_objects,
+        clock_rate,
+        difficulty_adjust,
+        section_len,
+    );
+
+    let strains = OsuStrains {
+        section_len: section_len.map_or(SECTION_LEN as f64 * clock_rate, |section_len| {
+            section_len.map_time(clock_rate)
+        }),
+        aim: aim.strain_peaks.iter().copied().map(f64::from).collect(),
+        speed: speed.strain_peaks.iter().copied().map(f64::from).collect(),
+        ..Default::default()
+    };
+
+    let aim_strain = aim.difficulty_value().sqrt() * DIFFICULTY_MULTIPLIER;
+    let speed_strain = speed.difficulty_value().sqrt() * DIFFICULTY_MULTIPLIER;
+
+    let stars = aim_strain + speed_strain + (aim_strain - speed_strain).abs() / 2.0;
+
+    diff_attrs.stars = stars as f64;
+    diff_attrs.speed_strain = speed_strain as f64;
+    diff_attrs.aim_strain = aim_strain as f64;
+
+    (diff_attrs, strains)
+}
+
+/// The maximum combo of an osu!standard map.
+///
+/// Only counts the nested objects of sliders which is considerably cheaper than
+/// calculating the stars, e.g. when validating scores.
+pub fn max_combo(map: &Beatmap) -> usize {
+    let mut curve_bufs = CurveBuffers::default();
+
+    map.hit_objects
+        .iter()
+        .map(|h| object_combo(h, map, &mut curve_bufs))
+        .sum()
+}
+
+/// The section strain peaks of an osu!standard map, e.g. to draw a difficulty graph.
+pub fn strains(map: &Beatmap, mods: u32) -> OsuStrains {
+    let clock_rate = mods.clock_rate();
+    let difficulty_adjust = DifficultyAdjust::default();
+    let (aim, speed, _) = calculate_skills(map, mods, None, clock_rate, &difficulty_adjust, None);
+
+    OsuStrains {
+        section_len: SECTION_LEN as f64 * clock_rate,
+        aim: aim.strain_peaks.into_iter().map(f64::from).collect(),
+        speed: speed.strain_peaks.into_iter().map(f64::from).collect(),
+        ..Default::default()
+    }
+}
+
+/// The radius of hit objects in osu!pixels after applying HR or EZ.
+#[inline]
+pub fn object_radius(cs: f32, mods: u32) -> f32 {
+    OBJECT_RADIUS * (1.0 - 0.7 * (stable_cs(cs, mods) - 5.0) / 5.0) / 2.0
+}
+
+/// The factor by which jump distances are scaled to normalize the object radius.
+#[inline]
+pub fn scaling_factor(cs: f32, mods: u32) -> f32 {
+    NORMALIZED_RADIUS / object_radius(cs, mods)
+}
+
+fn calculate_skills(
+    map: &Beatmap,
+    mods: u32,
+    passed_objects: Option<usize>,
+    clock_rate: f64,
+    difficulty_adjust: &DifficultyAdjust,
+    section_len: Option<SectionLen>,
+) -> (Skill, Skill, OsuDifficultyAttributes) {
     let take = passed_objects.unwrap_or(map.hit_objects.len());
 
-    let map_attributes = map.attributes().mods(mods).build();
+    let map_attributes = difficulty_adjust
+        .apply(map.attributes().mods(mods).clock_rate(clock_rate))
+        .build();
 
     let mod_mult = match (mods.hr(), mods.ez()) {
         (true, _) => 1.4,
@@ -39,17 +325,31 @@ pub fn stars(map: &Beatmap, mods: u32, passed_objects: Option<usize>) -> OsuDiff
 
     let mut diff_attrs = OsuDifficultyAttributes {
         ar: map_attributes.ar,
-        od: modify_od(map.od as f64, map_attributes.clock_rate, mod_mult),
+        od: modify_od(
+            difficulty_adjust.od.unwrap_or(map.od) as f64,
+            map_attributes.clock_rate,
+            mod_mult,
+        ),
+        base_ar: difficulty_adjust.ar.unwrap_or(map.ar) as f64,
+        base_od: difficulty_adjust.od.unwrap_or(map.od) as f64,
         ..Default::default()
     };
 
     if take < 2 {
-        return diff_attrs;
+        return (
+            Skill::new(SkillKind::Aim),
+            Skill::new(SkillKind::Speed),
+            diff_attrs,
+        );
     }
 
-    let section_len = SECTION_LEN * map_attributes.clock_rate as f32;
-    let radius = OBJECT_RADIUS * (1.0 - 0.7 * (map_attributes.cs as f32 - 5.0) / 5.0) / 2.0;
-    let scaling_factor = NORMALIZED_RADIUS / radius;
+    let section_len = match section_len {
+        Some(section_len) => section_len.map_time(map_attributes.clock_rate) as f32,
+        None => SECTION_LEN * map_attributes.clock_rate as f32,
+    };
+    let cs = difficulty_adjust.cs.unwrap_or(map.cs);
+    let radius = object_radius(cs, mods);
+    let scaling_factor = scaling_factor(cs, mods);
 
     let mut ticks_buf = Vec::new();
     let mut curve_bufs = CurveBuffers::default();
@@ -120,43 +420,15 @@ pub fn stars(map: &Beatmap, mods: u32, passed_objects: Option<usize>) -> OsuDiff
     aim.save_current_peak();
     speed.save_current_peak();
 
-    let aim_strain = aim.difficulty_value().sqrt() * DIFFICULTY_MULTIPLIER;
-    let speed_strain = speed.difficulty_value().sqrt() * DIFFICULTY_MULTIPLIER;
-
-    let stars = aim_strain + speed_strain + (aim_strain - speed_strain).abs() / 2.0;
-
-    diff_attrs.stars = stars as f64;
-    diff_attrs.speed_strain = speed_strain as f64;
-    diff_attrs.aim_strain = aim_strain as f64;
-
-    diff_attrs
-}
-
-#[derive(Clone, Debug, Default)]
-pub struct OsuDifficultyAttributes {
-    pub aim_strain: f64,
-    pub speed_strain: f64,
-    pub ar: f64,
-    pub od: f64,
-    pub hp: f64,
-    pub n_circles: usize,
-    pub n_sliders: usize,
-    pub n_spinners: usize,
-    pub stars: f64,
-    pub max_combo: usize,
-}
-
-#[derive(Clone, Debug)]
-pub struct OsuPerformanceAttributes {
-    pub difficulty: OsuDifficultyAttributes,
-    pub pp: f64,
-    pub pp_acc: f64,
-    pub pp_aim: f64,
-    pub pp_flashlight: f64,
-    pub pp_speed: f64,
+    (aim, speed, diff_attrs)
 }
 
-fn modify_od(base_od: f64, speed_mult: f64, mod_mult: f64) -> f64 {
+/// The effective overall difficulty after applying mods.
+///
+/// `mod_mult` is `1.4` for HR, `0.5` for EZ, and `1.0` otherwise while `speed_mult`
+/// is the clock rate. The 300 hit window is rounded to whole milliseconds
+/// before it is scaled by the clock rate and converted back.
+pub fn modify_od(base_od: f64, speed_mult: f64, mod_mult: f64) -> f64 {
     let mut od = base_od;
     od *= mod_mult;
     let mut odms = OD_MIN - (6.0 * od).ceil();
@@ -165,3 +437,11 @@ fn modify_od(base_od: f64, speed_mult: f64, mod_mult: f64) -> f64 {
     od = (OD_MIN - odms) / 6.0;
     return od;
 }
+
+/// The overall difficulty of a 300 hit window in milliseconds, the inverse of [`modify_od`].
+///
+/// Passing a window that was already scaled by the clock rate results in the effective OD.
+#[inline]
+pub fn hit_window_to_od(hit_window: f64) -> f64 {
+    (OD_MIN - hit_window) / 6.0
+}
@@ -1,8 +1,11 @@
 mod difficulty_object;
 use difficulty_object::DifficultyObject;
 
+mod object_iter;
+pub use object_iter::{difficulty_objects, OsuDifficultyObject, OsuDifficultyObjects};
+
 mod osu_object;
-use osu_object::OsuObject;
+use osu_object::{object_combo, OsuObject};
 
 mod pp;
 pub use pp::{OsuAttributeProvider, OsuPP};
@@ -14,5 +17,123 @@ mod skill_kind;
 use skill_kind::SkillKind;
 
 mod stars;
+pub use stars::{
+    hit_window_to_od, max_combo, modify_od, object_radius, scaling_factor, stars,
+    stars_with_clock_rate, strains, OsuDifficultyAttributes, OsuPerformanceAttributes, OsuStars,
+    OsuStrains,
+};
+
+use rosu_pp::{osu::OsuScoreState, Beatmap, GameMode, Mods};
+
+use crate::{
+    mods::{self, ScoreMultipliers},
+    util::math,
+    Error, HitWindows, MapAttributes,
+};
+
+/// The approximate date at which this version went live, formatted as `YYYY-MM-DD`.
+pub const VERSION_DATE: &str = "2014-05-01";
+
+/// The name of this version within its mode.
+pub const VERSION_NAME: &str = "2014-may";
+
+/// A short description of what changed in this version.
+pub const VERSION_DESCRIPTION: &str =
+    "Aim and speed get a length bonus and scale with the overall difficulty.";
+
+/// The mode this version calculates, i.e. osu!standard.
+pub const MODE: GameMode = GameMode::Osu;
+
+crate::calculator::difficulty_calculator!(OsuCalculator, Osu2014May);
+
+/// The building blocks of this version's difficulty calculation.
+///
+/// Only available with the `internals` feature. These types are meant for
+/// experimenting with custom object streams and are exempt from semver guarantees.
+#[cfg(feature = "internals")]
+pub mod internals {
+    pub use super::{
+        difficulty_object::DifficultyObject, osu_object::OsuObject, skill::Skill,
+        skill_kind::SkillKind,
+    };
+}
+
+/// Parse mod acronyms like `"HDDT"` or `"HD,DT"` into the mods value of this version.
+///
+/// Mods that did not exist yet at the time of this version cause an error.
+#[inline]
+pub fn parse_mods(acronyms: &str) -> Result<u32, Error> {
+    mods::parse_available(acronyms, mods::BEFORE_SCORE_V2)
+}
+
+/// The score multiplier of the mods in this version.
+#[inline]
+pub fn score_multiplier(mods: u32) -> f32 {
+    ScoreMultipliers::OSU.multiplier(mods)
+}
+
+/// The hit windows for the given overall difficulty and mods in this version.
+#[inline]
+pub fn hit_windows(od: f32, mods: u32) -> HitWindows {
+    HitWindows::rounded(od as f64, mods)
+}
+
+/// The preempt time in milliseconds for the given approach rate and mods in this version,
+/// i.e. how long an object is visible before it has to be hit.
+#[inline]
+pub fn ar_to_preempt(ar: f32, mods: u32) -> f64 {
+    math::ar_to_preempt(ar as f64, mods)
+}
+
+/// The effective approach rate of a preempt time in this version.
+///
+/// The result is not clamped and may be fractional, e.g. AR 9 with DT results in AR 10.33.
+#[inline]
+pub fn preempt_to_ar(preempt: f64) -> f64 {
+    math::preempt_to_ar(preempt)
+}
+
+/// The attributes of the map after applying mods as this version computed them.
+///
+/// Only the map's base values are considered so no difficulty calculation is required.
+pub fn map_attributes(map: &Beatmap, mods: u32) -> MapAttributes {
+    let hit_windows = hit_windows(map.od, mods);
+
+    MapAttributes {
+        ar: preempt_to_ar(ar_to_preempt(map.ar, mods)),
+        od: hit_window_to_od(hit_windows.n300),
+        cs: math::stable_cs(map.cs, mods) as f64,
+        hp: math::stable_hp(map.hp, mods) as f64,
+        clock_rate: mods.clock_rate(),
+        hit_windows,
+    }
+}
+
+/// Calculate the difficulty and the performance of a score in one pass.
+///
+/// Calling [`stars`] and then [`OsuPP`] without passing the attributes along calculates
+/// the difficulty twice, this function avoids that.
+pub fn stars_and_pp(
+    map: &Beatmap,
+    mods: u32,
+    state: OsuScoreState,
+) -> (OsuDifficultyAttributes, OsuPerformanceAttributes) {
+    let OsuScoreState {
+        max_combo,
+        n300,
+        n100,
+        n50,
+        n_misses,
+    } = state;
+
+    let performance = OsuPP::new(map)
+        .mods(mods)
+        .combo(max_combo)
+        .n300(n300)
+        .n100(n100)
+        .n50(n50)
+        .misses(n_misses)
+        .calculate();
 
-use rosu_pp::{Beatmap, Mods};
+    (performance.difficulty.clone(), performance)
+}
@@ -1,6 +1,18 @@
 use super::{
-    stars::{stars, OsuDifficultyAttributes, OsuPerformanceAttributes},
-    Beatmap, Mods,
+    stars::{
+        calculate_stars, calculate_stars_with_strains, OsuDifficultyAttributes,
+        OsuPerformanceAttributes, OsuStrains,
+    },
+    Beatmap, Mods, MODE,
+};
+
+use crate::{
+    mods::{DifficultyAdjust, FlLengthBonus},
+    osu::LegacyPerformanceAttributes,
+    section::SectionLen,
+    unranked::{AutopilotMode, PpComponents, RelaxMode, UnrankedConfig},
+    util::{cache::DifficultyCache, hitresults, mode::check_mode},
+    Error, IfFcAttributes, ScoreState,
 };
 
 /// Calculator for pp on osu!standard maps.
@@ -34,6 +46,7 @@ use super::{
 pub struct OsuPP<'m> {
     map: &'m Beatmap,
     attributes: Option<OsuDifficultyAttributes>,
+    cache: DifficultyCache<OsuDifficultyAttributes>,
     mods: u32,
     combo: Option<usize>,
     acc: Option<f32>,
@@ -42,7 +55,14 @@ pub struct OsuPP<'m> {
     n100: Option<usize>,
     n50: Option<usize>,
     n_misses: usize,
+    estimate_hitresults: bool,
     passed_objects: Option<usize>,
+    clock_rate: Option<f64>,
+    difficulty_adjust: DifficultyAdjust,
+    section_len: Option<SectionLen>,
+    fl_length_bonus: FlLengthBonus,
+
+    unranked: UnrankedConfig,
 }
 
 impl<'m> OsuPP<'m> {
@@ -51,6 +71,7 @@ impl<'m> OsuPP<'m> {
         Self {
             map,
             attributes: None,
+            cache: DifficultyCache::default(),
             mods: 0,
             combo: None,
             acc: None,
@@ -59,7 +80,14 @@ impl<'m> OsuPP<'m> {
             n100: None,
             n50: None,
             n_misses: 0,
+            estimate_hitresults: false,
             passed_objects: None,
+            clock_rate: None,
+            difficulty_adjust: DifficultyAdjust::default(),
+            section_len: None,
+            fl_length_bonus: FlLengthBonus::Flat,
+
+            unranked: UnrankedConfig::default(),
         }
     }
 
@@ -83,10 +111,20 @@ impl<'m> OsuPP<'m> {
     #[inline]
     pub fn mods(mut self, mods: u32) -> Self {
         self.mods = mods;
+        self.cache.reset();
 
         self
     }
 
+    /// Specify mods through their acronyms, e.g. `"HDDT"`.
+    ///
+    /// Returns an error for unknown acronyms and for mods
+    /// that did not exist yet at the time of this version.
+    #[inline]
+    pub fn mods_str(self, acronyms: &str) -> Result<Self, Error> {
+        super::parse_mods(acronyms).map(|mods| self.mods(mods))
+    }
+
     /// Specify the max combo of the play.
     #[inline]
     pub fn combo(mut self, combo: usize) -> Self {
@@ -131,6 +169,141 @@ impl<'m> OsuPP<'m> {
     #[inline]
     pub fn passed_objects(mut self, passed_objects: usize) -> Self {
         self.passed_objects.replace(passed_objects);
+        self.cache.reset();
+
+        self
+    }
+
+    /// Specify a custom clock rate, e.g. `1.4` for a lazer DT score that was set at 1.4x.
+    ///
+    /// The clock rate replaces the one of DT or HT.
+    #[inline]
+    pub fn clock_rate(mut self, clock_rate: f64) -> Self {
+        self.clock_rate.replace(clock_rate);
+        self.cache.reset();
+
+        self
+    }
+
+    /// Specify the values of lazer's Difficulty Adjust mod.
+    ///
+    /// The values replace the map's base values before HR, EZ, and the clock rate are applied.
+    #[inline]
+    pub fn difficulty_adjust(mut self, difficulty_adjust: DifficultyAdjust) -> Self {
+        self.difficulty_adjust = difficulty_adjust;
+        self.cache.reset();
+
+        self
+    }
+
+    /// Override the map's approach rate, see [`difficulty_adjust`](Self::difficulty_adjust).
+    #[inline]
+    pub fn ar(mut self, ar: f32) -> Self {
+        self.difficulty_adjust.ar = Some(ar);
+        self.cache.reset();
+
+        self
+    }
+
+    /// Override the map's circle size, see [`difficulty_adjust`](Self::difficulty_adjust).
+    #[inline]
+    pub fn cs(mut self, cs: f32) -> Self {
+        self.difficulty_adjust.cs = Some(cs);
+        self.cache.reset();
+
+        self
+    }
+
+    /// Override the map's overall difficulty, see [`difficulty_adjust`](Self::difficulty_adjust).
+    #[inline]
+    pub fn od(mut self, od: f32) -> Self {
+        self.difficulty_adjust.od = Some(od);
+        self.cache.reset();
+
+        self
+    }
+
+    /// Override the map's drain rate, see [`difficulty_adjust`](Self::difficulty_adjust).
+    #[inline]
+    pub fn hp(mut self, hp: f32) -> Self {
+        self.difficulty_adjust.hp = Some(hp);
+        self.cache.reset();
+
+        self
+    }
+
+    /// Override the length of the sections in which strain peaks are collected.
+    ///
+    /// Only meant for research, see [`SectionLen`].
+    #[cfg(feature = "experimental")]
+    #[inline]
+    pub fn section_len(mut self, section_len: SectionLen) -> Self {
+        self.section_len.replace(section_len);
+        self.cache.reset();
+
+        self
+    }
+
+    /// Specify how the FL aim bonus scales with the length of the play.
+    ///
+    /// Defaults to the way this version did it, i.e. [`FlLengthBonus::Flat`].
+    #[inline]
+    pub fn fl_length_bonus(mut self, fl_length_bonus: FlLengthBonus) -> Self {
+        self.fl_length_bonus = fl_length_bonus;
+
+        self
+    }
+
+    /// Specify how the Relax mod should be handled.
+    ///
+    /// By default, Relax is treated like nomod by [`calculate`](Self::calculate)
+    /// and rejected by [`try_calculate`](Self::try_calculate).
+    #[inline]
+    pub fn relax(mut self, relax: RelaxMode) -> Self {
+        self.unranked.relax.replace(relax);
+
+        self
+    }
+
+    /// Specify whether [`try_calculate`](Self::try_calculate) should accept mods
+    /// that bancho never awarded pp for, i.e. AT, CN, TP, and unconfigured RX.
+    ///
+    /// Useful for private servers, defaults to `false`.
+    #[inline]
+    pub fn allow_unranked(mut self, allow_unranked: bool) -> Self {
+        self.unranked.allow_unranked = allow_unranked;
+
+        self
+    }
+
+    /// Specify how the Autopilot mod should be handled.
+    ///
+    /// By default, Autopilot plays are rejected by [`try_calculate`](Self::try_calculate)
+    /// and are worth 0 pp with [`calculate`](Self::calculate).
+    #[inline]
+    pub fn autopilot(mut self, autopilot: AutopilotMode) -> Self {
+        self.unranked.autopilot = autopilot;
+
+        self
+    }
+
+    /// Provide parameters through a [`ScoreState`].
+    #[inline]
+    pub fn state(mut self, state: impl Into<ScoreState>) -> Self {
+        let ScoreState {
+            max_combo,
+            n300,
+            n100,
+            n50,
+            n_misses,
+            ..
+        } = state.into();
+
+        self.combo = Some(max_combo);
+        self.n300 = Some(n300);
+        self.n100 = Some(n100);
+        self.n50 = Some(n50);
+        self.n_misses = n_misses;
 
         self
     }
@@ -149,7 +322,10 @@ impl<'m> OsuPP<'m> {
             let mut n50 = self.n50.unwrap_or(0);
 
             let placed_points = 2 * n100 + n50 + self.n_misses;
-            let missing_objects = n_objects - n100 - n50 - self.n_misses;
+            let missing_objects = n_objects
+                .saturating_sub(n100)
+                .saturating_sub(n50)
+                .saturating_sub(self.n_misses);
             let missing_points =
                 ((6.0 * acc * n_objects as f32).round() as usize).saturating_sub(placed_points);
 
@@ -171,12 +347,16 @@ impl<'m> OsuPP<'m> {
             self.n50.replace(n50);
         } else {
             let misses = self.n_misses.min(n_objects);
-            let target_total = (acc * n_objects as f32 * 6.0).round() as usize;
-            let delta = target_total - (n_objects - misses);
+            let remaining = n_objects - misses;
+
+            // Between all 50s and all 300s for the objects that were not missed
+            let target_total =
+                ((acc * n_objects as f32 * 6.0).round() as usize).clamp(remaining, 6 * remaining);
+            let delta = target_total - remaining;
 
             let mut n300 = delta / 5;
-            let mut n100 = delta % 5;
-            let mut n50 = n_objects - n300 - n100 - misses;
+            let mut n100 = (delta % 5).min(remaining.saturating_sub(n300));
+            let mut n50 = remaining.saturating_sub(n300).saturating_sub(n100);
 
             // Sacrifice n300s to transform n50s into n100s
             let n = n300.min(n50 / 4);
@@ -197,7 +377,32 @@ impl<'m> OsuPP<'m> {
         self
     }
 
+    /// Estimate the hitresults from the [`combo`](Self::combo) and the [`misses`](Self::misses)
+    /// alone, e.g. for scores of old private servers that lack their 100s and 50s.
+    ///
+    /// The map is assumed to break as rarely as the combo allows. Each break that the
+    /// misses do not account for is counted as a 100, e.g. a dropped slider end, and
+    /// all other hits are 300s. Previously specified hitresults and accuracy are ignored.
+    #[inline]
+    pub fn estimate_hitresults(mut self) -> Self {
+        self.estimate_hitresults = true;
+
+        self
+    }
+
     fn assert_hitresults(&mut self) {
+        if self.estimate_hitresults {
+            let n_objects = self.passed_objects.unwrap_or(self.map.hit_objects.len());
+            let max_combo = self.attributes.as_ref().unwrap().max_combo;
+            let (n300, n100, n50) =
+                hitresults::estimate_osu(n_objects, max_combo, self.combo, self.n_misses);
+
+            self.n300.replace(n300);
+            self.n100.replace(n100);
+            self.n50.replace(n50);
+            self.acc.take();
+        }
+
         if self.acc.is_none() {
             let n_objects = self.passed_objects.unwrap_or(self.map.hit_objects.len());
 
@@ -231,14 +436,132 @@ impl<'m> OsuPP<'m> {
         }
     }
 
+    /// Same as [`calculate`](Self::calculate) but returns an error if the map can
+    /// not be calculated by this version or if the mods contain unranked mods that
+    /// are not configured to be handled.
+    pub fn try_calculate(self) -> Result<OsuPerformanceAttributes, Error> {
+        check_mode(self.map.mode, MODE)?;
+        self.unranked.check(self.mods)?;
+
+        Ok(self.calculate())
+    }
+
+    /// Calculate the performance of the score and of its full combo.
+    ///
+    /// For the full combo, the misses are counted as 300s and the combo is the map's
+    /// max combo. Hit results that were generated through [`accuracy`](Self::accuracy)
+    /// are generated again for the same accuracy without misses instead.
+    pub fn calculate_if_fc(self) -> IfFcAttributes<OsuPerformanceAttributes> {
+        let fc = self.if_fc();
+
+        IfFcAttributes {
+            score: self.calculate(),
+            fc: fc.calculate(),
+        }
+    }
+
+    /// Same as [`calculate_if_fc`](Self::calculate_if_fc) but returns an error if the map can
+    /// not be calculated by this version or if the mods contain unranked mods that
+    /// are not configured to be handled.
+    pub fn try_calculate_if_fc(self) -> Result<IfFcAttributes<OsuPerformanceAttributes>, Error> {
+        check_mode(self.map.mode, MODE)?;
+        self.unranked.check(self.mods)?;
+
+        Ok(self.calculate_if_fc())
+    }
+
+    fn if_fc(&self) -> Self {
+        let mut fc = self.clone();
+        fc.combo = None;
+        fc.n_misses = 0;
+
+        if let Some(acc) = self.acc {
+            fc.n300 = None;
+            fc.n100 = None;
+            fc.n50 = None;
+
+            return fc.accuracy(100.0 * acc);
+        }
+
+        if let Some(ref mut n300) = fc.n300 {
+            *n300 += self.n_misses;
+        }
+
+        fc
+    }
+
+    /// Calculate the performance attributes together with the strain peaks of each section,
+    /// e.g. to draw a difficulty graph next to the pp.
+    ///
+    /// Difficulty attributes passed via [`attributes`](Self::attributes) are ignored since
+    /// the strains are only available while calculating the difficulty.
+    pub fn calculate_with_strains(mut self) -> (OsuPerformanceAttributes, OsuStrains) {
+        let clock_rate = self.clock_rate.unwrap_or_else(|| self.mods.clock_rate());
+
+        let (attributes, strains) = calculate_stars_with_strains(
+            self.map,
+            self.mods,
+            self.passed_objects,
+            clock_rate,
+            &self.difficulty_adjust,
+            self.section_len,
+        );
+
+        self.cache.insert(attributes.clone());
+        self.attributes.replace(attributes);
+
+        (self.calculate(), strains)
+    }
+
     /// Returns an object which contains the pp and [`DifficultyAttributes`](crate::osu::DifficultyAttributes)
     /// containing stars and other attributes.
+    ///
+    /// Maps of other modes can not be calculated and result in default attributes,
+    /// [`try_calculate`](Self::try_calculate) returns an error for them instead.
     pub fn calculate(mut self) -> OsuPerformanceAttributes {
+        if check_mode(self.map.mode, MODE).is_err() {
+            return OsuPerformanceAttributes::default();
+        }
+
         if self.attributes.is_none() {
-            let attributes = stars(self.map, self.mods, self.passed_objects);
+            let attributes = self.cache.get_or_insert_with(|| {
+                let clock_rate = self.clock_rate.unwrap_or_else(|| self.mods.clock_rate());
+
+                calculate_stars(
+                    self.map,
+                    self.mods,
+                    self.passed_objects,
+                    clock_rate,
+                    &self.difficulty_adjust,
+                    self.section_len,
+                )
+            });
+
             self.attributes.replace(attributes);
         }
 
+        let max_pp = self.ss().calculate_performance().pp;
+        let mut performance = self.calculate_performance();
+        performance.max_pp = max_pp;
+
+        performance
+    }
+
+    /// The same calculator for an SS, i.e. full combo without 100s, 50s, or misses.
+    fn ss(&self) -> Self {
+        Self {
+            combo: None,
+            acc: None,
+            n300: None,
+            n100: None,
+            n50: None,
+            n_misses: 0,
+            estimate_hitresults: false,
+            ..self.clone()
+        }
+    }
+
+    fn calculate_performance(mut self) -> OsuPerformanceAttributes {
         // Make sure the hitresults and accuracy are set
         self.assert_hitresults();
 
@@ -246,18 +569,35 @@ impl<'m> OsuPP<'m> {
         let mut multiplier = 1.1;
 
         // NF penalty
-        if self.mods.nf() {
-            multiplier *= 0.9;
-        }
+        let nf_multiplier = if self.mods.nf() { 0.9 } else { 1.0 };
+        multiplier *= nf_multiplier;
 
         // SO penalty
         if self.mods.so() {
             multiplier *= 0.95;
         }
 
-        let aim_value = self.compute_aim_value(total_hits);
-        let speed_value = self.compute_speed_value(total_hits);
-        let acc_value = self.compute_accuracy_value(total_hits);
+        let fl_bonus = self.fl_bonus(total_hits);
+        let mut aim_value = self.compute_aim_value(total_hits);
+        let mut speed_value = self.compute_speed_value(total_hits);
+        let (mut acc_value, acc_on_circles) = self.compute_accuracy_value(total_hits);
+        let n_spinners = self.attributes.as_ref().unwrap().n_spinners;
+
+        // Unranked mods
+        if self.unranked.applies_to(self.mods) {
+            let mut components = PpComponents {
+                aim: aim_value as f64,
+                speed: speed_value as f64,
+                acc: acc_value as f64,
+                flashlight: 0.0,
+            };
+
+            self.unranked.adjust(self.mods, &mut components);
+
+            aim_value = components.aim as f32;
+            speed_value = components.speed as f32;
+            acc_value = components.acc as f32;
+        }
 
         let pp = (aim_value.powf(1.1) + speed_value.powf(1.1) + acc_value.powf(1.1))
             .powf(1.0 / 1.1)
@@ -270,6 +610,14 @@ impl<'m> OsuPP<'m> {
             pp_flashlight: 0.0,
             pp_speed: speed_value as f64,
             pp: pp as f64,
+            max_pp: 0.0,
+            nf_multiplier: nf_multiplier as f64,
+            legacy: Some(LegacyPerformanceAttributes {
+                fl_bonus: fl_bonus as f64,
+                n_spinners,
+                acc_on_circles: acc_on_circles as f64,
+                flashlight: None,
+            }),
         }
     }
 
@@ -308,9 +656,7 @@ impl<'m> OsuPP<'m> {
         }
 
         // FL bonus
-        if self.mods.fl() {
-            aim_value *= 1.50;
-        }
+        aim_value *= self.fl_bonus(total_hits);
 
         // Scale with accuracy
         aim_value *= 0.5 + self.acc.unwrap() / 2.0;
@@ -343,7 +689,7 @@ impl<'m> OsuPP<'m> {
         speed_value
     }
 
-    fn compute_accuracy_value(&self, total_hits: f32) -> f32 {
+    fn compute_accuracy_value(&self, total_hits: f32) -> (f32, f32) {
         let attributes = self.attributes.as_ref().unwrap();
         let n_circles = attributes.n_circles as f32;
         let n300 = self.n300.unwrap_or(0) as f32;
@@ -370,7 +716,16 @@ impl<'m> OsuPP<'m> {
             acc_value *= 1.02;
         }
 
-        acc_value
+        (acc_value, better_acc_percentage)
+    }
+
+    /// The aim multiplier of FL.
+    fn fl_bonus(&self, total_hits: f32) -> f32 {
+        if self.mods.fl() {
+            self.fl_length_bonus.multiplier(total_hits, self.combo)
+        } else {
+            1.0
+        }
     }
 
     #[inline]
@@ -492,4 +847,27 @@ mod test {
             total_objects, n_objects
         );
     }
+
+    #[test]
+    fn osu_score_v2_bits_ignored() {
+        let map = Beatmap::default();
+
+        let attributes = OsuDifficultyAttributes {
+            od: 8.0,
+            n_circles: 8,
+            ..Default::default()
+        };
+
+        let calculator = OsuPP::new(&map)
+            .attributes(attributes)
+            .passed_objects(10)
+            .n100(2);
+
+        // ScoreV2 did not exist yet so its bit must not affect the accuracy
+        let (acc_value, acc) = calculator.clone().compute_accuracy_value(10.0);
+        let (v2_acc_value, v2_acc) = calculator.mods(1 << 29).compute_accuracy_value(10.0);
+
+        assert_eq!(acc_value, v2_acc_value);
+        assert_eq!(acc, v2_acc);
+    }
 }
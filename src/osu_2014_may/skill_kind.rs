@@ -5,13 +5,13 @@ const STREAM_SPACING_TRESHOLD: f32 = 110.0;
 const ALMOST_DIAMETER: f32 = 90.0;
 
 #[derive(Copy, Clone)]
-pub(crate) enum SkillKind {
+pub enum SkillKind {
     Aim,
     Speed,
 }
 
 impl SkillKind {
-    pub(crate) fn strain_value_of(self, current: &DifficultyObject) -> f32 {
+    pub fn strain_value_of(self, current: &DifficultyObject) -> f32 {
         match self {
             Self::Aim => {
                 let aim_value = apply_diminishing_exp(current.dist)
@@ -1,6 +1,6 @@
-use super::{DifficultyObject, SkillKind};
+use crate::util::strain::{self, StrainKind, StrainObject, StrainValue};
 
-use std::cmp::Ordering;
+use super::{DifficultyObject, SkillKind};
 
 const SPEED_SKILL_MULTIPLIER: f32 = 1400.0;
 const SPEED_STRAIN_DECAY_BASE: f32 = 0.3;
@@ -8,90 +8,41 @@ const SPEED_STRAIN_DECAY_BASE: f32 = 0.3;
 const AIM_SKILL_MULTIPLIER: f32 = 26.25;
 const AIM_STRAIN_DECAY_BASE: f32 = 0.15;
 
-const DECAY_WEIGHT: f32 = 0.9;
-
-pub(crate) struct Skill {
-    current_strain: f32,
-    current_section_peak: f32,
-
-    kind: SkillKind,
-    pub(crate) strain_peaks: Vec<f32>,
-
-    prev_time: Option<f32>,
-}
-
-impl Skill {
-    #[inline]
-    pub(crate) fn new(kind: SkillKind) -> Self {
-        Self {
-            current_strain: 1.0,
-            current_section_peak: 1.0,
-
-            kind,
-            strain_peaks: Vec::with_capacity(128),
-
-            prev_time: None,
-        }
-    }
-
-    #[inline]
-    pub(crate) fn save_current_peak(&mut self) {
-        self.strain_peaks.push(self.current_section_peak);
-    }
-
-    #[inline]
-    pub(crate) fn start_new_section_from(&mut self, time: f32) {
-        self.current_section_peak = self.peak_strain(time - self.prev_time.unwrap());
-    }
-
-    #[inline]
-    pub(crate) fn process(&mut self, current: &DifficultyObject) {
-        self.current_strain *= self.strain_decay(current.delta);
-        self.current_strain += !current.base.is_spinner() as u8 as f32
-            * self.kind.strain_value_of(current)
-            * self.skill_multiplier();
-        self.current_section_peak = self.current_section_peak.max(self.current_strain);
-        self.prev_time.replace(current.base.time);
-    }
-
-    pub(crate) fn difficulty_value(&mut self) -> f32 {
-        let mut difficulty = 0.0;
-        let mut weight = 1.0;
-
-        self.strain_peaks
-            .sort_unstable_by(|a, b| b.partial_cmp(a).unwrap_or(Ordering::Equal));
-
-        for &strain in self.strain_peaks.iter() {
-            difficulty += strain * weight;
-            weight *= DECAY_WEIGHT;
-        }
-
-        difficulty
-    }
+pub type Skill = strain::Skill<SkillKind>;
 
+impl StrainKind for SkillKind {
     #[inline]
-    fn skill_multiplier(&self) -> f32 {
-        match self.kind {
+    fn skill_multiplier(self) -> f32 {
+        match self {
             SkillKind::Aim => AIM_SKILL_MULTIPLIER,
             SkillKind::Speed => SPEED_SKILL_MULTIPLIER,
         }
     }
 
     #[inline]
-    fn strain_decay_base(&self) -> f32 {
-        match self.kind {
+    fn strain_decay_base(self) -> f32 {
+        match self {
             SkillKind::Aim => AIM_STRAIN_DECAY_BASE,
             SkillKind::Speed => SPEED_STRAIN_DECAY_BASE,
         }
     }
+}
+
+impl StrainValue<DifficultyObject<'_>> for SkillKind {
+    #[inline]
+    fn strain_value(self, current: &DifficultyObject<'_>) -> f32 {
+        !current.base.is_spinner() as u8 as f32 * self.strain_value_of(current)
+    }
+}
 
+impl StrainObject for DifficultyObject<'_> {
     #[inline]
-    fn peak_strain(&self, delta_time: f32) -> f32 {
-        self.current_strain * self.strain_decay(delta_time)
+    fn time(&self) -> f32 {
+        self.base.time
     }
 
     #[inline]
-    fn strain_decay(&self, ms: f32) -> f32 {
-        self.strain_decay_base().powf(ms / 1000.0)
+    fn decay_delta(&self) -> f32 {
+        self.delta
     }
 }
@@ -0,0 +1,133 @@
+use std::{mem, vec::IntoIter};
+
+use rosu_pp::{Beatmap, Mods};
+
+use crate::util::{curve::CurveBuffers, mode::check_mode};
+
+use super::{
+    object_radius, scaling_factor, DifficultyObject, OsuDifficultyAttributes, OsuObject, MODE,
+};
+
+/// The geometry and timing of a hit object in relation to its predecessors,
+/// as processed by this version before evaluating any skill.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct OsuDifficultyObject {
+    /// The start time of the object in milliseconds, divided by the clock rate.
+    pub start_time: f64,
+    /// The time since the previous object in milliseconds, divided by the clock rate.
+    pub delta: f64,
+    /// The delta time capped at a minimum of 50.
+    pub strain_time: f64,
+    /// The distance from the cursor position at the end of the previous object
+    /// in normalized osu!pixels, `0.0` for spinners.
+    pub jump_dist: f64,
+    /// The distance the cursor travelled along the previous object if it is a slider
+    /// in normalized osu!pixels.
+    pub travel_dist: f64,
+    /// The angle in radians between the previous two jumps, `None` for the first two objects.
+    pub angle: Option<f64>,
+}
+
+/// Iterator over the [`OsuDifficultyObject`]s of a map, created by [`difficulty_objects`].
+///
+/// The first object has no predecessor and is thus skipped.
+pub struct OsuDifficultyObjects {
+    objects: IntoIter<OsuObject>,
+    prev: Option<OsuObject>,
+    prev_prev: Option<OsuObject>,
+    prev_vals: Option<(f32, f32)>,
+    clock_rate: f32,
+    scaling_factor: f32,
+}
+
+/// Process the hit objects of a map the way this version does for its difficulty
+/// calculation without evaluating any skill, e.g. to analyze patterns with custom metrics.
+///
+/// The positions and slider paths are evaluated upfront while the
+/// [`OsuDifficultyObject`]s are created lazily.
+///
+/// Maps of other modes can not be calculated and result in no objects.
+pub fn difficulty_objects(map: &Beatmap, mods: u32) -> OsuDifficultyObjects {
+    let clock_rate = mods.clock_rate() as f32;
+    let radius = object_radius(map.cs, mods);
+    let scaling_factor = scaling_factor(map.cs, mods);
+
+    let mut ticks_buf = Vec::new();
+    let mut attributes = OsuDifficultyAttributes::default();
+    let mut curve_bufs = CurveBuffers::default();
+
+    // Maps of other modes can not be calculated
+    let take = if check_mode(map.mode, MODE).is_ok() {
+        map.hit_objects.len()
+    } else {
+        0
+    };
+
+    let hit_objects: Vec<_> = map
+        .hit_objects
+        .iter()
+        .take(take)
+        .map(|h| {
+            OsuObject::new(
+                h,
+                map,
+                radius,
+                scaling_factor,
+                &mut ticks_buf,
+                &mut attributes,
+                &mut curve_bufs,
+            )
+        })
+        .collect();
+
+    let mut objects = hit_objects.into_iter();
+
+    OsuDifficultyObjects {
+        prev: objects.next(),
+        prev_prev: None,
+        prev_vals: None,
+        objects,
+        clock_rate,
+        scaling_factor,
+    }
+}
+
+impl Iterator for OsuDifficultyObjects {
+    type Item = OsuDifficultyObject;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let curr = self.objects.next()?;
+        let prev = self.prev.as_ref()?;
+
+        let h = DifficultyObject::new(
+            &curr,
+            prev,
+            self.prev_vals,
+            self.prev_prev.take(),
+            self.clock_rate,
+            self.scaling_factor,
+        );
+
+        let object = OsuDifficultyObject {
+            start_time: (h.base.time / self.clock_rate) as f64,
+            delta: h.delta as f64,
+            strain_time: h.strain_time as f64,
+            jump_dist: h.jump_dist as f64,
+            travel_dist: h.travel_dist as f64,
+            angle: h.angle.map(f64::from),
+        };
+
+        self.prev_vals = Some((h.jump_dist, h.strain_time));
+        self.prev_prev = mem::replace(&mut self.prev, Some(curr));
+
+        Some(object)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.objects.size_hint()
+    }
+}
+
+impl ExactSizeIterator for OsuDifficultyObjects {}
@@ -13,13 +13,13 @@ const AIM_ANGLE_BONUS_BEGIN: f32 = std::f32::consts::FRAC_PI_3;
 const TIMING_THRESHOLD: f32 = 107.0;
 
 #[derive(Copy, Clone)]
-pub(crate) enum SkillKind {
+pub enum SkillKind {
     Aim,
     Speed,
 }
 
 impl SkillKind {
-    pub(crate) fn strain_value_of(self, current: &DifficultyObject) -> f32 {
+    pub fn strain_value_of(self, current: &DifficultyObject) -> f32 {
         match self {
             Self::Aim => {
                 if current.base.is_spinner() {
@@ -1,19 +1,19 @@
 use super::OsuObject;
 
-pub(crate) struct DifficultyObject<'h> {
-    pub(crate) base: &'h OsuObject,
-    pub(crate) prev: Option<(f32, f32)>, // (jump_dist, strain_time)
+pub struct DifficultyObject<'h> {
+    pub base: &'h OsuObject,
+    pub prev: Option<(f32, f32)>, // (jump_dist, strain_time)
 
-    pub(crate) jump_dist: f32,
-    pub(crate) travel_dist: f32,
-    pub(crate) angle: Option<f32>,
+    pub jump_dist: f32,
+    pub travel_dist: f32,
+    pub angle: Option<f32>,
 
-    pub(crate) delta: f32,
-    pub(crate) strain_time: f32,
+    pub delta: f32,
+    pub strain_time: f32,
 }
 
 impl<'h> DifficultyObject<'h> {
-    pub(crate) fn new(
+    pub fn new(
         base: &'h OsuObject,
         prev: &OsuObject,
         prev_vals: Option<(f32, f32)>, // (jump_dist, strain_time)
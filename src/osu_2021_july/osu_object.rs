@@ -10,12 +10,12 @@ use super::OsuDifficultyAttributes;
 const LEGACY_LAST_TICK_OFFSET: f64 = 36.0;
 const BASE_SCORING_DISTANCE: f64 = 100.0;
 
-pub(crate) struct OsuObject {
-    pub(crate) time: f32,
-    pub(crate) pos: Pos2,
-    pub(crate) end_pos: Pos2,
+pub struct OsuObject {
+    pub time: f32,
+    pub pos: Pos2,
+    pub end_pos: Pos2,
     // circle: Some(0.0) | slider: Some(_) | spinner: None
-    pub(crate) travel_dist: Option<f32>,
+    pub travel_dist: Option<f32>,
 }
 
 impl OsuObject {
@@ -31,12 +31,16 @@ impl OsuObject {
         attrs.max_combo += 1; // hitcircle, slider head, or spinner
 
         match &h.kind {
-            HitObjectKind::Circle => Self {
-                time: h.start_time as f32,
-                pos: h.pos,
-                end_pos: h.pos,
-                travel_dist: Some(0.0),
-            },
+            HitObjectKind::Circle => {
+                attrs.n_circles += 1;
+
+                Self {
+                    time: h.start_time as f32,
+                    pos: h.pos,
+                    end_pos: h.pos,
+                    travel_dist: Some(0.0),
+                }
+            }
             HitObjectKind::Slider {
                 pixel_len,
                 repeats,
@@ -169,17 +173,79 @@ impl OsuObject {
                     travel_dist: Some(travel_dist),
                 }
             }
-            HitObjectKind::Spinner { .. } | HitObjectKind::Hold { .. } => Self {
-                time: h.start_time as f32,
-                pos: h.pos,
-                end_pos: h.pos,
-                travel_dist: None,
-            },
+            // Holds only appear in osu!mania maps which are never calculated
+            HitObjectKind::Spinner { .. } | HitObjectKind::Hold { .. } => {
+                attrs.n_spinners += 1;
+
+                Self {
+                    time: h.start_time as f32,
+                    pos: h.pos,
+                    end_pos: h.pos,
+                    travel_dist: None,
+                }
+            }
         }
     }
 
     #[inline]
-    pub(crate) fn is_spinner(&self) -> bool {
+    pub fn is_spinner(&self) -> bool {
         self.travel_dist.is_none()
     }
 }
+
+/// The combo of a hit object, i.e. `1` for circles and spinners and the head, ticks,
+/// repeats, and tail for sliders.
+///
+/// Counts the same nested objects as [`OsuObject::new`] without computing their positions.
+pub(crate) fn object_combo(h: &HitObject, map: &Beatmap, curve_bufs: &mut CurveBuffers) -> usize {
+    let (pixel_len, repeats, control_points) = match &h.kind {
+        HitObjectKind::Slider {
+            pixel_len,
+            repeats,
+            control_points,
+            ..
+        } => (*pixel_len, *repeats, control_points),
+        _ => return 1,
+    };
+
+    let timing_point = map.timing_point_at(h.start_time);
+    let difficulty_point = map.difficulty_point_at(h.start_time).unwrap_or_default();
+
+    let scoring_dist = BASE_SCORING_DISTANCE * map.slider_mult * difficulty_point.slider_vel;
+    let vel = scoring_dist / timing_point.beat_len;
+
+    let tick_dist_mult = if map.version < 8 {
+        difficulty_point.slider_vel.recip()
+    } else {
+        1.0
+    };
+
+    let tick_dist = if difficulty_point.generate_ticks {
+        scoring_dist / map.tick_rate * tick_dist_mult
+    } else {
+        f64::INFINITY
+    };
+
+    let curve = Curve::new(control_points, pixel_len, curve_bufs);
+    let len = curve.dist().min(100_000.0);
+    let tick_dist = tick_dist.clamp(0.0, len);
+
+    // Head and tail
+    let mut combo = 2;
+
+    // Repeat points are only added alongside ticks
+    if tick_dist != 0.0 {
+        let min_dist_from_end = vel * 10.0;
+        let mut curr_dist = tick_dist;
+        let mut n_ticks = 0;
+
+        while curr_dist < len - min_dist_from_end {
+            n_ticks += 1;
+            curr_dist += tick_dist;
+        }
+
+        combo += n_ticks + repeats * (1 + n_ticks);
+    }
+
+    combo
+}
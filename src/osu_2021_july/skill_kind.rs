@@ -19,15 +19,15 @@ const AIM_DIFFICULTY_MULTIPLIER: f32 = 1.06;
 const SPEED_DIFFICULTY_MULTIPLIER: f32 = 1.04;
 
 #[derive(Copy, Clone)]
-pub(crate) enum SkillKind {
-    Aim,
+pub enum SkillKind {
+    Aim { with_sliders: bool },
     Speed,
 }
 
 impl SkillKind {
-    pub(crate) fn strain_value_of(self, current: &DifficultyObject) -> f32 {
+    pub fn strain_value_of(self, current: &DifficultyObject) -> f32 {
         match self {
-            Self::Aim => {
+            Self::Aim { with_sliders } => {
                 if current.base.is_spinner() {
                     return 0.0;
                 }
@@ -49,10 +49,16 @@ impl SkillKind {
                 }
 
                 let jump_dist_exp = apply_diminishing_exp(current.jump_dist);
-                let travel_dist_exp = apply_diminishing_exp(current.travel_dist);
 
-                let dist_exp =
-                    jump_dist_exp + travel_dist_exp + (travel_dist_exp * jump_dist_exp).sqrt();
+                // Later versions compare the aim strain with and without the slider
+                // travel distance to reduce the aim value of slider-heavy maps
+                let dist_exp = if with_sliders {
+                    let travel_dist_exp = apply_diminishing_exp(current.travel_dist);
+
+                    jump_dist_exp + travel_dist_exp + (travel_dist_exp * jump_dist_exp).sqrt()
+                } else {
+                    jump_dist_exp
+                };
 
                 (result + dist_exp / (current.strain_time).max(TIMING_THRESHOLD))
                     .max(dist_exp / current.strain_time)
@@ -100,9 +106,9 @@ impl SkillKind {
     }
 
     #[inline]
-    pub(crate) fn difficulty_values(&self) -> (usize, f32) {
+    pub fn difficulty_values(&self) -> (usize, f32) {
         match self {
-            Self::Aim => (AIM_REDUCED_SECTION_COUNT, AIM_DIFFICULTY_MULTIPLIER),
+            Self::Aim { .. } => (AIM_REDUCED_SECTION_COUNT, AIM_DIFFICULTY_MULTIPLIER),
             Self::Speed => (SPEED_REDUCED_SECTION_COUNT, SPEED_DIFFICULTY_MULTIPLIER),
         }
     }
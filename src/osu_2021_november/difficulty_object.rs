@@ -9,21 +9,21 @@ const MIN_DELTA_TIME: f64 = 25.0;
 const MAXIMUM_SLIDER_RADIUS: f32 = NORMALIZED_RADIUS * 2.4;
 const ASSUMED_SLIDER_RADIUS: f32 = NORMALIZED_RADIUS * 1.8;
 
-pub(crate) struct DifficultyObject<'h> {
-    pub(crate) base: &'h OsuObject,
-    pub(crate) clock_rate: f64,
+pub struct DifficultyObject<'h> {
+    pub base: &'h OsuObject,
+    pub clock_rate: f64,
 
-    pub(crate) delta: f64,
-    pub(crate) strain_time: f64,
+    pub delta: f64,
+    pub strain_time: f64,
 
-    pub(crate) angle: Option<f64>,
-    pub(crate) jump_dist: f64,
+    pub angle: Option<f64>,
+    pub jump_dist: f64,
 
-    pub(crate) movement_dist: f64,
-    pub(crate) movement_time: f64,
+    pub movement_dist: f64,
+    pub movement_time: f64,
 
-    pub(crate) travel_dist: f64,
-    pub(crate) travel_time: f64,
+    pub travel_dist: f64,
+    pub travel_time: f64,
 }
 
 impl<'h> DifficultyObject<'h> {
@@ -1,4 +1,5 @@
 mod difficulty_object;
+mod object_iter;
 mod osu_object;
 mod pp;
 mod scaling_factor;
@@ -8,14 +9,30 @@ mod skill_kind;
 use std::mem;
 
 use difficulty_object::DifficultyObject;
-use osu_object::{ObjectParameters, OsuObject};
+pub use object_iter::{difficulty_objects, OsuDifficultyObject, OsuDifficultyObjects};
+use osu_object::{object_combo, ObjectParameters, OsuObject};
 pub use pp::*;
-use rosu_pp::{Beatmap, Mods};
+use rosu_pp::{osu::OsuScoreState, Beatmap, GameMode, Mods};
 use scaling_factor::ScalingFactor;
 use skill::Skill;
 use skill_kind::SkillKind;
 
-use crate::util::curve::CurveBuffers;
+pub use crate::osu::{
+    DifficultyAttributes as OsuDifficultyAttributes,
+    PerformanceAttributes as OsuPerformanceAttributes, Strains as OsuStrains,
+};
+
+use crate::{
+    mods::{self, DifficultyAdjust, ScoreMultipliers},
+    section::SectionLen,
+    util::{
+        curve::CurveBuffers,
+        math::{self, stable_cs},
+        mode::check_mode,
+        mods::SCORE_V2,
+    },
+    Error, HitWindows, MapAttributes,
+};
 
 use self::skill::Skills;
 
@@ -24,6 +41,123 @@ const DIFFICULTY_MULTIPLIER: f64 = 0.0675;
 const NORMALIZED_RADIUS: f32 = 50.0; // * diameter of 100; easier mental maths.
 const STACK_DISTANCE: f32 = 3.0;
 
+/// The approximate date at which this version went live, formatted as `YYYY-MM-DD`.
+pub const VERSION_DATE: &str = "2021-11-09";
+
+/// The name of this version within its mode.
+pub const VERSION_NAME: &str = "2021-november";
+
+/// A short description of what changed in this version.
+pub const VERSION_DESCRIPTION: &str =
+    "Rework of aim and speed, adding slider aim and rhythm complexity.";
+
+/// The mode this version calculates, i.e. osu!standard.
+pub const MODE: GameMode = GameMode::Osu;
+
+crate::calculator::difficulty_calculator!(OsuCalculator, Osu2021November);
+
+/// The building blocks of this version's difficulty calculation.
+///
+/// Only available with the `internals` feature. These types are meant for
+/// experimenting with custom object streams and are exempt from semver guarantees.
+#[cfg(feature = "internals")]
+pub mod internals {
+    pub use super::{
+        difficulty_object::DifficultyObject,
+        osu_object::{NestedObject, NestedObjectKind, OsuObject, OsuObjectKind},
+        skill::Skill,
+        skill_kind::SkillKind,
+    };
+}
+
+/// Parse mod acronyms like `"HDDT"` or `"HD,DT"` into the mods value of this version.
+///
+/// Mods that did not exist yet at the time of this version cause an error.
+#[inline]
+pub fn parse_mods(acronyms: &str) -> Result<u32, Error> {
+    mods::parse_available(acronyms, mods::ALL)
+}
+
+/// The score multiplier of the mods in this version.
+#[inline]
+pub fn score_multiplier(mods: u32) -> f32 {
+    let multipliers = if mods & SCORE_V2 > 0 {
+        ScoreMultipliers::SCORE_V2
+    } else {
+        ScoreMultipliers::OSU
+    };
+
+    multipliers.multiplier(mods)
+}
+
+/// The hit windows for the given overall difficulty and mods in this version.
+#[inline]
+pub fn hit_windows(od: f32, mods: u32) -> HitWindows {
+    HitWindows::exact(od as f64, mods)
+}
+
+/// The effective overall difficulty after applying mods.
+///
+/// `mod_mult` is `1.4` for HR, `0.5` for EZ, and `1.0` otherwise while `speed_mult`
+/// is the clock rate. Unlike the versions until 2018, the 300 hit window is not rounded.
+#[inline]
+pub fn modify_od(base_od: f64, speed_mult: f64, mod_mult: f64) -> f64 {
+    math::modify_od(base_od, speed_mult, mod_mult)
+}
+
+/// The overall difficulty of a 300 hit window in milliseconds, the inverse of [`modify_od`].
+///
+/// Passing a window that was already scaled by the clock rate results in the effective OD.
+#[inline]
+pub fn hit_window_to_od(hit_window: f64) -> f64 {
+    math::hit_window_to_od(hit_window)
+}
+
+/// The preempt time in milliseconds for the given approach rate and mods in this version,
+/// i.e. how long an object is visible before it has to be hit.
+#[inline]
+pub fn ar_to_preempt(ar: f32, mods: u32) -> f64 {
+    math::ar_to_preempt(ar as f64, mods)
+}
+
+/// The effective approach rate of a preempt time in this version.
+///
+/// The result is not clamped and may be fractional, e.g. AR 9 with DT results in AR 10.33.
+#[inline]
+pub fn preempt_to_ar(preempt: f64) -> f64 {
+    math::preempt_to_ar(preempt)
+}
+
+/// The attributes of the map after applying mods as this version computed them.
+///
+/// Only the map's base values are considered so no difficulty calculation is required.
+pub fn map_attributes(map: &Beatmap, mods: u32) -> MapAttributes {
+    let hit_windows = hit_windows(map.od, mods);
+
+    MapAttributes {
+        ar: preempt_to_ar(ar_to_preempt(map.ar, mods)),
+        od: hit_window_to_od(hit_windows.n300),
+        cs: math::stable_cs(map.cs, mods) as f64,
+        hp: math::stable_hp(map.hp, mods) as f64,
+        clock_rate: mods.clock_rate(),
+        hit_windows,
+    }
+}
+
+/// Calculate the difficulty and the performance of a score in one pass.
+///
+/// Calling [`stars`] and then [`OsuPP`] without passing the attributes along calculates
+/// the difficulty twice, this function avoids that.
+pub fn stars_and_pp(
+    map: &Beatmap,
+    mods: u32,
+    state: OsuScoreState,
+) -> (OsuDifficultyAttributes, OsuPerformanceAttributes) {
+    let performance = OsuPP::new(map).mods(mods).state(state).calculate();
+
+    (performance.difficulty.clone(), performance)
+}
+
 /// Difficulty calculation for osu!standard maps.
 ///
 /// In case of a partial play, e.g. a fail, one can specify the amount of passed objects.
@@ -31,12 +165,224 @@ const STACK_DISTANCE: f32 = 3.0;
 /// If you want to calculate the difficulty after every few objects, instead of
 /// calling this function multiple times with different `passed_objects`, you should use
 /// [`OsuGradualDifficultyAttributes`](crate::osu::OsuGradualDifficultyAttributes).
+///
+/// Maps of other modes can not be calculated and result in default attributes.
 pub fn stars(map: &Beatmap, mods: u32, passed_objects: Option<usize>) -> OsuDifficultyAttributes {
-    let (mut skills, mut attributes) = calculate_skills(map, mods, passed_objects);
+    stars_with_clock_rate(map, mods, passed_objects, mods.clock_rate())
+}
+
+/// Difficulty calculation for osu!standard maps with a custom clock rate.
+///
+/// The clock rate replaces the one of DT or HT, e.g. `1.4` for a lazer DT
+/// score that was set at 1.4x, and applies to both the map attributes and
+/// the strain timing.
+pub fn stars_with_clock_rate(
+    map: &Beatmap,
+    mods: u32,
+    passed_objects: Option<usize>,
+    clock_rate: f64,
+) -> OsuDifficultyAttributes {
+    calculate_stars(
+        map,
+        mods,
+        passed_objects,
+        clock_rate,
+        &DifficultyAdjust::default(),
+        None,
+    )
+}
+
+/// Difficulty calculator on osu!standard maps.
+///
+/// # Example
+///
+/// ```
+/// use rosu_pp::Beatmap;
+/// use rosu_pp_older::osu_2021_november::OsuStars;
+///
+/// # /*
+/// let map: Beatmap = ...
+/// # */
+/// # let map = Beatmap::default();
+///
+/// let difficulty_attrs = OsuStars::new(&map)
+///     .mods(8 + 64) // HDDT
+///     .calculate();
+///
+/// println!("Stars: {}", difficulty_attrs.stars);
+/// ```
+#[derive(Clone, Debug)]
+pub struct OsuStars<'map> {
+    map: &'map Beatmap,
+    mods: u32,
+    passed_objects: Option<usize>,
+    clock_rate: Option<f64>,
+    difficulty_adjust: DifficultyAdjust,
+}
+
+impl<'map> OsuStars<'map> {
+    /// Create a new difficulty calculator for osu!standard maps.
+    #[inline]
+    pub fn new(map: &'map Beatmap) -> Self {
+        Self {
+            map,
+            mods: 0,
+            passed_objects: None,
+            clock_rate: None,
+            difficulty_adjust: DifficultyAdjust::default(),
+        }
+    }
+
+    /// Specify mods through their bit values.
+    ///
+    /// See [https://github.com/ppy/osu-api/wiki#mods](https://github.com/ppy/osu-api/wiki#mods)
+    #[inline]
+    pub fn mods(mut self, mods: u32) -> Self {
+        self.mods = mods;
+
+        self
+    }
+
+    /// Specify mods through their acronyms, e.g. `"HDDT"`.
+    ///
+    /// Returns an error for unknown acronyms and for mods
+    /// that did not exist yet at the time of this version.
+    #[inline]
+    pub fn mods_str(self, acronyms: &str) -> Result<Self, Error> {
+        parse_mods(acronyms).map(|mods| self.mods(mods))
+    }
+
+    /// Amount of passed objects for partial plays, e.g. a fail.
+    #[inline]
+    pub fn passed_objects(mut self, passed_objects: usize) -> Self {
+        self.passed_objects = Some(passed_objects);
+
+        self
+    }
+
+    /// Adjust the clock rate used in the calculation, e.g. `1.4` for a lazer DT
+    /// score that was set at 1.4x.
+    ///
+    /// If none is specified, the clock rate of the mods is used.
+    #[inline]
+    pub fn clock_rate(mut self, clock_rate: f64) -> Self {
+        self.clock_rate = Some(clock_rate);
+
+        self
+    }
+
+    /// Specify the values of lazer's Difficulty Adjust mod.
+    ///
+    /// The values replace the map's base values before HR, EZ, and the clock rate are applied.
+    #[inline]
+    pub fn difficulty_adjust(mut self, difficulty_adjust: DifficultyAdjust) -> Self {
+        self.difficulty_adjust = difficulty_adjust;
+
+        self
+    }
+
+    /// Override the map's approach rate, see [`difficulty_adjust`](Self::difficulty_adjust).
+    #[inline]
+    pub fn ar(mut self, ar: f32) -> Self {
+        self.difficulty_adjust.ar = Some(ar);
+
+        self
+    }
+
+    /// Override the map's circle size, see [`difficulty_adjust`](Self::difficulty_adjust).
+    #[inline]
+    pub fn cs(mut self, cs: f32) -> Self {
+        self.difficulty_adjust.cs = Some(cs);
+
+        self
+    }
+
+    /// Override the map's overall difficulty, see [`difficulty_adjust`](Self::difficulty_adjust).
+    #[inline]
+    pub fn od(mut self, od: f32) -> Self {
+        self.difficulty_adjust.od = Some(od);
+
+        self
+    }
+
+    /// Override the map's drain rate, see [`difficulty_adjust`](Self::difficulty_adjust).
+    #[inline]
+    pub fn hp(mut self, hp: f32) -> Self {
+        self.difficulty_adjust.hp = Some(hp);
+
+        self
+    }
+
+    /// Calculate all difficulty related values, including stars.
+    ///
+    /// Maps of other modes can not be calculated and result in default attributes.
+    #[inline]
+    pub fn calculate(self) -> OsuDifficultyAttributes {
+        let clock_rate = self.clock_rate.unwrap_or_else(|| self.mods.clock_rate());
+
+        calculate_stars(
+            self.map,
+            self.mods,
+            self.passed_objects,
+            clock_rate,
+            &self.difficulty_adjust,
+            None,
+        )
+    }
+}
+
+pub(crate) fn calculate_stars(
+    map: &Beatmap,
+    mods: u32,
+    passed_objects: Option<usize>,
+    clock_rate: f64,
+    difficulty_adjust: &DifficultyAdjust,
+    section_len: Option<SectionLen>,
+) -> OsuDifficultyAttributes {
+    calculate_stars_with_strains(
+        map,
+        mods,
+        passed_objects,
+        clock_rate,
+        difficulty_adjust,
+        section_len,
+    )
+    .0
+}
+
+/// Calculate the difficulty attributes while keeping the section strain peaks.
+pub(crate) fn calculate_stars_with_strains(
+    map: &Beatmap,
+    mods: u32,
+    passed_objects: Option<usize>,
+    clock_rate: f64,
+    difficulty_adjust: &DifficultyAdjust,
+    section_len: Option<SectionLen>,
+) -> (OsuDifficultyAttributes, OsuStrains) {
+    if check_mode(map.mode, MODE).is_err() {
+        return Default::default();
+    }
+
+    let (mut skills, mut attributes) = calculate_skills(
+        map,
+        mods,
+        passed_objects,
+        clock_rate,
+        difficulty_adjust,
+        section_len,
+    );
+
+    let mut strains = OsuStrains {
+        section_len: section_len.map_or(SECTION_LEN * clock_rate, |section_len| {
+            section_len.map_time(clock_rate)
+        }),
+        ..Default::default()
+    };
 
     let aim_rating = {
         let aim = skills.aim();
         let mut aim_strains = mem::take(&mut aim.strain_peaks);
+        strains.aim = aim_strains.clone();
 
         Skill::difficulty_value(&mut aim_strains, aim).sqrt() * DIFFICULTY_MULTIPLIER
     };
@@ -45,6 +391,7 @@ pub fn stars(map: &Beatmap, mods: u32, passed_objects: Option<usize>) -> OsuDiff
         let aim_no_sliders = skills.aim_no_sliders();
 
         let mut aim_strains_no_sliders = mem::take(&mut aim_no_sliders.strain_peaks);
+        strains.aim_no_sliders = aim_strains_no_sliders.clone();
         let aim_rating_no_sliders =
             Skill::difficulty_value(&mut aim_strains_no_sliders, aim_no_sliders).sqrt()
                 * DIFFICULTY_MULTIPLIER;
@@ -58,6 +405,7 @@ pub fn stars(map: &Beatmap, mods: u32, passed_objects: Option<usize>) -> OsuDiff
 
     let speed_rating = if let Some(speed) = speed {
         let mut speed_strains = mem::take(&mut speed.strain_peaks);
+        strains.speed = speed_strains.clone();
 
         Skill::difficulty_value(&mut speed_strains, speed).sqrt() * DIFFICULTY_MULTIPLIER
     } else {
@@ -66,6 +414,7 @@ pub fn stars(map: &Beatmap, mods: u32, passed_objects: Option<usize>) -> OsuDiff
 
     let flashlight_rating = if let Some(flashlight) = flashlight {
         let mut flashlight_strains = mem::take(&mut flashlight.strain_peaks);
+        strains.flashlight = flashlight_strains.clone();
 
         Skill::difficulty_value(&mut flashlight_strains, flashlight).sqrt() * DIFFICULTY_MULTIPLIER
     } else {
@@ -77,10 +426,56 @@ pub fn stars(map: &Beatmap, mods: u32, passed_objects: Option<usize>) -> OsuDiff
     attributes.aim_strain = aim_rating;
     attributes.speed_strain = speed_rating;
     attributes.flashlight_rating = flashlight_rating;
-    attributes.slider_factor = slider_factor;
+    attributes.slider_factor = Some(slider_factor);
     attributes.stars = star_rating;
 
-    attributes
+    (attributes, strains)
+}
+
+/// The maximum combo of an osu!standard map.
+///
+/// Only counts the nested objects of sliders which is considerably cheaper than
+/// calculating the stars, e.g. when validating scores.
+pub fn max_combo(map: &Beatmap) -> usize {
+    let mut curve_bufs = CurveBuffers::default();
+
+    map.hit_objects
+        .iter()
+        .map(|h| object_combo(h, map, &mut curve_bufs))
+        .sum()
+}
+
+/// The section strain peaks of an osu!standard map, e.g. to draw a difficulty graph.
+pub fn strains(map: &Beatmap, mods: u32) -> OsuStrains {
+    let clock_rate = mods.clock_rate();
+    let difficulty_adjust = DifficultyAdjust::default();
+    let (mut skills, _) = calculate_skills(map, mods, None, clock_rate, &difficulty_adjust, None);
+
+    let aim = mem::take(&mut skills.aim().strain_peaks);
+    let aim_no_sliders = mem::take(&mut skills.aim_no_sliders().strain_peaks);
+    let (speed, flashlight) = skills.speed_flashlight();
+
+    OsuStrains {
+        section_len: SECTION_LEN * clock_rate,
+        aim,
+        aim_no_sliders,
+        speed: speed.map_or_else(Vec::new, |skill| mem::take(&mut skill.strain_peaks)),
+        flashlight: flashlight.map_or_else(Vec::new, |skill| mem::take(&mut skill.strain_peaks)),
+    }
+}
+
+/// The radius of hit objects in osu!pixels after applying HR or EZ.
+#[inline]
+pub fn object_radius(cs: f32, mods: u32) -> f32 {
+    ScalingFactor::new(stable_cs(cs, mods) as f64).radius()
+}
+
+/// The factor by which jump distances are scaled to normalize the object radius.
+///
+/// Objects with a radius below 30 get an additional bonus of up to 10%.
+#[inline]
+pub fn scaling_factor(cs: f32, mods: u32) -> f32 {
+    ScalingFactor::new(stable_cs(cs, mods) as f64).adjusted()
 }
 
 fn calculate_star_rating(aim_rating: f64, speed_rating: f64, flashlight_rating: f64) -> f64 {
@@ -116,21 +511,29 @@ fn calculate_skills(
     map: &Beatmap,
     mods: u32,
     passed_objects: Option<usize>,
+    clock_rate: f64,
+    difficulty_adjust: &DifficultyAdjust,
+    section_len: Option<SectionLen>,
 ) -> (Skills, OsuDifficultyAttributes) {
     let take = passed_objects.unwrap_or(map.hit_objects.len());
 
-    let map_attrs = map.attributes().mods(mods).build();
+    let map_attrs = difficulty_adjust
+        .apply(map.attributes().mods(mods).clock_rate(clock_rate))
+        .build();
     let hit_window = map_attrs.hit_windows.od;
 
     let hr = mods.hr();
 
-    let time_preempt = (map_attrs.hit_windows.ar * mods.clock_rate()) as f32 as f64;
-    let scaling_factor = ScalingFactor::new(map_attrs.cs);
+    let time_preempt = (map_attrs.hit_windows.ar * clock_rate) as f32 as f64;
+    let cs = stable_cs(difficulty_adjust.cs.unwrap_or(map.cs), mods);
+    let scaling_factor = ScalingFactor::new(cs as f64);
 
     let mut attributes = OsuDifficultyAttributes {
         ar: map_attrs.ar,
         hp: map_attrs.hp,
         od: map_attrs.od,
+        base_ar: difficulty_adjust.ar.unwrap_or(map.ar) as f64,
+        base_od: difficulty_adjust.od.unwrap_or(map.od) as f64,
         ..Default::default()
     };
 
@@ -173,9 +576,14 @@ fn calculate_skills(
 
     let mut prev_prev = None;
 
+    let section_len = match section_len {
+        Some(section_len) => section_len.map_time(clock_rate) / map_attrs.clock_rate,
+        None => SECTION_LEN,
+    };
+
     // First object has no predecessor and thus no strain, handle distinctly
     let mut curr_section_end =
-        (prev.time / map_attrs.clock_rate / SECTION_LEN).ceil() * SECTION_LEN;
+        (prev.time / map_attrs.clock_rate / section_len).ceil() * section_len;
 
     // Handle second object separately to remove later if-branching
     let h = DifficultyObject::new(
@@ -190,7 +598,7 @@ fn calculate_skills(
 
     while base_time > curr_section_end {
         skills.start_new_section_from(curr_section_end);
-        curr_section_end += SECTION_LEN;
+        curr_section_end += section_len;
     }
 
     skills.process(&h);
@@ -210,7 +618,7 @@ fn calculate_skills(
 
         while base_time > curr_section_end {
             skills.save_peak_and_start_new_section(curr_section_end);
-            curr_section_end += SECTION_LEN;
+            curr_section_end += section_len;
         }
 
         skills.process(&h);
@@ -361,86 +769,6 @@ fn old_stacking(hit_objects: &mut [OsuObject], stack_threshold: f64) {
     }
 }
 
-/// The result of a difficulty calculation on an osu!standard map.
-#[derive(Clone, Debug, Default, PartialEq)]
-pub struct OsuDifficultyAttributes {
-    /// The aim portion of the total strain.
-    pub aim_strain: f64,
-    /// The speed portion of the total strain.
-    pub speed_strain: f64,
-    /// The flashlight portion of the total strain.
-    pub flashlight_rating: f64,
-    /// The ratio of the aim strain with and without considering sliders
-    pub slider_factor: f64,
-    /// The approach rate.
-    pub ar: f64,
-    /// The overall difficulty
-    pub od: f64,
-    /// The health drain rate.
-    pub hp: f64,
-    /// The amount of circles.
-    pub n_circles: usize,
-    /// The amount of sliders.
-    pub n_sliders: usize,
-    /// The amount of spinners.
-    pub n_spinners: usize,
-    /// The final star rating
-    pub stars: f64,
-    /// The maximum combo.
-    pub max_combo: usize,
-}
-
-impl OsuDifficultyAttributes {
-    /// Return the maximum combo.
-    #[inline]
-    pub fn max_combo(&self) -> usize {
-        self.max_combo
-    }
-}
-
-/// The result of a performance calculation on an osu!standard map.
-#[derive(Clone, Debug, Default, PartialEq)]
-pub struct OsuPerformanceAttributes {
-    /// The difficulty attributes that were used for the performance calculation
-    pub difficulty: OsuDifficultyAttributes,
-    /// The final performance points.
-    pub pp: f64,
-    /// The accuracy portion of the final pp.
-    pub pp_acc: f64,
-    /// The aim portion of the final pp.
-    pub pp_aim: f64,
-    /// The flashlight portion of the final pp.
-    pub pp_flashlight: f64,
-    /// The speed portion of the final pp.
-    pub pp_speed: f64,
-}
-
-impl OsuPerformanceAttributes {
-    /// Return the star value.
-    #[inline]
-    pub fn stars(&self) -> f64 {
-        self.difficulty.stars
-    }
-
-    /// Return the performance point value.
-    #[inline]
-    pub fn pp(&self) -> f64 {
-        self.pp
-    }
-
-    /// Return the maximum combo of the map.
-    #[inline]
-    pub fn max_combo(&self) -> usize {
-        self.difficulty.max_combo
-    }
-}
-
-impl From<OsuPerformanceAttributes> for OsuDifficultyAttributes {
-    fn from(attributes: OsuPerformanceAttributes) -> Self {
-        attributes.difficulty
-    }
-}
-
 fn _difficulty_range(val: f64, max: f64, avg: f64, min: f64) -> f64 {
     if val > 5.0 {
         avg + (max - avg) * (val - 5.0) / 5.0
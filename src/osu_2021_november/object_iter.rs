@@ -0,0 +1,149 @@
+use std::{mem, vec::IntoIter};
+
+use rosu_pp::{Beatmap, Mods};
+
+use crate::util::{curve::CurveBuffers, math::stable_cs, mode::check_mode};
+
+use super::{
+    old_stacking,
+    osu_object::{ObjectParameters, OsuObject},
+    stacking, DifficultyObject, OsuDifficultyAttributes, ScalingFactor, MODE,
+};
+
+/// The geometry and timing of a hit object in relation to its predecessors,
+/// as processed by this version before evaluating any skill.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct OsuDifficultyObject {
+    /// The start time of the object in milliseconds, divided by the clock rate.
+    pub start_time: f64,
+    /// The time since the previous object in milliseconds, divided by the clock rate.
+    pub delta: f64,
+    /// The delta time capped at a minimum of 25.
+    pub strain_time: f64,
+    /// The distance from the lazy cursor position at the end of the previous object
+    /// in normalized osu!pixels.
+    pub jump_dist: f64,
+    /// The distance the lazy cursor travelled along the previous object if it is a slider
+    /// in normalized osu!pixels.
+    pub travel_dist: f64,
+    /// The time it took to travel along the previous object in milliseconds.
+    pub travel_time: f64,
+    /// The shortest distance from the previous object, either its end or its head
+    /// for sliders, in normalized osu!pixels.
+    pub movement_dist: f64,
+    /// The time it took to cover the movement distance in milliseconds.
+    pub movement_time: f64,
+    /// The angle in radians between the previous two jumps, `None` for the first two objects
+    /// or if one of them is a spinner.
+    pub angle: Option<f64>,
+}
+
+/// Iterator over the [`OsuDifficultyObject`]s of a map, created by [`difficulty_objects`].
+///
+/// The first object has no predecessor and is thus skipped.
+pub struct OsuDifficultyObjects {
+    objects: IntoIter<OsuObject>,
+    prev: Option<OsuObject>,
+    prev_prev: Option<OsuObject>,
+    clock_rate: f64,
+    scaling_factor: ScalingFactor,
+}
+
+/// Process the hit objects of a map the way this version does for its difficulty
+/// calculation without evaluating any skill, e.g. to analyze patterns with custom metrics.
+///
+/// The positions and slider paths are evaluated upfront while the
+/// [`OsuDifficultyObject`]s are created lazily.
+///
+/// Maps of other modes can not be calculated and result in no objects.
+pub fn difficulty_objects(map: &Beatmap, mods: u32) -> OsuDifficultyObjects {
+    let map_attrs = map.attributes().mods(mods).build();
+    let time_preempt = (map_attrs.hit_windows.ar * map_attrs.clock_rate) as f32 as f64;
+    let scaling_factor = ScalingFactor::new(stable_cs(map.cs, mods) as f64);
+
+    let mut attributes = OsuDifficultyAttributes::default();
+
+    let mut params = ObjectParameters {
+        map,
+        attributes: &mut attributes,
+        ticks: Vec::new(),
+        curve_bufs: CurveBuffers::default(),
+    };
+
+    // Maps of other modes can not be calculated
+    let take = if check_mode(map.mode, MODE).is_ok() {
+        map.hit_objects.len()
+    } else {
+        0
+    };
+
+    let mut hit_objects: Vec<_> = map
+        .hit_objects
+        .iter()
+        .take(take)
+        .map(|h| OsuObject::new(h, mods.hr(), &mut params))
+        .collect();
+
+    let stack_threshold = time_preempt * map.stack_leniency as f64;
+
+    if map.version >= 6 {
+        stacking(&mut hit_objects, stack_threshold);
+    } else {
+        old_stacking(&mut hit_objects, stack_threshold);
+    }
+
+    for h in hit_objects.iter_mut() {
+        h.pos += scaling_factor.stack_offset(h.stack_height);
+    }
+
+    let mut objects = hit_objects.into_iter();
+
+    OsuDifficultyObjects {
+        prev: objects.next(),
+        prev_prev: None,
+        objects,
+        clock_rate: map_attrs.clock_rate,
+        scaling_factor,
+    }
+}
+
+impl Iterator for OsuDifficultyObjects {
+    type Item = OsuDifficultyObject;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let curr = self.objects.next()?;
+        let prev = self.prev.as_mut()?;
+
+        let h = DifficultyObject::new(
+            &curr,
+            prev,
+            self.prev_prev.as_ref(),
+            &self.scaling_factor,
+            self.clock_rate,
+        );
+
+        let object = OsuDifficultyObject {
+            start_time: h.base.time / self.clock_rate,
+            delta: h.delta,
+            strain_time: h.strain_time,
+            jump_dist: h.jump_dist,
+            travel_dist: h.travel_dist,
+            travel_time: h.travel_time,
+            movement_dist: h.movement_dist,
+            movement_time: h.movement_time,
+            angle: h.angle,
+        };
+
+        self.prev_prev = mem::replace(&mut self.prev, Some(curr));
+
+        Some(object)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.objects.size_hint()
+    }
+}
+
+impl ExactSizeIterator for OsuDifficultyObjects {}
@@ -13,15 +13,15 @@ const LEGACY_LAST_TICK_OFFSET: f64 = 36.0;
 const BASE_SCORING_DISTANCE: f64 = 100.0;
 
 #[derive(Clone, Debug)]
-pub(crate) struct OsuObject {
-    pub(crate) time: f64,
-    pub(crate) pos: Pos2,
-    pub(crate) stack_height: f32,
-    pub(crate) kind: OsuObjectKind,
+pub struct OsuObject {
+    pub time: f64,
+    pub pos: Pos2,
+    pub stack_height: f32,
+    pub kind: OsuObjectKind,
 }
 
 #[derive(Clone, Debug)]
-pub(crate) enum OsuObjectKind {
+pub enum OsuObjectKind {
     Circle,
     Slider {
         end_time: f64,
@@ -35,14 +35,14 @@ pub(crate) enum OsuObjectKind {
 }
 
 #[derive(Clone, Debug)]
-pub(crate) struct NestedObject {
-    pub(crate) pos: Pos2,
-    pub(crate) time: f64,
-    pub(crate) kind: NestedObjectKind,
+pub struct NestedObject {
+    pub pos: Pos2,
+    pub time: f64,
+    pub kind: NestedObjectKind,
 }
 
 #[derive(Copy, Clone, Debug)]
-pub(crate) enum NestedObjectKind {
+pub enum NestedObjectKind {
     Repeat,
     Tail,
     Tick,
@@ -283,6 +283,7 @@ impl OsuObject {
                     },
                 }
             }
+            // Holds only appear in osu!mania maps which are never calculated
             HitObjectKind::Spinner { end_time } | HitObjectKind::Hold { end_time } => {
                 attrs.n_spinners += 1;
 
@@ -299,7 +300,7 @@ impl OsuObject {
     }
 
     #[inline]
-    pub(crate) fn end_time(&self) -> f64 {
+    pub fn end_time(&self) -> f64 {
         match &self.kind {
             OsuObjectKind::Circle => self.time,
             OsuObjectKind::Slider { end_time, .. } => *end_time,
@@ -308,7 +309,7 @@ impl OsuObject {
     }
 
     #[inline]
-    pub(crate) fn end_pos(&self) -> Pos2 {
+    pub fn end_pos(&self) -> Pos2 {
         match &self.kind {
             OsuObjectKind::Circle | OsuObjectKind::Spinner { .. } => self.pos,
             OsuObjectKind::Slider { end_pos, .. } => *end_pos,
@@ -316,7 +317,7 @@ impl OsuObject {
     }
 
     #[inline]
-    pub(crate) fn lazy_end_pos(&self, stack_offset: Pos2) -> Pos2 {
+    pub fn lazy_end_pos(&self, stack_offset: Pos2) -> Pos2 {
         match &self.kind {
             OsuObjectKind::Circle | OsuObjectKind::Spinner { .. } => self.pos,
             OsuObjectKind::Slider { lazy_end_pos, .. } => *lazy_end_pos + stack_offset,
@@ -324,17 +325,62 @@ impl OsuObject {
     }
 
     #[inline]
-    pub(crate) fn is_circle(&self) -> bool {
+    pub fn is_circle(&self) -> bool {
         matches!(self.kind, OsuObjectKind::Circle)
     }
 
     #[inline]
-    pub(crate) fn is_slider(&self) -> bool {
+    pub fn is_slider(&self) -> bool {
         matches!(self.kind, OsuObjectKind::Slider { .. })
     }
 
     #[inline]
-    pub(crate) fn is_spinner(&self) -> bool {
+    pub fn is_spinner(&self) -> bool {
         matches!(self.kind, OsuObjectKind::Spinner { .. })
     }
 }
+
+/// The combo of a hit object, i.e. `1` for circles and spinners and the head, ticks,
+/// repeats, and tail for sliders.
+///
+/// Counts the same nested objects as [`OsuObject::new`] without computing their positions.
+pub(crate) fn object_combo(h: &HitObject, map: &Beatmap, curve_bufs: &mut CurveBuffers) -> usize {
+    let (pixel_len, repeats, control_points) = match &h.kind {
+        HitObjectKind::Slider {
+            pixel_len,
+            repeats,
+            control_points,
+            ..
+        } => (*pixel_len, *repeats, control_points),
+        _ => return 1,
+    };
+
+    let timing_point = map.timing_point_at(h.start_time);
+    let difficulty_point = map.difficulty_point_at(h.start_time).unwrap_or_default();
+
+    let mut tick_dist = 100.0 * map.slider_mult / map.tick_rate;
+
+    if map.version >= 8 {
+        tick_dist /= (100.0 / difficulty_point.slider_vel).clamp(10.0, 1000.0) / 100.0;
+    }
+
+    let curve = Curve::new(control_points, pixel_len, curve_bufs);
+
+    let velocity = (BASE_SCORING_DISTANCE * map.slider_mult * difficulty_point.slider_vel)
+        / timing_point.beat_len;
+
+    let len = curve.dist().min(100_000.0);
+    tick_dist = tick_dist.clamp(0.0, len);
+    let min_dist_from_end = velocity * 10.0;
+
+    let mut curr_dist = tick_dist;
+    let mut n_ticks = 0;
+
+    while curr_dist < len - min_dist_from_end {
+        n_ticks += 1;
+        curr_dist += tick_dist;
+    }
+
+    // Head, ticks of each span, repeat points, and the legacy last tick
+    2 + n_ticks + repeats * (1 + n_ticks)
+}
@@ -1,6 +1,14 @@
-use rosu_pp::{osu::OsuScoreState, Beatmap, Mods};
+use rosu_pp::{Beatmap, Mods};
 
-use super::{OsuDifficultyAttributes, OsuPerformanceAttributes};
+use crate::{
+    mods::DifficultyAdjust,
+    section::SectionLen,
+    unranked::{AutopilotMode, PpComponents, RelaxMode, UnrankedConfig},
+    util::{cache::DifficultyCache, hitresults, mode::check_mode, mods::SCORE_V2},
+    Error, IfFcAttributes, ScoreState,
+};
+
+use super::{OsuDifficultyAttributes, OsuPerformanceAttributes, OsuStrains, MODE};
 
 /// Performance calculator on osu!standard maps.
 ///
@@ -36,6 +44,7 @@ use super::{OsuDifficultyAttributes, OsuPerformanceAttributes};
 pub struct OsuPP<'map> {
     map: &'map Beatmap,
     attributes: Option<OsuDifficultyAttributes>,
+    cache: DifficultyCache<OsuDifficultyAttributes>,
     mods: u32,
     acc: Option<f64>,
     pub(crate) combo: Option<usize>,
@@ -44,7 +53,13 @@ pub struct OsuPP<'map> {
     pub(crate) n100: Option<usize>,
     pub(crate) n50: Option<usize>,
     pub(crate) n_misses: usize,
+    estimate_hitresults: bool,
     pub(crate) passed_objects: Option<usize>,
+    clock_rate: Option<f64>,
+    difficulty_adjust: DifficultyAdjust,
+    section_len: Option<SectionLen>,
+
+    unranked: UnrankedConfig,
 }
 
 impl<'map> OsuPP<'map> {
@@ -54,6 +69,7 @@ impl<'map> OsuPP<'map> {
         Self {
             map,
             attributes: None,
+            cache: DifficultyCache::default(),
             mods: 0,
             acc: None,
             combo: None,
@@ -62,7 +78,13 @@ impl<'map> OsuPP<'map> {
             n100: None,
             n50: None,
             n_misses: 0,
+            estimate_hitresults: false,
             passed_objects: None,
+            clock_rate: None,
+            difficulty_adjust: DifficultyAdjust::default(),
+            section_len: None,
+
+            unranked: UnrankedConfig::default(),
         }
     }
 
@@ -84,10 +106,20 @@ impl<'map> OsuPP<'map> {
     #[inline]
     pub fn mods(mut self, mods: u32) -> Self {
         self.mods = mods;
+        self.cache.reset();
 
         self
     }
 
+    /// Specify mods through their acronyms, e.g. `"HDDT"`.
+    ///
+    /// Returns an error for unknown acronyms and for mods
+    /// that did not exist yet at the time of this version.
+    #[inline]
+    pub fn mods_str(self, acronyms: &str) -> Result<Self, Error> {
+        super::parse_mods(acronyms).map(|mods| self.mods(mods))
+    }
+
     /// Specify the max combo of the play.
     #[inline]
     pub fn combo(mut self, combo: usize) -> Self {
@@ -136,20 +168,127 @@ impl<'map> OsuPP<'map> {
     #[inline]
     pub fn passed_objects(mut self, passed_objects: usize) -> Self {
         self.passed_objects.replace(passed_objects);
+        self.cache.reset();
+
+        self
+    }
+
+    /// Specify a custom clock rate, e.g. `1.4` for a lazer DT score that was set at 1.4x.
+    ///
+    /// The clock rate replaces the one of DT or HT.
+    #[inline]
+    pub fn clock_rate(mut self, clock_rate: f64) -> Self {
+        self.clock_rate = Some(clock_rate);
+        self.cache.reset();
+
+        self
+    }
+
+    /// Specify the values of lazer's Difficulty Adjust mod.
+    ///
+    /// The values replace the map's base values before HR, EZ, and the clock rate are applied.
+    #[inline]
+    pub fn difficulty_adjust(mut self, difficulty_adjust: DifficultyAdjust) -> Self {
+        self.difficulty_adjust = difficulty_adjust;
+        self.cache.reset();
+
+        self
+    }
+
+    /// Override the map's approach rate, see [`difficulty_adjust`](Self::difficulty_adjust).
+    #[inline]
+    pub fn ar(mut self, ar: f32) -> Self {
+        self.difficulty_adjust.ar = Some(ar);
+        self.cache.reset();
+
+        self
+    }
+
+    /// Override the map's circle size, see [`difficulty_adjust`](Self::difficulty_adjust).
+    #[inline]
+    pub fn cs(mut self, cs: f32) -> Self {
+        self.difficulty_adjust.cs = Some(cs);
+        self.cache.reset();
+
+        self
+    }
+
+    /// Override the map's overall difficulty, see [`difficulty_adjust`](Self::difficulty_adjust).
+    #[inline]
+    pub fn od(mut self, od: f32) -> Self {
+        self.difficulty_adjust.od = Some(od);
+        self.cache.reset();
 
         self
     }
 
-    /// Provide parameters through an [`OsuScoreState`].
+    /// Override the map's drain rate, see [`difficulty_adjust`](Self::difficulty_adjust).
     #[inline]
-    pub fn state(mut self, state: OsuScoreState) -> Self {
-        let OsuScoreState {
+    pub fn hp(mut self, hp: f32) -> Self {
+        self.difficulty_adjust.hp = Some(hp);
+        self.cache.reset();
+
+        self
+    }
+
+    /// Override the length of the sections in which strain peaks are collected.
+    ///
+    /// Only meant for research, see [`SectionLen`].
+    #[cfg(feature = "experimental")]
+    #[inline]
+    pub fn section_len(mut self, section_len: SectionLen) -> Self {
+        self.section_len.replace(section_len);
+        self.cache.reset();
+
+        self
+    }
+
+    /// Specify how the Relax mod should be handled.
+    ///
+    /// By default, Relax plays are penalized the same way as in osu!lazer at the time.
+    /// Specifying a [`RelaxMode`] replaces that penalty.
+    /// Unless a [`RelaxMode`] is specified or unranked mods are allowed,
+    /// [`try_calculate`](Self::try_calculate) rejects Relax plays.
+    #[inline]
+    pub fn relax(mut self, relax: RelaxMode) -> Self {
+        self.unranked.relax = Some(relax);
+
+        self
+    }
+
+    /// Specify whether [`try_calculate`](Self::try_calculate) should accept mods
+    /// that bancho never awarded pp for, i.e. AT, CN, TP, and unconfigured RX.
+    ///
+    /// Useful for private servers, defaults to `false`.
+    #[inline]
+    pub fn allow_unranked(mut self, allow_unranked: bool) -> Self {
+        self.unranked.allow_unranked = allow_unranked;
+
+        self
+    }
+
+    /// Specify how the Autopilot mod should be handled.
+    ///
+    /// By default, Autopilot plays are rejected by [`try_calculate`](Self::try_calculate)
+    /// and are worth 0 pp with [`calculate`](Self::calculate).
+    #[inline]
+    pub fn autopilot(mut self, autopilot: AutopilotMode) -> Self {
+        self.unranked.autopilot = autopilot;
+
+        self
+    }
+
+    /// Provide parameters through a [`ScoreState`].
+    #[inline]
+    pub fn state(mut self, state: impl Into<ScoreState>) -> Self {
+        let ScoreState {
             max_combo,
             n300,
             n100,
             n50,
             n_misses,
-        } = state;
+            ..
+        } = state.into();
 
         self.combo = Some(max_combo);
         self.n300 = Some(n300);
@@ -174,7 +313,10 @@ impl<'map> OsuPP<'map> {
             let mut n50 = self.n50.unwrap_or(0);
 
             let placed_points = 2 * n100 + n50 + self.n_misses;
-            let missing_objects = n_objects - n100 - n50 - self.n_misses;
+            let missing_objects = n_objects
+                .saturating_sub(n100)
+                .saturating_sub(n50)
+                .saturating_sub(self.n_misses);
             let missing_points =
                 ((6.0 * acc * n_objects as f64).round() as usize).saturating_sub(placed_points);
 
@@ -198,12 +340,16 @@ impl<'map> OsuPP<'map> {
             acc = (6 * n300 + 2 * n100 + n50) as f64 / (6 * n_objects) as f64;
         } else {
             let misses = self.n_misses.min(n_objects);
-            let target_total = (acc * n_objects as f64 * 6.0).round() as usize;
-            let delta = target_total - (n_objects - misses);
+            let remaining = n_objects - misses;
+
+            // Between all 50s and all 300s for the objects that were not missed
+            let target_total =
+                ((acc * n_objects as f64 * 6.0).round() as usize).clamp(remaining, 6 * remaining);
+            let delta = target_total - remaining;
 
             let mut n300 = delta / 5;
-            let mut n100 = (delta % 5).min(n_objects - n300 - misses);
-            let mut n50 = n_objects - n300 - n100 - misses;
+            let mut n100 = (delta % 5).min(remaining.saturating_sub(n300));
+            let mut n50 = remaining.saturating_sub(n300).saturating_sub(n100);
 
             // Sacrifice n300s to transform n50s into n100s
             let n = n300.min(n50 / 4);
@@ -223,7 +369,35 @@ impl<'map> OsuPP<'map> {
         self
     }
 
-    fn assert_hitresults(self, attributes: OsuDifficultyAttributes) -> OsuPPInner {
+    /// Estimate the hitresults from the [`combo`](Self::combo) and the [`misses`](Self::misses)
+    /// alone, e.g. for scores of old private servers that lack their 100s and 50s.
+    ///
+    /// The map is assumed to break as rarely as the combo allows. Each break that the
+    /// misses do not account for is counted as a 100, e.g. a dropped slider end, and
+    /// all other hits are 300s. Previously specified hitresults and accuracy are ignored.
+    #[inline]
+    pub fn estimate_hitresults(mut self) -> Self {
+        self.estimate_hitresults = true;
+
+        self
+    }
+
+    fn assert_hitresults(mut self, attributes: OsuDifficultyAttributes) -> OsuPPInner {
+        if self.estimate_hitresults {
+            let n_objects = self.passed_objects.unwrap_or(self.map.hit_objects.len());
+            let (n300, n100, n50) = hitresults::estimate_osu(
+                n_objects,
+                attributes.max_combo,
+                self.combo,
+                self.n_misses,
+            );
+
+            self.n300 = Some(n300);
+            self.n100 = Some(n100);
+            self.n50 = Some(n50);
+            self.acc = None;
+        }
+
         let mut n300 = self.n300;
         let mut n100 = self.n100;
         let mut n50 = self.n50;
@@ -250,6 +424,7 @@ impl<'map> OsuPP<'map> {
                 n50,
                 total_hits,
                 effective_misses,
+                unranked: self.unranked,
             }
         } else {
             let n_objects = self.passed_objects.unwrap_or(self.map.hit_objects.len());
@@ -301,18 +476,133 @@ impl<'map> OsuPP<'map> {
                 n50,
                 total_hits,
                 effective_misses,
+                unranked: self.unranked,
             }
         }
     }
 
+    /// Same as [`calculate`](Self::calculate) but returns an error if the map can
+    /// not be calculated by this version or if the mods contain unranked mods that
+    /// are not configured to be handled.
+    pub fn try_calculate(self) -> Result<OsuPerformanceAttributes, Error> {
+        check_mode(self.map.mode, MODE)?;
+        self.unranked.check(self.mods)?;
+
+        Ok(self.calculate())
+    }
+
+    /// Calculate the performance of the score and of its full combo.
+    ///
+    /// For the full combo, the misses are counted as 300s and the combo is the map's
+    /// max combo. Hit results that were generated through [`accuracy`](Self::accuracy)
+    /// are generated again for the same accuracy without misses instead.
+    pub fn calculate_if_fc(self) -> IfFcAttributes<OsuPerformanceAttributes> {
+        let fc = self.if_fc();
+
+        IfFcAttributes {
+            score: self.calculate(),
+            fc: fc.calculate(),
+        }
+    }
+
+    /// Same as [`calculate_if_fc`](Self::calculate_if_fc) but returns an error if the map can
+    /// not be calculated by this version or if the mods contain unranked mods that
+    /// are not configured to be handled.
+    pub fn try_calculate_if_fc(self) -> Result<IfFcAttributes<OsuPerformanceAttributes>, Error> {
+        check_mode(self.map.mode, MODE)?;
+        self.unranked.check(self.mods)?;
+
+        Ok(self.calculate_if_fc())
+    }
+
+    fn if_fc(&self) -> Self {
+        let mut fc = self.clone();
+        fc.combo = None;
+        fc.n_misses = 0;
+
+        if let Some(acc) = self.acc {
+            fc.n300 = None;
+            fc.n100 = None;
+            fc.n50 = None;
+
+            return fc.accuracy(100.0 * acc);
+        }
+
+        if let Some(ref mut n300) = fc.n300 {
+            *n300 += self.n_misses;
+        }
+
+        fc
+    }
+
+    /// Calculate the performance attributes together with the strain peaks of each section,
+    /// e.g. to draw a difficulty graph next to the pp.
+    ///
+    /// Difficulty attributes passed via [`attributes`](Self::attributes) are ignored since
+    /// the strains are only available while calculating the difficulty.
+    pub fn calculate_with_strains(mut self) -> (OsuPerformanceAttributes, OsuStrains) {
+        let clock_rate = self.clock_rate.unwrap_or_else(|| self.mods.clock_rate());
+
+        let (attributes, strains) = super::calculate_stars_with_strains(
+            self.map,
+            self.mods,
+            self.passed_objects,
+            clock_rate,
+            &self.difficulty_adjust,
+            self.section_len,
+        );
+
+        self.cache.insert(attributes.clone());
+        self.attributes.replace(attributes);
+
+        (self.calculate(), strains)
+    }
+
     /// Calculate all performance related values, including pp and stars.
+    ///
+    /// Maps of other modes can not be calculated and result in default attributes,
+    /// [`try_calculate`](Self::try_calculate) returns an error for them instead.
     pub fn calculate(mut self) -> OsuPerformanceAttributes {
-        let attributes = self
-            .attributes
-            .take()
-            .unwrap_or_else(|| super::stars(self.map, self.mods, self.passed_objects));
+        if check_mode(self.map.mode, MODE).is_err() {
+            return OsuPerformanceAttributes::default();
+        }
+
+        let attributes = self.attributes.take().unwrap_or_else(|| {
+            self.cache.get_or_insert_with(|| {
+                let clock_rate = self.clock_rate.unwrap_or_else(|| self.mods.clock_rate());
+
+                super::calculate_stars(
+                    self.map,
+                    self.mods,
+                    self.passed_objects,
+                    clock_rate,
+                    &self.difficulty_adjust,
+                    self.section_len,
+                )
+            })
+        });
 
-        self.assert_hitresults(attributes).calculate()
+        let ss = self.ss().assert_hitresults(attributes.clone());
+        let max_pp = ss.calculate().pp;
+
+        let mut performance = self.assert_hitresults(attributes).calculate();
+        performance.max_pp = max_pp;
+
+        performance
+    }
+
+    /// The same calculator for an SS, i.e. full combo without 100s, 50s, or misses.
+    fn ss(&self) -> Self {
+        Self {
+            combo: None,
+            acc: None,
+            n300: None,
+            n100: None,
+            n50: None,
+            n_misses: 0,
+            estimate_hitresults: false,
+            ..self.clone()
+        }
     }
 }
 
@@ -328,6 +618,8 @@ struct OsuPPInner {
 
     total_hits: f64,
     effective_misses: usize,
+
+    unranked: UnrankedConfig,
 }
 
 impl OsuPPInner {
@@ -335,9 +627,13 @@ impl OsuPPInner {
         let mut multiplier = 1.12;
 
         // NF penalty
-        if self.mods.nf() {
-            multiplier *= (1.0 - 0.02 * (self.effective_misses as f64)).max(0.9);
-        }
+        let nf_multiplier = if self.mods.nf() {
+            (1.0 - 0.02 * (self.effective_misses as f64)).max(0.9)
+        } else {
+            1.0
+        };
+
+        multiplier *= nf_multiplier;
 
         // SO penalty
         if self.mods.so() {
@@ -346,7 +642,7 @@ impl OsuPPInner {
         }
 
         // Relax penalty
-        if self.mods.rx() {
+        if self.mods.rx() && self.unranked.relax.is_none() {
             // * As we're adding 100s and 50s to an approximated number of combo breaks\
             // * the result can be higher than total hits in specific scenarios
             // * (which breaks some calculations) so we need to clamp it.
@@ -356,10 +652,27 @@ impl OsuPPInner {
             multiplier *= 0.6;
         }
 
-        let aim_value = self.compute_aim_value();
-        let speed_value = self.compute_speed_value();
-        let acc_value = self.compute_accuracy_value();
-        let flashlight_value = self.compute_flashlight_value();
+        let mut aim_value = self.compute_aim_value();
+        let mut speed_value = self.compute_speed_value();
+        let mut acc_value = self.compute_accuracy_value();
+        let mut flashlight_value = self.compute_flashlight_value();
+
+        // Unranked mods
+        if self.unranked.applies_to(self.mods) {
+            let mut components = PpComponents {
+                aim: aim_value,
+                speed: speed_value,
+                acc: acc_value,
+                flashlight: flashlight_value,
+            };
+
+            self.unranked.adjust(self.mods, &mut components);
+
+            aim_value = components.aim;
+            speed_value = components.speed;
+            acc_value = components.acc;
+            flashlight_value = components.flashlight;
+        }
 
         let pp = (aim_value.powf(1.1)
             + speed_value.powf(1.1)
@@ -375,6 +688,9 @@ impl OsuPPInner {
             pp_flashlight: flashlight_value,
             pp_speed: speed_value,
             pp,
+            max_pp: 0.0,
+            nf_multiplier,
+            legacy: None,
         }
     }
 
@@ -438,8 +754,8 @@ impl OsuPPInner {
                 .clamp(0.0, estimate_difficult_sliders);
 
             let base = 1.0 - estimate_slider_ends_dropped / estimate_difficult_sliders;
-            let slider_nerf_factor =
-                (1.0 - attributes.slider_factor) * base * base * base + attributes.slider_factor;
+            let slider_factor = attributes.slider_factor.unwrap_or(1.0);
+            let slider_nerf_factor = (1.0 - slider_factor) * base * base * base + slider_factor;
 
             aim_value *= slider_nerf_factor;
         }
@@ -505,25 +821,32 @@ impl OsuPPInner {
     }
 
     fn compute_accuracy_value(&self) -> f64 {
-        if self.mods.rx() {
+        if self.mods.rx() && self.unranked.relax.is_none() {
             return 0.0;
         }
 
         let attributes = &self.attributes;
         let total_hits = self.total_hits;
-        let n_circles = attributes.n_circles as f64;
         let n300 = self.n300 as f64;
         let n100 = self.n100 as f64;
         let n50 = self.n50 as f64;
 
-        let better_acc_percentage = (n_circles > 0.0) as u8 as f64
-            * (((n300 - (total_hits - n_circles)) * 6.0 + n100 * 2.0 + n50) / (n_circles * 6.0))
+        let mut n_acc_objects = attributes.n_circles as f64;
+
+        // ScoreV2 also considers slider heads for accuracy
+        if self.mods & SCORE_V2 > 0 {
+            n_acc_objects += attributes.n_sliders as f64;
+        }
+
+        let better_acc_percentage = (n_acc_objects > 0.0) as u8 as f64
+            * (((n300 - (total_hits - n_acc_objects)) * 6.0 + n100 * 2.0 + n50)
+                / (n_acc_objects * 6.0))
                 .max(0.0);
 
         let mut acc_value = 1.52163_f64.powf(attributes.od) * better_acc_percentage.powi(24) * 2.83;
 
         // Bonus for many hitcircles
-        acc_value *= ((n_circles / 1000.0).powf(0.3)).min(1.15);
+        acc_value *= ((n_acc_objects / 1000.0).powf(0.3)).min(1.15);
 
         // HD bonus (this would include the Blinds mod but it's currently not representable)
         if self.mods.hd() {
@@ -1,4 +1,4 @@
-use crate::util::math::lerp;
+use crate::util::{math::lerp, sum::StrainSum};
 
 use super::{skill_kind::calculate_speed_rhythm_bonus, DifficultyObject, SkillKind};
 
@@ -90,29 +90,29 @@ impl Skills {
 }
 
 #[derive(Clone)]
-pub(crate) struct Skill {
+pub struct Skill {
     curr_strain: f64,
-    pub(crate) curr_section_peak: f64,
+    pub curr_section_peak: f64,
 
     kind: SkillKind,
-    pub(crate) strain_peaks: Vec<f64>,
+    pub strain_peaks: Vec<f64>,
 
     prev_time: Option<f64>,
 }
 
 impl Skill {
     #[inline]
-    pub(crate) fn aim(with_sliders: bool) -> Self {
+    pub fn aim(with_sliders: bool) -> Self {
         Self::new(SkillKind::aim(with_sliders))
     }
 
     #[inline]
-    pub(crate) fn flashlight(scaling_factor: f64) -> Self {
+    pub fn flashlight(scaling_factor: f64) -> Self {
         Self::new(SkillKind::flashlight(scaling_factor))
     }
 
     #[inline]
-    pub(crate) fn speed(hit_window: f64) -> Self {
+    pub fn speed(hit_window: f64) -> Self {
         Self::new(SkillKind::speed(hit_window))
     }
 
@@ -130,7 +130,7 @@ impl Skill {
     }
 
     #[inline]
-    pub(crate) fn process(&mut self, curr: &DifficultyObject<'_>) {
+    pub fn process(&mut self, curr: &DifficultyObject<'_>) {
         self.kind.pre_process();
         self.curr_section_peak = self.strain_value_at(curr).max(self.curr_section_peak);
         self.prev_time = Some(curr.base.time / curr.clock_rate);
@@ -138,17 +138,17 @@ impl Skill {
     }
 
     #[inline]
-    pub(crate) fn save_current_peak(&mut self) {
+    pub fn save_current_peak(&mut self) {
         self.strain_peaks.push(self.curr_section_peak);
     }
 
     #[inline]
-    pub(crate) fn start_new_section_from(&mut self, time: f64) {
+    pub fn start_new_section_from(&mut self, time: f64) {
         // The maximum strain of the new section is not zero by default
         self.curr_section_peak = self.calculate_initial_strain(time);
     }
 
-    pub(crate) fn difficulty_value(strain_peaks: &mut [f64], this: &Self) -> f64 {
+    pub fn difficulty_value(strain_peaks: &mut [f64], this: &Self) -> f64 {
         // ? Common values to debug
         // println!("---");
 
@@ -156,7 +156,7 @@ impl Skill {
         //     println!("[{}] {}", i, strain);
         // }
 
-        let mut difficulty = 0.0;
+        let mut difficulty = StrainSum::default();
         let mut weight = 1.0;
         let decay_weight = this.kind.decay_weight();
 
@@ -180,10 +180,10 @@ impl Skill {
             weight *= decay_weight;
         }
 
-        difficulty * difficulty_multiplier
+        difficulty.value() * difficulty_multiplier
     }
 
-    pub(crate) fn calculate_initial_strain(&self, time: f64) -> f64 {
+    pub fn calculate_initial_strain(&self, time: f64) -> f64 {
         let prev_time = self.prev_time.unwrap_or(0.0);
         let decayed_strain = self.curr_strain * self.kind.strain_decay(time - prev_time);
 
@@ -193,7 +193,7 @@ impl Skill {
         }
     }
 
-    pub(crate) fn strain_value_at(&mut self, curr: &DifficultyObject<'_>) -> f64 {
+    pub fn strain_value_at(&mut self, curr: &DifficultyObject<'_>) -> f64 {
         self.curr_strain *= self.kind.strain_decay(curr.delta);
         self.curr_strain += self.kind.strain_value_of(curr) * self.kind.skill_multiplier();
 
@@ -46,7 +46,7 @@ const FLASHLIGHT_REDUCED_SECTION_COUNT: usize = 10;
 const FLASHLIGHT_HISTORY_LENGTH: usize = 10;
 
 #[derive(Clone)]
-pub(crate) struct AimHistoryEntry {
+pub struct AimHistoryEntry {
     angle: Option<f64>,
     is_slider: bool,
     is_spinner: bool,
@@ -75,7 +75,7 @@ impl From<&DifficultyObject<'_>> for AimHistoryEntry {
 }
 
 #[derive(Clone)]
-pub(crate) struct FlashlightHistoryEntry {
+pub struct FlashlightHistoryEntry {
     end_pos: Pos2,
     is_spinner: bool,
     jump_dist: f64,
@@ -94,7 +94,7 @@ impl From<&DifficultyObject<'_>> for FlashlightHistoryEntry {
 }
 
 #[derive(Clone, Debug)]
-pub(crate) struct SpeedHistoryEntry {
+pub struct SpeedHistoryEntry {
     is_slider: bool,
     start_time: f64,
     strain_time: f64,
@@ -111,7 +111,7 @@ impl From<&DifficultyObject<'_>> for SpeedHistoryEntry {
 }
 
 #[derive(Clone)]
-pub(crate) enum SkillKind {
+pub enum SkillKind {
     Aim {
         history: VecDeque<AimHistoryEntry>,
         with_sliders: bool,
@@ -128,21 +128,21 @@ pub(crate) enum SkillKind {
 }
 
 impl SkillKind {
-    pub(crate) fn aim(with_sliders: bool) -> Self {
+    pub fn aim(with_sliders: bool) -> Self {
         Self::Aim {
             history: VecDeque::with_capacity(AIM_HISTORY_LENGTH + 1),
             with_sliders,
         }
     }
 
-    pub(crate) fn flashlight(scaling_factor: f64) -> Self {
+    pub fn flashlight(scaling_factor: f64) -> Self {
         Self::Flashlight {
             history: VecDeque::with_capacity(FLASHLIGHT_HISTORY_LENGTH + 1),
             scaling_factor,
         }
     }
 
-    pub(crate) fn speed(hit_window: f64) -> Self {
+    pub fn speed(hit_window: f64) -> Self {
         Self::Speed {
             curr_rhythm: 1.0,
             history: VecDeque::with_capacity(SPEED_HISTORY_LENGTH + 1),
@@ -150,7 +150,7 @@ impl SkillKind {
         }
     }
 
-    pub(crate) fn pre_process(&mut self) {
+    pub fn pre_process(&mut self) {
         match self {
             Self::Aim { history, .. } => history.truncate(AIM_HISTORY_LENGTH),
             Self::Flashlight { history, .. } => history.truncate(FLASHLIGHT_HISTORY_LENGTH),
@@ -158,7 +158,7 @@ impl SkillKind {
         }
     }
 
-    pub(crate) fn post_process(&mut self, current: &DifficultyObject<'_>) {
+    pub fn post_process(&mut self, current: &DifficultyObject<'_>) {
         match self {
             Self::Aim { history, .. } => history.push_front(current.into()),
             Self::Flashlight { history, .. } => history.push_front(current.into()),
@@ -166,7 +166,7 @@ impl SkillKind {
         }
     }
 
-    pub(crate) fn strain_value_of(&self, curr: &DifficultyObject<'_>) -> f64 {
+    pub fn strain_value_of(&self, curr: &DifficultyObject<'_>) -> f64 {
         match self {
             Self::Aim {
                 history,
@@ -410,7 +410,7 @@ impl SkillKind {
     }
 
     #[inline]
-    pub(crate) fn difficulty_values(&self) -> (usize, f64) {
+    pub fn difficulty_values(&self) -> (usize, f64) {
         match self {
             Self::Aim { .. } => (AIM_REDUCED_SECTION_COUNT, AIM_DIFFICULTY_MULTIPLIER),
             Self::Flashlight { .. } => (
@@ -422,7 +422,7 @@ impl SkillKind {
     }
 
     #[inline]
-    pub(crate) fn skill_multiplier(&self) -> f64 {
+    pub fn skill_multiplier(&self) -> f64 {
         match self {
             SkillKind::Aim { .. } => AIM_SKILL_MULTIPLIER,
             SkillKind::Flashlight { .. } => FLASHLIGHT_SKILL_MULTIPLIER,
@@ -431,7 +431,7 @@ impl SkillKind {
     }
 
     #[inline]
-    pub(crate) fn strain_decay_base(&self) -> f64 {
+    pub fn strain_decay_base(&self) -> f64 {
         match self {
             SkillKind::Aim { .. } => AIM_STRAIN_DECAY_BASE,
             SkillKind::Flashlight { .. } => FLASHLIGHT_STRAIN_DECAY_BASE,
@@ -440,7 +440,7 @@ impl SkillKind {
     }
 
     #[inline]
-    pub(crate) fn decay_weight(&self) -> f64 {
+    pub fn decay_weight(&self) -> f64 {
         match self {
             SkillKind::Aim { .. } => AIM_DECAY_WEIGHT,
             SkillKind::Flashlight { .. } => FLASHLIGHT_DECAY_WEIGHT,
@@ -449,7 +449,7 @@ impl SkillKind {
     }
 
     #[inline]
-    pub(crate) fn strain_decay(&self, ms: f64) -> f64 {
+    pub fn strain_decay(&self, ms: f64) -> f64 {
         self.strain_decay_base().powf(ms / 1000.0)
     }
 }
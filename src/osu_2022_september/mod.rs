@@ -0,0 +1,792 @@
+mod difficulty_object;
+mod object_iter;
+mod osu_object;
+mod pp;
+mod scaling_factor;
+mod skill;
+mod skill_kind;
+
+use std::mem;
+
+use difficulty_object::DifficultyObject;
+pub use object_iter::{difficulty_objects, OsuDifficultyObject, OsuDifficultyObjects};
+use osu_object::{object_combo, ObjectParameters, OsuObject};
+pub use pp::*;
+use rosu_pp::{osu::OsuScoreState, Beatmap, GameMode, Mods};
+use scaling_factor::ScalingFactor;
+use skill::Skill;
+use skill_kind::SkillKind;
+
+pub use crate::osu::{
+    DifficultyAttributes as OsuDifficultyAttributes,
+    PerformanceAttributes as OsuPerformanceAttributes, Strains as OsuStrains,
+};
+
+use crate::{
+    mods::{self, DifficultyAdjust, ScoreMultipliers},
+    section::SectionLen,
+    util::{
+        curve::CurveBuffers,
+        math::{self, stable_cs},
+        mode::check_mode,
+        mods::SCORE_V2,
+    },
+    Error, HitWindows, MapAttributes,
+};
+
+use self::skill::Skills;
+
+const SECTION_LEN: f64 = 400.0;
+const DIFFICULTY_MULTIPLIER: f64 = 0.0675;
+const NORMALIZED_RADIUS: f32 = 50.0; // * diameter of 100; easier mental maths.
+const STACK_DISTANCE: f32 = 3.0;
+
+/// The approximate date at which this version went live, formatted as `YYYY-MM-DD`.
+pub const VERSION_DATE: &str = "2022-09-28";
+
+/// The name of this version within its mode.
+pub const VERSION_NAME: &str = "2022-september";
+
+/// A short description of what changed in this version.
+pub const VERSION_DESCRIPTION: &str =
+    "Speed accuracy only considers relevant notes and flashlight considers hidden.";
+
+/// The mode this version calculates, i.e. osu!standard.
+pub const MODE: GameMode = GameMode::Osu;
+
+crate::calculator::difficulty_calculator!(OsuCalculator, Osu2022September);
+
+/// The building blocks of this version's difficulty calculation.
+///
+/// Only available with the `internals` feature. These types are meant for
+/// experimenting with custom object streams and are exempt from semver guarantees.
+#[cfg(feature = "internals")]
+pub mod internals {
+    pub use super::{
+        difficulty_object::DifficultyObject,
+        osu_object::{NestedObject, NestedObjectKind, OsuObject, OsuObjectKind},
+        skill::Skill,
+        skill_kind::SkillKind,
+    };
+}
+
+/// Parse mod acronyms like `"HDDT"` or `"HD,DT"` into the mods value of this version.
+///
+/// Mods that did not exist yet at the time of this version cause an error.
+#[inline]
+pub fn parse_mods(acronyms: &str) -> Result<u32, Error> {
+    mods::parse_available(acronyms, mods::ALL)
+}
+
+/// The score multiplier of the mods in this version.
+#[inline]
+pub fn score_multiplier(mods: u32) -> f32 {
+    let multipliers = if mods & SCORE_V2 > 0 {
+        ScoreMultipliers::SCORE_V2
+    } else {
+        ScoreMultipliers::OSU
+    };
+
+    multipliers.multiplier(mods)
+}
+
+/// The hit windows for the given overall difficulty and mods in this version.
+#[inline]
+pub fn hit_windows(od: f32, mods: u32) -> HitWindows {
+    HitWindows::exact(od as f64, mods)
+}
+
+/// The effective overall difficulty after applying mods.
+///
+/// `mod_mult` is `1.4` for HR, `0.5` for EZ, and `1.0` otherwise while `speed_mult`
+/// is the clock rate. Unlike the versions until 2018, the 300 hit window is not rounded.
+#[inline]
+pub fn modify_od(base_od: f64, speed_mult: f64, mod_mult: f64) -> f64 {
+    math::modify_od(base_od, speed_mult, mod_mult)
+}
+
+/// The overall difficulty of a 300 hit window in milliseconds, the inverse of [`modify_od`].
+///
+/// Passing a window that was already scaled by the clock rate results in the effective OD.
+#[inline]
+pub fn hit_window_to_od(hit_window: f64) -> f64 {
+    math::hit_window_to_od(hit_window)
+}
+
+/// The preempt time in milliseconds for the given approach rate and mods in this version,
+/// i.e. how long an object is visible before it has to be hit.
+#[inline]
+pub fn ar_to_preempt(ar: f32, mods: u32) -> f64 {
+    math::ar_to_preempt(ar as f64, mods)
+}
+
+/// The effective approach rate of a preempt time in this version.
+///
+/// The result is not clamped and may be fractional, e.g. AR 9 with DT results in AR 10.33.
+#[inline]
+pub fn preempt_to_ar(preempt: f64) -> f64 {
+    math::preempt_to_ar(preempt)
+}
+
+/// The attributes of the map after applying mods as this version computed them.
+///
+/// Only the map's base values are considered so no difficulty calculation is required.
+pub fn map_attributes(map: &Beatmap, mods: u32) -> MapAttributes {
+    let hit_windows = hit_windows(map.od, mods);
+
+    MapAttributes {
+        ar: preempt_to_ar(ar_to_preempt(map.ar, mods)),
+        od: hit_window_to_od(hit_windows.n300),
+        cs: math::stable_cs(map.cs, mods) as f64,
+        hp: math::stable_hp(map.hp, mods) as f64,
+        clock_rate: mods.clock_rate(),
+        hit_windows,
+    }
+}
+
+/// Calculate the difficulty and the performance of a score in one pass.
+///
+/// Calling [`stars`] and then [`OsuPP`] without passing the attributes along calculates
+/// the difficulty twice, this function avoids that.
+pub fn stars_and_pp(
+    map: &Beatmap,
+    mods: u32,
+    state: OsuScoreState,
+) -> (OsuDifficultyAttributes, OsuPerformanceAttributes) {
+    let performance = OsuPP::new(map).mods(mods).state(state).calculate();
+
+    (performance.difficulty.clone(), performance)
+}
+
+/// Difficulty calculation for osu!standard maps.
+///
+/// In case of a partial play, e.g. a fail, one can specify the amount of passed objects.
+///
+/// If you want to calculate the difficulty after every few objects, instead of
+/// calling this function multiple times with different `passed_objects`, you should use
+/// [`OsuGradualDifficultyAttributes`](crate::osu::OsuGradualDifficultyAttributes).
+///
+/// Maps of other modes can not be calculated and result in default attributes.
+pub fn stars(map: &Beatmap, mods: u32, passed_objects: Option<usize>) -> OsuDifficultyAttributes {
+    stars_with_clock_rate(map, mods, passed_objects, mods.clock_rate())
+}
+
+/// Difficulty calculation for osu!standard maps with a custom clock rate.
+///
+/// The clock rate replaces the one of DT or HT, e.g. `1.4` for a lazer DT
+/// score that was set at 1.4x, and applies to both the map attributes and
+/// the strain timing.
+pub fn stars_with_clock_rate(
+    map: &Beatmap,
+    mods: u32,
+    passed_objects: Option<usize>,
+    clock_rate: f64,
+) -> OsuDifficultyAttributes {
+    calculate_stars(
+        map,
+        mods,
+        passed_objects,
+        clock_rate,
+        &DifficultyAdjust::default(),
+        None,
+    )
+}
+
+/// Difficulty calculator on osu!standard maps.
+///
+/// # Example
+///
+/// ```
+/// use rosu_pp::Beatmap;
+/// use rosu_pp_older::osu_2022_september::OsuStars;
+///
+/// # /*
+/// let map: Beatmap = ...
+/// # */
+/// # let map = Beatmap::default();
+///
+/// let difficulty_attrs = OsuStars::new(&map)
+///     .mods(8 + 64) // HDDT
+///     .calculate();
+///
+/// println!("Stars: {}", difficulty_attrs.stars);
+/// ```
+#[derive(Clone, Debug)]
+pub struct OsuStars<'map> {
+    map: &'map Beatmap,
+    mods: u32,
+    passed_objects: Option<usize>,
+    clock_rate: Option<f64>,
+    difficulty_adjust: DifficultyAdjust,
+}
+
+impl<'map> OsuStars<'map> {
+    /// Create a new difficulty calculator for osu!standard maps.
+    #[inline]
+    pub fn new(map: &'map Beatmap) -> Self {
+        Self {
+            map,
+            mods: 0,
+            passed_objects: None,
+            clock_rate: None,
+            difficulty_adjust: DifficultyAdjust::default(),
+        }
+    }
+
+    /// Specify mods through their bit values.
+    ///
+    /// See [https://github.com/ppy/osu-api/wiki#mods](https://github.com/ppy/osu-api/wiki#mods)
+    #[inline]
+    pub fn mods(mut self, mods: u32) -> Self {
+        self.mods = mods;
+
+        self
+    }
+
+    /// Specify mods through their acronyms, e.g. `"HDDT"`.
+    ///
+    /// Returns an error for unknown acronyms and for mods
+    /// that did not exist yet at the time of this version.
+    #[inline]
+    pub fn mods_str(self, acronyms: &str) -> Result<Self, Error> {
+        parse_mods(acronyms).map(|mods| self.mods(mods))
+    }
+
+    /// Amount of passed objects for partial plays, e.g. a fail.
+    #[inline]
+    pub fn passed_objects(mut self, passed_objects: usize) -> Self {
+        self.passed_objects = Some(passed_objects);
+
+        self
+    }
+
+    /// Adjust the clock rate used in the calculation, e.g. `1.4` for a lazer DT
+    /// score that was set at 1.4x.
+    ///
+    /// If none is specified, the clock rate of the mods is used.
+    #[inline]
+    pub fn clock_rate(mut self, clock_rate: f64) -> Self {
+        self.clock_rate = Some(clock_rate);
+
+        self
+    }
+
+    /// Specify the values of lazer's Difficulty Adjust mod.
+    ///
+    /// The values replace the map's base values before HR, EZ, and the clock rate are applied.
+    #[inline]
+    pub fn difficulty_adjust(mut self, difficulty_adjust: DifficultyAdjust) -> Self {
+        self.difficulty_adjust = difficulty_adjust;
+
+        self
+    }
+
+    /// Override the map's approach rate, see [`difficulty_adjust`](Self::difficulty_adjust).
+    #[inline]
+    pub fn ar(mut self, ar: f32) -> Self {
+        self.difficulty_adjust.ar = Some(ar);
+
+        self
+    }
+
+    /// Override the map's circle size, see [`difficulty_adjust`](Self::difficulty_adjust).
+    #[inline]
+    pub fn cs(mut self, cs: f32) -> Self {
+        self.difficulty_adjust.cs = Some(cs);
+
+        self
+    }
+
+    /// Override the map's overall difficulty, see [`difficulty_adjust`](Self::difficulty_adjust).
+    #[inline]
+    pub fn od(mut self, od: f32) -> Self {
+        self.difficulty_adjust.od = Some(od);
+
+        self
+    }
+
+    /// Override the map's drain rate, see [`difficulty_adjust`](Self::difficulty_adjust).
+    #[inline]
+    pub fn hp(mut self, hp: f32) -> Self {
+        self.difficulty_adjust.hp = Some(hp);
+
+        self
+    }
+
+    /// Calculate all difficulty related values, including stars.
+    ///
+    /// Maps of other modes can not be calculated and result in default attributes.
+    #[inline]
+    pub fn calculate(self) -> OsuDifficultyAttributes {
+        let clock_rate = self.clock_rate.unwrap_or_else(|| self.mods.clock_rate());
+
+        calculate_stars(
+            self.map,
+            self.mods,
+            self.passed_objects,
+            clock_rate,
+            &self.difficulty_adjust,
+            None,
+        )
+    }
+}
+
+pub(crate) fn calculate_stars(
+    map: &Beatmap,
+    mods: u32,
+    passed_objects: Option<usize>,
+    clock_rate: f64,
+    difficulty_adjust: &DifficultyAdjust,
+    section_len: Option<SectionLen>,
+) -> OsuDifficultyAttributes {
+    calculate_stars_with_strains(
+        map,
+        mods,
+        passed_objects,
+        clock_rate,
+        difficulty_adjust,
+        section_len,
+    )
+    .0
+}
+
+/// Calculate the difficulty attributes while keeping the section strain peaks.
+pub(crate) fn calculate_stars_with_strains(
+    map: &Beatmap,
+    mods: u32,
+    passed_objects: Option<usize>,
+    clock_rate: f64,
+    difficulty_adjust: &DifficultyAdjust,
+    section_len: Option<SectionLen>,
+) -> (OsuDifficultyAttributes, OsuStrains) {
+    if check_mode(map.mode, MODE).is_err() {
+        return Default::default();
+    }
+
+    let (mut skills, mut attributes) = calculate_skills(
+        map,
+        mods,
+        passed_objects,
+        clock_rate,
+        difficulty_adjust,
+        section_len,
+    );
+
+    let mut strains = OsuStrains {
+        section_len: section_len.map_or(SECTION_LEN * clock_rate, |section_len| {
+            section_len.map_time(clock_rate)
+        }),
+        ..Default::default()
+    };
+
+    let aim_rating = {
+        let aim = skills.aim();
+        let mut aim_strains = mem::take(&mut aim.strain_peaks);
+        strains.aim = aim_strains.clone();
+
+        Skill::difficulty_value(&mut aim_strains, aim).sqrt() * DIFFICULTY_MULTIPLIER
+    };
+
+    let slider_factor = if aim_rating > 0.0 {
+        let aim_no_sliders = skills.aim_no_sliders();
+
+        let mut aim_strains_no_sliders = mem::take(&mut aim_no_sliders.strain_peaks);
+        strains.aim_no_sliders = aim_strains_no_sliders.clone();
+        let aim_rating_no_sliders =
+            Skill::difficulty_value(&mut aim_strains_no_sliders, aim_no_sliders).sqrt()
+                * DIFFICULTY_MULTIPLIER;
+
+        aim_rating_no_sliders / aim_rating
+    } else {
+        1.0
+    };
+
+    let (speed, flashlight) = skills.speed_flashlight();
+
+    let (speed_rating, speed_note_count) = if let Some(speed) = speed {
+        let mut speed_strains = mem::take(&mut speed.strain_peaks);
+        strains.speed = speed_strains.clone();
+        let speed_note_count = speed.relevant_note_count();
+
+        let speed_rating =
+            Skill::difficulty_value(&mut speed_strains, speed).sqrt() * DIFFICULTY_MULTIPLIER;
+
+        (speed_rating, speed_note_count)
+    } else {
+        (0.0, 0.0)
+    };
+
+    let flashlight_rating = if let Some(flashlight) = flashlight {
+        let mut flashlight_strains = mem::take(&mut flashlight.strain_peaks);
+        strains.flashlight = flashlight_strains.clone();
+
+        Skill::difficulty_value(&mut flashlight_strains, flashlight).sqrt() * DIFFICULTY_MULTIPLIER
+    } else {
+        0.0
+    };
+
+    let star_rating = calculate_star_rating(aim_rating, speed_rating, flashlight_rating);
+
+    attributes.aim_strain = aim_rating;
+    attributes.speed_strain = speed_rating;
+    attributes.speed_note_count = speed_note_count;
+    attributes.flashlight_rating = flashlight_rating;
+    attributes.slider_factor = Some(slider_factor);
+    attributes.stars = star_rating;
+
+    (attributes, strains)
+}
+
+/// The maximum combo of an osu!standard map.
+///
+/// Only counts the nested objects of sliders which is considerably cheaper than
+/// calculating the stars, e.g. when validating scores.
+pub fn max_combo(map: &Beatmap) -> usize {
+    let mut curve_bufs = CurveBuffers::default();
+
+    map.hit_objects
+        .iter()
+        .map(|h| object_combo(h, map, &mut curve_bufs))
+        .sum()
+}
+
+/// The section strain peaks of an osu!standard map, e.g. to draw a difficulty graph.
+pub fn strains(map: &Beatmap, mods: u32) -> OsuStrains {
+    let clock_rate = mods.clock_rate();
+    let difficulty_adjust = DifficultyAdjust::default();
+    let (mut skills, _) = calculate_skills(map, mods, None, clock_rate, &difficulty_adjust, None);
+
+    let aim = mem::take(&mut skills.aim().strain_peaks);
+    let aim_no_sliders = mem::take(&mut skills.aim_no_sliders().strain_peaks);
+    let (speed, flashlight) = skills.speed_flashlight();
+
+    OsuStrains {
+        section_len: SECTION_LEN * clock_rate,
+        aim,
+        aim_no_sliders,
+        speed: speed.map_or_else(Vec::new, |skill| mem::take(&mut skill.strain_peaks)),
+        flashlight: flashlight.map_or_else(Vec::new, |skill| mem::take(&mut skill.strain_peaks)),
+    }
+}
+
+/// The radius of hit objects in osu!pixels after applying HR or EZ.
+#[inline]
+pub fn object_radius(cs: f32, mods: u32) -> f32 {
+    ScalingFactor::new(stable_cs(cs, mods) as f64).radius()
+}
+
+/// The factor by which jump distances are scaled to normalize the object radius.
+///
+/// Objects with a radius below 30 get an additional bonus of up to 10%.
+#[inline]
+pub fn scaling_factor(cs: f32, mods: u32) -> f32 {
+    ScalingFactor::new(stable_cs(cs, mods) as f64).adjusted()
+}
+
+fn calculate_star_rating(aim_rating: f64, speed_rating: f64, flashlight_rating: f64) -> f64 {
+    let base_aim_performance = {
+        let base = 5.0 * (aim_rating / 0.0675).max(1.0) - 4.0;
+
+        base * base * base / 100_000.0
+    };
+
+    let base_speed_performance = {
+        let base = 5.0 * (speed_rating / 0.0675).max(1.0) - 4.0;
+
+        base * base * base / 100_000.0
+    };
+
+    let base_flashlight_performance = flashlight_rating * flashlight_rating * 25.0;
+
+    let base_performance = (base_aim_performance.powf(1.1)
+        + base_speed_performance.powf(1.1)
+        + base_flashlight_performance.powf(1.1))
+    .powf(1.0 / 1.1);
+
+    if base_performance > 0.00001 {
+        1.14_f64.cbrt()
+            * 0.027
+            * ((100_000.0 / (1.0_f64 / 1.1).exp2() * base_performance).cbrt() + 4.0)
+    } else {
+        0.0
+    }
+}
+
+fn calculate_skills(
+    map: &Beatmap,
+    mods: u32,
+    passed_objects: Option<usize>,
+    clock_rate: f64,
+    difficulty_adjust: &DifficultyAdjust,
+    section_len: Option<SectionLen>,
+) -> (Skills, OsuDifficultyAttributes) {
+    let take = passed_objects.unwrap_or(map.hit_objects.len());
+
+    let map_attrs = difficulty_adjust
+        .apply(map.attributes().mods(mods).clock_rate(clock_rate))
+        .build();
+    let hit_window = map_attrs.hit_windows.od;
+
+    let hr = mods.hr();
+
+    let time_preempt = (map_attrs.hit_windows.ar * clock_rate) as f32 as f64;
+    let cs = stable_cs(difficulty_adjust.cs.unwrap_or(map.cs), mods);
+    let scaling_factor = ScalingFactor::new(cs as f64);
+
+    let mut attributes = OsuDifficultyAttributes {
+        ar: map_attrs.ar,
+        hp: map_attrs.hp,
+        od: map_attrs.od,
+        base_ar: difficulty_adjust.ar.unwrap_or(map.ar) as f64,
+        base_od: difficulty_adjust.od.unwrap_or(map.od) as f64,
+        ..Default::default()
+    };
+
+    let mut params = ObjectParameters {
+        map,
+        attributes: &mut attributes,
+        ticks: Vec::new(),
+        curve_bufs: CurveBuffers::default(),
+    };
+
+    let mut hit_objects: Vec<_> = map
+        .hit_objects
+        .iter()
+        .take(take)
+        .map(|h| OsuObject::new(h, hr, &mut params))
+        .collect();
+
+    let stack_threshold = time_preempt * map.stack_leniency as f64;
+
+    if map.version >= 6 {
+        stacking(&mut hit_objects, stack_threshold);
+    } else {
+        old_stacking(&mut hit_objects, stack_threshold);
+    }
+
+    let mut hit_objects = hit_objects.into_iter().map(|mut h| {
+        let stack_offset = scaling_factor.stack_offset(h.stack_height);
+        h.pos += stack_offset;
+
+        h
+    });
+
+    let mut skills = Skills::new(
+        hit_window,
+        mods.rx(),
+        scaling_factor.radius(),
+        mods.fl(),
+        mods.hd(),
+        time_preempt,
+    );
+
+    let (mut prev, curr) = match (hit_objects.next(), hit_objects.next()) {
+        (Some(prev), Some(curr)) => (prev, curr),
+        (Some(_), None) | (None, None) => return (skills, attributes),
+        (None, Some(_)) => unreachable!(),
+    };
+
+    let mut prev_prev = None;
+
+    let section_len = match section_len {
+        Some(section_len) => section_len.map_time(clock_rate) / map_attrs.clock_rate,
+        None => SECTION_LEN,
+    };
+
+    // First object has no predecessor and thus no strain, handle distinctly
+    let mut curr_section_end =
+        (prev.time / map_attrs.clock_rate / section_len).ceil() * section_len;
+
+    // Handle second object separately to remove later if-branching
+    let h = DifficultyObject::new(
+        &curr,
+        &mut prev,
+        prev_prev.as_ref(),
+        &scaling_factor,
+        map_attrs.clock_rate,
+    );
+
+    let base_time = h.base.time / map_attrs.clock_rate;
+
+    while base_time > curr_section_end {
+        skills.start_new_section_from(curr_section_end);
+        curr_section_end += section_len;
+    }
+
+    skills.process(&h);
+    prev_prev = Some(mem::replace(&mut prev, curr));
+
+    // Handle all other objects
+    for curr in hit_objects {
+        let h = DifficultyObject::new(
+            &curr,
+            &mut prev,
+            prev_prev.as_ref(),
+            &scaling_factor,
+            map_attrs.clock_rate,
+        );
+
+        let base_time = h.base.time / map_attrs.clock_rate;
+
+        while base_time > curr_section_end {
+            skills.save_peak_and_start_new_section(curr_section_end);
+            curr_section_end += section_len;
+        }
+
+        skills.process(&h);
+        prev_prev = Some(mem::replace(&mut prev, curr));
+    }
+
+    skills.save_current_peak();
+
+    (skills, attributes)
+}
+
+fn stacking(hit_objects: &mut [OsuObject], stack_threshold: f64) {
+    let mut extended_start_idx = 0;
+    let extended_end_idx = hit_objects.len() - 1;
+
+    // First big `if` in osu!lazer's function can be skipped
+
+    for i in (1..=extended_end_idx).rev() {
+        let mut n = i;
+        let mut obj_i_idx = i;
+        // * We should check every note which has not yet got a stack.
+        // * Consider the case we have two interwound stacks and this will make sense.
+        // *   o <-1      o <-2
+        // *    o <-3      o <-4
+        // * We first process starting from 4 and handle 2,
+        // * then we come backwards on the i loop iteration until we reach 3 and handle 1.
+        // * 2 and 1 will be ignored in the i loop because they already have a stack value.
+
+        if hit_objects[obj_i_idx].stack_height.abs() > 0.0 || hit_objects[obj_i_idx].is_spinner() {
+            continue;
+        }
+
+        // * If this object is a hitcircle, then we enter this "special" case.
+        // * It either ends with a stack of hitcircles only,
+        // * or a stack of hitcircles that are underneath a slider.
+        // * Any other case is handled by the "is_slider" code below this.
+        if hit_objects[obj_i_idx].is_circle() {
+            loop {
+                n = match n.checked_sub(1) {
+                    Some(n) => n,
+                    None => break,
+                };
+
+                if hit_objects[n].is_spinner() {
+                    continue;
+                } else if hit_objects[obj_i_idx].time - hit_objects[n].end_time() > stack_threshold
+                {
+                    break; // * We are no longer within stacking range of the previous object.
+                }
+
+                // * HitObjects before the specified update range haven't been reset yet
+                if n < extended_start_idx {
+                    hit_objects[n].stack_height = 0.0;
+                    extended_start_idx = n;
+                }
+
+                // * This is a special case where hticircles are moved DOWN and RIGHT (negative stacking)
+                // * if they are under the *last* slider in a stacked pattern.
+                // *    o==o <- slider is at original location
+                // *        o <- hitCircle has stack of -1
+                // *         o <- hitCircle has stack of -2
+                if hit_objects[n].is_slider()
+                    && hit_objects[n]
+                        .end_pos()
+                        .distance(hit_objects[obj_i_idx].pos)
+                        < STACK_DISTANCE
+                {
+                    let offset =
+                        hit_objects[obj_i_idx].stack_height - hit_objects[n].stack_height + 1.0;
+
+                    for j in n + 1..=i {
+                        // * For each object which was declared under this slider, we will offset
+                        // * it to appear *below* the slider end (rather than above).
+                        if hit_objects[n].end_pos().distance(hit_objects[j].pos) < STACK_DISTANCE {
+                            hit_objects[j].stack_height -= offset;
+                        }
+                    }
+
+                    // * We have hit a slider. We should restart calculation using this as the new base.
+                    // * Breaking here will mean that the slider still has StackCount of 0,
+                    // * so will be handled in the i-outer-loop.
+                    break;
+                }
+
+                if hit_objects[n].pos.distance(hit_objects[obj_i_idx].pos) < STACK_DISTANCE {
+                    // * Keep processing as if there are no sliders.
+                    // * If we come across a slider, this gets cancelled out.
+                    // * NOTE: Sliders with start positions stacking
+                    // * are a special case that is also handled here.
+
+                    hit_objects[n].stack_height = hit_objects[obj_i_idx].stack_height + 1.0;
+                    obj_i_idx = n;
+                }
+            }
+        } else if hit_objects[obj_i_idx].is_slider() {
+            // * We have hit the first slider in a possible stack.
+            // * From this point on, we ALWAYS stack positive regardless.
+            loop {
+                n = match n.checked_sub(1) {
+                    Some(n) => n,
+                    None => break,
+                };
+
+                if hit_objects[n].is_spinner() {
+                    continue;
+                } else if hit_objects[obj_i_idx].time - hit_objects[n].time > stack_threshold {
+                    break; // * We are no longer within stacking range of the previous object.
+                }
+
+                if hit_objects[n]
+                    .end_pos()
+                    .distance(hit_objects[obj_i_idx].pos)
+                    < STACK_DISTANCE
+                {
+                    hit_objects[n].stack_height = hit_objects[obj_i_idx].stack_height + 1.0;
+                    obj_i_idx = n;
+                }
+            }
+        }
+    }
+}
+
+fn old_stacking(hit_objects: &mut [OsuObject], stack_threshold: f64) {
+    for i in 0..hit_objects.len() {
+        if hit_objects[i].stack_height != 0.0 && !hit_objects[i].is_slider() {
+            continue;
+        }
+
+        let mut start_time = hit_objects[i].end_time();
+        let end_pos = hit_objects[i].end_pos();
+
+        let mut slider_stack = 0.0;
+
+        for j in i + 1..hit_objects.len() {
+            if hit_objects[j].time - stack_threshold > start_time {
+                break;
+            }
+
+            if hit_objects[j].pos.distance(hit_objects[i].pos) < STACK_DISTANCE {
+                hit_objects[i].stack_height += 1.0;
+                start_time = hit_objects[j].end_time();
+            } else if hit_objects[j].pos.distance(end_pos) < STACK_DISTANCE {
+                slider_stack += 1.0;
+                hit_objects[j].stack_height -= slider_stack;
+                start_time = hit_objects[j].end_time();
+            }
+        }
+    }
+}
+
+fn _difficulty_range(val: f64, max: f64, avg: f64, min: f64) -> f64 {
+    if val > 5.0 {
+        avg + (max - avg) * (val - 5.0) / 5.0
+    } else if val < 5.0 {
+        avg - (avg - min) * (5.0 - val) / 5.0
+    } else {
+        avg
+    }
+}
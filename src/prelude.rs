@@ -0,0 +1,129 @@
+//! Commonly used items of all versions.
+//!
+//! The calculators of each version module are re-exported under the name of their
+//! [`Version`] variant, e.g. `osu_2015_april::OsuPP` as `Osu2015AprilPP`, and the
+//! `stars` functions are prefixed with their module name, e.g. `osu_2015_april_stars`.
+//!
+//! ```
+//! # #[cfg(feature = "osu-2015-april")]
+//! # {
+//! use rosu_pp_older::prelude::*;
+//!
+//! # /*
+//! let map: Beatmap = ...
+//! # */
+//! # let map = Beatmap::default();
+//!
+//! let stars = osu_2015_april_stars(&map, 0, None).stars;
+//! let pp = Osu2015AprilPP::new(&map).accuracy(98.5).calculate().pp;
+//! # }
+//! ```
+
+pub use rosu_pp::Beatmap;
+
+pub use crate::{
+    stars, AnyDifficultyAttributes, AnyIfFcAttributes, AnyPP, AnyPerformanceAttributes, AnyStars,
+    DifficultyAttributes, DifficultyCalculator, Error, IfFcAttributes, OlderBeatmapExt,
+    PerformanceAttributes, ScoreState, SharedDifficultyAttributes, SharedPerformanceAttributes,
+    Version, VersionInfo,
+};
+
+#[cfg(feature = "osu-2012-tp")]
+pub use crate::osu_2012_tp::{
+    stars as osu_2012_tp_stars, OsuCalculator as Osu2012TpCalculator, OsuPP as Osu2012TpPP,
+    OsuStars as Osu2012TpStars,
+};
+#[cfg(feature = "osu-2014-february")]
+pub use crate::osu_2014_february::{
+    stars as osu_2014_february_stars, OsuCalculator as Osu2014FebruaryCalculator,
+    OsuPP as Osu2014FebruaryPP, OsuStars as Osu2014FebruaryStars,
+};
+#[cfg(feature = "osu-2014-july")]
+pub use crate::osu_2014_july::{
+    stars as osu_2014_july_stars, OsuCalculator as Osu2014JulyCalculator, OsuPP as Osu2014JulyPP,
+    OsuStars as Osu2014JulyStars,
+};
+#[cfg(feature = "osu-2014-may")]
+pub use crate::osu_2014_may::{
+    stars as osu_2014_may_stars, OsuCalculator as Osu2014MayCalculator, OsuPP as Osu2014MayPP,
+    OsuStars as Osu2014MayStars,
+};
+#[cfg(feature = "osu-2015-april")]
+pub use crate::osu_2015_april::{
+    stars as osu_2015_april_stars, OsuCalculator as Osu2015AprilCalculator,
+    OsuPP as Osu2015AprilPP, OsuStars as Osu2015AprilStars,
+};
+#[cfg(feature = "osu-2015-february")]
+pub use crate::osu_2015_february::{
+    stars as osu_2015_february_stars, OsuCalculator as Osu2015FebruaryCalculator,
+    OsuPP as Osu2015FebruaryPP, OsuStars as Osu2015FebruaryStars,
+};
+#[cfg(feature = "osu-2018")]
+pub use crate::osu_2018::{
+    stars as osu_2018_stars, OsuCalculator as Osu2018Calculator, OsuPP as Osu2018PP,
+    OsuStars as Osu2018Stars,
+};
+#[cfg(feature = "osu-2019")]
+pub use crate::osu_2019::{
+    stars as osu_2019_stars, OsuCalculator as Osu2019Calculator, OsuPP as Osu2019PP,
+    OsuStars as Osu2019Stars,
+};
+#[cfg(feature = "osu-2021-january")]
+pub use crate::osu_2021_january::{
+    stars as osu_2021_january_stars, OsuCalculator as Osu2021JanuaryCalculator,
+    OsuPP as Osu2021JanuaryPP, OsuStars as Osu2021JanuaryStars,
+};
+#[cfg(feature = "osu-2021-july")]
+pub use crate::osu_2021_july::{
+    stars as osu_2021_july_stars, OsuCalculator as Osu2021JulyCalculator, OsuPP as Osu2021JulyPP,
+    OsuStars as Osu2021JulyStars,
+};
+#[cfg(feature = "osu-2021-november")]
+pub use crate::osu_2021_november::{
+    stars as osu_2021_november_stars, OsuCalculator as Osu2021NovemberCalculator,
+    OsuPP as Osu2021NovemberPP, OsuStars as Osu2021NovemberStars,
+};
+#[cfg(feature = "osu-2022-september")]
+pub use crate::osu_2022_september::{
+    stars as osu_2022_september_stars, OsuCalculator as Osu2022SeptemberCalculator,
+    OsuPP as Osu2022SeptemberPP, OsuStars as Osu2022SeptemberStars,
+};
+
+#[cfg(feature = "taiko-2020")]
+pub use crate::taiko_2020::{
+    TaikoCalculator as Taiko2020Calculator, TaikoPP as Taiko2020PP, TaikoStars as Taiko2020Stars,
+};
+#[cfg(feature = "taiko-2022")]
+pub use crate::taiko_2022::{
+    TaikoCalculator as Taiko2022Calculator, TaikoPP as Taiko2022PP, TaikoStars as Taiko2022Stars,
+};
+#[cfg(feature = "taiko-ppv1")]
+pub use crate::taiko_ppv1::{
+    stars as taiko_ppv1_stars, TaikoCalculator as TaikoPpv1Calculator, TaikoPP as TaikoPpv1PP,
+    TaikoStars as TaikoPpv1Stars,
+};
+
+#[cfg(feature = "mania-2018")]
+pub use crate::mania_2018::{
+    ManiaCalculator as Mania2018Calculator, ManiaPP as Mania2018PP, ManiaStars as Mania2018Stars,
+};
+#[cfg(feature = "mania-2022")]
+pub use crate::mania_2022::{
+    ManiaCalculator as Mania2022Calculator, ManiaPP as Mania2022PP, ManiaStars as Mania2022Stars,
+};
+#[cfg(feature = "mania-ppv1")]
+pub use crate::mania_ppv1::{
+    stars as mania_ppv1_stars, ManiaCalculator as ManiaPpv1Calculator, ManiaPP as ManiaPpv1PP,
+    ManiaStars as ManiaPpv1Stars,
+};
+
+#[cfg(feature = "catch-2020")]
+pub use crate::catch_2020::{
+    stars as catch_2020_stars, CatchCalculator as Catch2020Calculator, CatchPP as Catch2020PP,
+    CatchStars as Catch2020Stars,
+};
+#[cfg(feature = "fruits-ppv1")]
+pub use crate::fruits_ppv1::{
+    stars as fruits_ppv1_stars, FruitsCalculator as FruitsPpv1Calculator, FruitsPP as FruitsPpv1PP,
+    FruitsStars as FruitsPpv1Stars,
+};
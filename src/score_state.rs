@@ -0,0 +1,127 @@
+use rosu_pp::{
+    catch::CatchScoreState, mania::ManiaScoreState, osu::OsuScoreState, taiko::TaikoScoreState,
+};
+
+/// The hit results of a score, accepted by the `state` method of every pp calculator.
+///
+/// The fields follow the naming of the osu! api so that stored scores can be passed
+/// along as they are. Each calculator only considers the fields of its mode:
+///
+/// - osu!standard: `max_combo`, `n300`, `n100`, `n50`, and `n_misses`
+/// - osu!taiko: `max_combo`, `n300`, `n100`, and `n_misses`
+/// - osu!mania: `n_geki` as 320s, `n300`, `n_katu` as 200s, `n100`, `n50`, and `n_misses`
+/// - osu!catch: `max_combo`, `n300` as fruits, `n100` as droplets, `n50` as tiny droplets,
+///   `n_katu` as tiny droplet misses, and `n_misses`
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ScoreState {
+    /// The maximum combo of the score.
+    pub max_combo: usize,
+    /// The amount of 300s.
+    pub n300: usize,
+    /// The amount of 100s.
+    pub n100: usize,
+    /// The amount of 50s.
+    pub n50: usize,
+    /// The amount of misses.
+    pub n_misses: usize,
+    /// The amount of gekis, i.e. 320s in osu!mania.
+    pub n_geki: usize,
+    /// The amount of katus, i.e. 200s in osu!mania or tiny droplet misses in osu!catch.
+    pub n_katu: usize,
+}
+
+impl From<OsuScoreState> for ScoreState {
+    #[inline]
+    fn from(state: OsuScoreState) -> Self {
+        Self {
+            max_combo: state.max_combo,
+            n300: state.n300,
+            n100: state.n100,
+            n50: state.n50,
+            n_misses: state.n_misses,
+            ..Default::default()
+        }
+    }
+}
+
+impl From<TaikoScoreState> for ScoreState {
+    #[inline]
+    fn from(state: TaikoScoreState) -> Self {
+        Self {
+            max_combo: state.max_combo,
+            n300: state.n300,
+            n100: state.n100,
+            n_misses: state.n_misses,
+            ..Default::default()
+        }
+    }
+}
+
+impl From<ManiaScoreState> for ScoreState {
+    #[inline]
+    fn from(state: ManiaScoreState) -> Self {
+        Self {
+            n_geki: state.n320,
+            n300: state.n300,
+            n_katu: state.n200,
+            n100: state.n100,
+            n50: state.n50,
+            n_misses: state.n_misses,
+            ..Default::default()
+        }
+    }
+}
+
+impl From<CatchScoreState> for ScoreState {
+    #[inline]
+    fn from(state: CatchScoreState) -> Self {
+        Self {
+            max_combo: state.max_combo,
+            n300: state.n_fruits,
+            n100: state.n_droplets,
+            n50: state.n_tiny_droplets,
+            n_katu: state.n_tiny_droplet_misses,
+            n_misses: state.n_misses,
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(feature = "rosu-v2")]
+impl From<&rosu_v2::prelude::Score> for ScoreState {
+    #[inline]
+    fn from(score: &rosu_v2::prelude::Score) -> Self {
+        let stats = &score.statistics;
+
+        Self {
+            max_combo: score.max_combo as usize,
+            n300: stats.count_300 as usize,
+            n100: stats.count_100 as usize,
+            n50: stats.count_50 as usize,
+            n_misses: stats.count_miss as usize,
+            n_geki: stats.count_geki as usize,
+            n_katu: stats.count_katu as usize,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn mania_hits() {
+        let state = ScoreState::from(ManiaScoreState {
+            n320: 500,
+            n300: 200,
+            n200: 30,
+            n100: 20,
+            n50: 10,
+            n_misses: 5,
+        });
+
+        assert_eq!(state.n_geki, 500);
+        assert_eq!(state.n_katu, 30);
+        assert_eq!(state.max_combo, 0);
+    }
+}
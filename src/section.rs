@@ -0,0 +1,37 @@
+/// The length of the sections in which strain peaks are collected.
+///
+/// Every version collects the peak strain of each 400ms section. This is
+/// only meant to study the effect of that constant, ratings calculated with
+/// a different section length are not comparable to the official ones.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(not(feature = "experimental"), allow(dead_code))]
+pub struct SectionLen {
+    len: f64,
+    scales_with_clock_rate: bool,
+}
+
+#[cfg_attr(not(feature = "experimental"), allow(dead_code))]
+impl SectionLen {
+    /// Sections of `len` milliseconds.
+    ///
+    /// If `scales_with_clock_rate` is `true`, the length is measured in
+    /// real time like all versions do, i.e. sections of the unmodified map
+    /// are 1.5 times as long with DT. Otherwise sections always span `len`
+    /// milliseconds of the unmodified map.
+    #[inline]
+    pub const fn new(len: f64, scales_with_clock_rate: bool) -> Self {
+        Self {
+            len,
+            scales_with_clock_rate,
+        }
+    }
+
+    /// The length in milliseconds of the unmodified map.
+    pub(crate) fn map_time(self, clock_rate: f64) -> f64 {
+        if self.scales_with_clock_rate {
+            self.len * clock_rate
+        } else {
+            self.len
+        }
+    }
+}
@@ -0,0 +1,178 @@
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        RwLock,
+    },
+};
+
+use crate::{AnyDifficultyAttributes, Version};
+
+/// The store through which [`AnyStars`](crate::AnyStars) and [`AnyPP`](crate::AnyPP)
+/// share difficulty attributes, see their `store` methods.
+pub type AnyAttributeStore = AttributeStore<AttributeKey, AnyDifficultyAttributes>;
+
+/// The key of the attributes in an [`AnyAttributeStore`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct AttributeKey {
+    /// The id of the map or a hash of its content.
+    pub map: u64,
+    /// The bit values of the mods.
+    pub mods: u32,
+    /// The version that calculated the attributes.
+    pub version: Version,
+}
+
+/// A thread-safe store of previously calculated attributes.
+///
+/// The store only requires a shared reference so it can be put into an `Arc`
+/// and shared across threads or async tasks, e.g. by the handlers of a web service.
+/// The key is up to the caller, e.g. a tuple of map id, mods, and version name.
+///
+/// Only available with the `store` feature.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "osu-2019")]
+/// # {
+/// use rosu_pp::Beatmap;
+/// use rosu_pp_older::{osu_2019, AttributeStore};
+///
+/// # /*
+/// let map: Beatmap = ...
+/// # */
+/// # let map = Beatmap::default();
+/// let store = AttributeStore::new();
+///
+/// let attrs = store.get_or_insert_with((1234, 8 + 64), || osu_2019::stars(&map, 8 + 64, None));
+/// let stats = store.stats();
+///
+/// println!("Stars: {} | Hits: {} | Misses: {}", attrs.stars, stats.hits, stats.misses);
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct AttributeStore<K, A> {
+    entries: RwLock<HashMap<K, A>>,
+    hits: AtomicUsize,
+    misses: AtomicUsize,
+}
+
+/// The usage statistics of an [`AttributeStore`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct StoreStats {
+    /// How often a lookup found stored attributes.
+    pub hits: usize,
+    /// How often a lookup found nothing.
+    pub misses: usize,
+    /// The amount of stored attributes.
+    pub len: usize,
+}
+
+impl<K: Hash + Eq, A: Clone> AttributeStore<K, A> {
+    /// Create an empty store.
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            entries: RwLock::new(HashMap::new()),
+            hits: AtomicUsize::new(0),
+            misses: AtomicUsize::new(0),
+        }
+    }
+
+    /// The stored attributes of the key, counted as hit or miss.
+    pub fn get(&self, key: &K) -> Option<A> {
+        let attrs = self
+            .entries
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(key)
+            .cloned();
+
+        let counter = if attrs.is_some() {
+            &self.hits
+        } else {
+            &self.misses
+        };
+
+        counter.fetch_add(1, Ordering::Relaxed);
+
+        attrs
+    }
+
+    /// Store the attributes of the key, replacing previous attributes.
+    pub fn insert(&self, key: K, attrs: A) {
+        self.entries
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(key, attrs);
+    }
+
+    /// The stored attributes of the key or, if there are none, the attributes of
+    /// `calculate` which are then stored.
+    ///
+    /// The calculation happens without holding the lock so concurrent calls for
+    /// the same key may calculate the attributes more than once.
+    pub fn get_or_insert_with(&self, key: K, calculate: impl FnOnce() -> A) -> A {
+        if let Some(attrs) = self.get(&key) {
+            return attrs;
+        }
+
+        let attrs = calculate();
+        self.insert(key, attrs.clone());
+
+        attrs
+    }
+
+    /// Remove the attributes of the key, e.g. after the map was updated.
+    pub fn remove(&self, key: &K) -> Option<A> {
+        self.entries
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(key)
+    }
+
+    /// Remove all attributes and reset the statistics.
+    pub fn clear(&self) {
+        self.entries
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+
+        self.hits.store(0, Ordering::Relaxed);
+        self.misses.store(0, Ordering::Relaxed);
+    }
+
+    /// The current usage statistics.
+    pub fn stats(&self) -> StoreStats {
+        StoreStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            len: self.entries.read().unwrap_or_else(|e| e.into_inner()).len(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn hits_and_misses() {
+        let store = AttributeStore::new();
+
+        assert_eq!(store.get_or_insert_with((1, 0), || 5.0), 5.0);
+        assert_eq!(store.get_or_insert_with((1, 0), || 6.0), 5.0);
+        assert_eq!(store.get(&(1, 64)), None);
+
+        let expected = StoreStats {
+            hits: 1,
+            misses: 2,
+            len: 1,
+        };
+
+        assert_eq!(store.stats(), expected);
+    }
+}
@@ -0,0 +1,177 @@
+use rosu_pp::{Beatmap, GameMode};
+
+/// The accuracy of the full combo score that each version rates.
+const SUMMARY_ACC: f64 = 98.0;
+
+/// The score that stands in for [`SUMMARY_ACC`] in score-based osu!mania versions.
+const SUMMARY_MANIA_SCORE: u32 = 950_000;
+
+/// How a version weighs a map, see [`summarize`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct VersionSummary {
+    /// The name of the version within its mode.
+    pub version: &'static str,
+    /// The approximate date at which the version went live, formatted as `YYYY-MM-DD`.
+    pub date: &'static str,
+    /// The star rating of the map.
+    pub stars: f64,
+    /// The aim strain divided by the speed strain, only available for osu!standard.
+    pub aim_speed_ratio: Option<f64>,
+    /// The pp of a nomod full combo with 98% accuracy.
+    ///
+    /// osu!mania versions before 2022 rate the score instead of the accuracy,
+    /// a score of 950,000 is used for them.
+    pub pp: f64,
+}
+
+/// Summarize how each version of the map's mode weighs the map, ordered by date.
+///
+/// Meant for a single overview of a map's difficulty across history. Converts are
+/// not included, i.e. an osu!standard map is only summarized by osu!standard versions.
+pub fn summarize(map: &Beatmap) -> Vec<VersionSummary> {
+    macro_rules! osu {
+        ($module:ident) => {{
+            let attrs = crate::$module::OsuPP::new(map)
+                .accuracy(SUMMARY_ACC as _)
+                .calculate();
+            let difficulty = &attrs.difficulty;
+
+            let aim_speed_ratio = if difficulty.speed_strain > 0.0 {
+                Some(difficulty.aim_strain / difficulty.speed_strain)
+            } else {
+                None
+            };
+
+            VersionSummary {
+                version: crate::$module::VERSION_NAME,
+                date: crate::$module::VERSION_DATE,
+                stars: difficulty.stars,
+                aim_speed_ratio,
+                pp: attrs.pp,
+            }
+        }};
+    }
+
+    macro_rules! summary {
+        ($module:ident, $attrs:expr) => {{
+            let attrs = $attrs;
+
+            VersionSummary {
+                version: crate::$module::VERSION_NAME,
+                date: crate::$module::VERSION_DATE,
+                stars: attrs.difficulty.stars,
+                aim_speed_ratio: None,
+                pp: attrs.pp,
+            }
+        }};
+    }
+
+    let mut summaries = Vec::new();
+
+    match map.mode {
+        GameMode::Osu => {
+            #[cfg(feature = "osu-2012-tp")]
+            summaries.push(osu!(osu_2012_tp));
+            #[cfg(feature = "osu-2014-february")]
+            summaries.push(osu!(osu_2014_february));
+            #[cfg(feature = "osu-2014-may")]
+            summaries.push(osu!(osu_2014_may));
+            #[cfg(feature = "osu-2014-july")]
+            summaries.push(osu!(osu_2014_july));
+            #[cfg(feature = "osu-2015-february")]
+            summaries.push(osu!(osu_2015_february));
+            #[cfg(feature = "osu-2015-april")]
+            summaries.push(osu!(osu_2015_april));
+            #[cfg(feature = "osu-2018")]
+            summaries.push(osu!(osu_2018));
+            #[cfg(feature = "osu-2019")]
+            summaries.push(osu!(osu_2019));
+            #[cfg(feature = "osu-2021-january")]
+            summaries.push(osu!(osu_2021_january));
+            #[cfg(feature = "osu-2021-july")]
+            summaries.push(osu!(osu_2021_july));
+            #[cfg(feature = "osu-2021-november")]
+            summaries.push(osu!(osu_2021_november));
+            #[cfg(feature = "osu-2022-september")]
+            summaries.push(osu!(osu_2022_september));
+        }
+        GameMode::Taiko => {
+            #[cfg(feature = "taiko-ppv1")]
+            summaries.push(summary!(
+                taiko_ppv1,
+                crate::taiko_ppv1::TaikoPP::new(map)
+                    .accuracy(SUMMARY_ACC as f32)
+                    .calculate()
+            ));
+            #[cfg(feature = "taiko-2020")]
+            summaries.push(summary!(
+                taiko_2020,
+                crate::taiko_2020::TaikoPP::new(map)
+                    .accuracy(SUMMARY_ACC)
+                    .calculate()
+            ));
+            #[cfg(feature = "taiko-2022")]
+            summaries.push(summary!(
+                taiko_2022,
+                crate::taiko_2022::TaikoPP::new(map)
+                    .accuracy(SUMMARY_ACC)
+                    .calculate()
+            ));
+        }
+        GameMode::Catch => {
+            #[cfg(feature = "fruits-ppv1")]
+            summaries.push(summary!(
+                fruits_ppv1,
+                crate::fruits_ppv1::FruitsPP::new(map)
+                    .accuracy(SUMMARY_ACC as f32)
+                    .calculate()
+            ));
+            #[cfg(feature = "catch-2020")]
+            summaries.push(summary!(
+                catch_2020,
+                crate::catch_2020::CatchPP::new(map)
+                    .accuracy(SUMMARY_ACC as f32)
+                    .calculate()
+            ));
+        }
+        GameMode::Mania => {
+            #[cfg(feature = "mania-ppv1")]
+            summaries.push(summary!(
+                mania_ppv1,
+                crate::mania_ppv1::ManiaPP::new(map)
+                    .score(SUMMARY_MANIA_SCORE)
+                    .calculate()
+            ));
+            #[cfg(feature = "mania-2018")]
+            summaries.push(summary!(
+                mania_2018,
+                crate::mania_2018::ManiaPP::new(map)
+                    .score(SUMMARY_MANIA_SCORE)
+                    .calculate()
+            ));
+            #[cfg(feature = "mania-2022")]
+            summaries.push(summary!(
+                mania_2022,
+                crate::mania_2022::ManiaPP::new(map)
+                    .accuracy(SUMMARY_ACC)
+                    .calculate()
+            ));
+        }
+    }
+
+    summaries
+}
+
+#[cfg(all(test, feature = "all"))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn ordered_by_date() {
+        let summaries = summarize(&Beatmap::default());
+
+        assert_eq!(summaries.len(), 12);
+        assert!(summaries.windows(2).all(|w| w[0].date <= w[1].date));
+    }
+}
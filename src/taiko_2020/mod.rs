@@ -11,15 +11,22 @@ use difficulty_object::DifficultyObject;
 use hitobject_rhythm::{closest_rhythm, HitObjectRhythm};
 pub use pp::*;
 use rim::Rim;
-use rosu_pp::{Beatmap, Mods};
+use rosu_pp::{taiko::TaikoScoreState, Beatmap, GameMode, Mods};
 use skill_kind::SkillKind;
 use stamina_cheese::StaminaCheeseDetector;
 use taiko_object::IntoTaikoObjectIter;
 
 use self::skill::Skills;
+use crate::{
+    convert,
+    mods::{self, ScoreMultipliers},
+    object_count::count_objects,
+    util::{mode::check_mode, sum::StrainSum},
+    Error,
+};
 
-use std::cmp::Ordering;
 use std::f64::consts::PI;
+use std::{borrow::Cow, cmp::Ordering};
 
 const SECTION_LEN: f64 = 400.0;
 
@@ -27,6 +34,60 @@ const COLOR_SKILL_MULTIPLIER: f64 = 0.01;
 const RHYTHM_SKILL_MULTIPLIER: f64 = 0.014;
 const STAMINA_SKILL_MULTIPLIER: f64 = 0.02;
 
+/// The approximate date at which this version went live, formatted as `YYYY-MM-DD`.
+pub const VERSION_DATE: &str = "2020-09-01";
+
+/// The name of this version within its mode.
+pub const VERSION_NAME: &str = "2020";
+
+/// A short description of what changed in this version.
+pub const VERSION_DESCRIPTION: &str = "Rework into separate colour, rhythm, and stamina skills.";
+
+/// The mode this version calculates, i.e. osu!taiko.
+pub const MODE: GameMode = GameMode::Taiko;
+
+crate::calculator::difficulty_calculator!(TaikoCalculator, Taiko2020);
+
+/// Parse mod acronyms like `"HDDT"` or `"HD,DT"` into the mods value of this version.
+///
+/// Mods that did not exist yet at the time of this version cause an error.
+#[inline]
+pub fn parse_mods(acronyms: &str) -> Result<u32, Error> {
+    mods::parse_available(acronyms, mods::BEFORE_MIRROR)
+}
+
+/// The score multiplier of the mods in this version.
+#[inline]
+pub fn score_multiplier(mods: u32) -> f32 {
+    ScoreMultipliers::TAIKO.multiplier(mods)
+}
+
+/// Calculate the difficulty and the performance of a score in one pass.
+///
+/// Calling [`TaikoStars`] and then [`TaikoPP`] without passing the attributes along calculates
+/// the difficulty twice, this function avoids that.
+pub fn stars_and_pp(
+    map: &Beatmap,
+    mods: u32,
+    state: TaikoScoreState,
+) -> (TaikoDifficultyAttributes, TaikoPerformanceAttributes) {
+    let performance = TaikoPP::new(map).mods(mods).state(state).calculate();
+
+    (performance.difficulty, performance)
+}
+
+/// The maximum combo of an osu!taiko map, i.e. the amount of circles.
+///
+/// Considerably cheaper than calculating the stars, e.g. when validating scores.
+#[inline]
+pub fn max_combo(map: &Beatmap) -> usize {
+    convert::taiko(map)
+        .hit_objects
+        .iter()
+        .filter(|h| h.is_circle())
+        .count()
+}
+
 /// Difficulty calculator on osu!taiko maps.
 ///
 /// # Example
@@ -47,9 +108,10 @@ const STAMINA_SKILL_MULTIPLIER: f64 = 0.02;
 /// ```
 #[derive(Clone, Debug)]
 pub struct TaikoStars<'map> {
-    map: &'map Beatmap,
+    map: Cow<'map, Beatmap>,
     mods: u32,
     passed_objects: Option<usize>,
+    clock_rate: Option<f64>,
 }
 
 impl<'map> TaikoStars<'map> {
@@ -57,9 +119,10 @@ impl<'map> TaikoStars<'map> {
     #[inline]
     pub fn new(map: &'map Beatmap) -> Self {
         Self {
-            map,
+            map: convert::taiko(map),
             mods: 0,
             passed_objects: None,
+            clock_rate: None,
         }
     }
 
@@ -73,6 +136,15 @@ impl<'map> TaikoStars<'map> {
         self
     }
 
+    /// Specify mods through their acronyms, e.g. `"HDDT"`.
+    ///
+    /// Returns an error for unknown acronyms and for mods
+    /// that did not exist yet at the time of this version.
+    #[inline]
+    pub fn mods_str(self, acronyms: &str) -> Result<Self, Error> {
+        parse_mods(acronyms).map(|mods| self.mods(mods))
+    }
+
     /// Amount of passed objects for partial plays, e.g. a fail.
     ///
     /// If you want to calculate the difficulty after every few objects, instead of
@@ -85,9 +157,30 @@ impl<'map> TaikoStars<'map> {
         self
     }
 
+    /// Adjust the clock rate used in the calculation, e.g. `1.4` for a lazer DT
+    /// score that was set at 1.4x.
+    ///
+    /// If none is specified, the clock rate of the mods is used.
+    #[inline]
+    pub fn clock_rate(mut self, clock_rate: f64) -> Self {
+        self.clock_rate = Some(clock_rate);
+
+        self
+    }
+
     /// Calculate all difficulty related values, including stars.
+    ///
+    /// Maps of other modes, except for converted osu!standard maps, result in default attributes.
     #[inline]
     pub fn calculate(self) -> TaikoDifficultyAttributes {
+        if check_mode(self.map.mode, MODE).is_err() {
+            return TaikoDifficultyAttributes::default();
+        }
+
+        let take = self.passed_objects.unwrap_or(self.map.hit_objects.len());
+        let (n_circles, n_sliders, n_spinners) =
+            count_objects(self.map.hit_objects.iter().take(take));
+
         let (skills, max_combo) = calculate_skills(self);
         let mut buf = vec![0.0; skills.strain_peaks_len()];
 
@@ -113,7 +206,13 @@ impl<'map> TaikoStars<'map> {
 
         let stars = rescale(1.4 * separate_rating + 0.5 * combined_rating);
 
-        TaikoDifficultyAttributes { stars, max_combo }
+        TaikoDifficultyAttributes {
+            stars,
+            max_combo,
+            n_circles,
+            n_sliders,
+            n_spinners,
+        }
     }
 }
 
@@ -122,6 +221,7 @@ fn calculate_skills(params: TaikoStars<'_>) -> (Skills, usize) {
         map,
         mods,
         passed_objects,
+        clock_rate,
     } = params;
 
     let take = passed_objects.unwrap_or(map.hit_objects.len());
@@ -129,7 +229,7 @@ fn calculate_skills(params: TaikoStars<'_>) -> (Skills, usize) {
     // True if the object at that index is stamina cheese
     let cheese = map.find_cheese();
     let mut skills = Skills::new();
-    let clock_rate = mods.clock_rate();
+    let clock_rate = clock_rate.unwrap_or_else(|| mods.clock_rate());
     let mut max_combo = 0;
 
     match map.hit_objects.get(0) {
@@ -219,7 +319,7 @@ fn locally_combined_difficulty(peaks: &mut Vec<f64>, skills: &Skills, stamina_pe
     peaks.extend(iter);
     peaks.sort_unstable_by(|a, b| b.partial_cmp(a).unwrap_or(Ordering::Equal));
 
-    let mut difficulty = 0.0;
+    let mut difficulty = StrainSum::default();
     let mut weight = 1.0;
 
     for strain in peaks {
@@ -227,7 +327,7 @@ fn locally_combined_difficulty(peaks: &mut Vec<f64>, skills: &Skills, stamina_pe
         weight *= 0.9;
     }
 
-    difficulty
+    difficulty.value()
 }
 
 #[inline]
@@ -237,11 +337,18 @@ fn norm(p: f64, a: f64, b: f64, c: f64) -> f64 {
 
 /// The result of a difficulty calculation on an osu!taiko map.
 #[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct TaikoDifficultyAttributes {
     /// The final star rating.
     pub stars: f64,
     /// The maximum combo.
     pub max_combo: usize,
+    /// The amount of notes.
+    pub n_circles: usize,
+    /// The amount of drumrolls.
+    pub n_sliders: usize,
+    /// The amount of swells.
+    pub n_spinners: usize,
 }
 
 impl TaikoDifficultyAttributes {
@@ -254,6 +361,7 @@ impl TaikoDifficultyAttributes {
 
 /// The result of a performance calculation on an osu!taiko map.
 #[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct TaikoPerformanceAttributes {
     /// The difficulty attributes that were used for the performance calculation
     pub difficulty: TaikoDifficultyAttributes,
@@ -263,6 +371,8 @@ pub struct TaikoPerformanceAttributes {
     pub pp_acc: f64,
     /// The strain portion of the final pp.
     pub pp_strain: f64,
+    /// The multiplier that was applied due to the NoFail mod.
+    pub nf_multiplier: f64,
 }
 
 impl TaikoPerformanceAttributes {
@@ -1,8 +1,15 @@
-use rosu_pp::{taiko::TaikoScoreState, Beatmap, Mods};
+use std::borrow::Cow;
 
-use crate::util::math::difficulty_range;
+use rosu_pp::{Beatmap, Mods};
 
-use super::{TaikoDifficultyAttributes, TaikoPerformanceAttributes, TaikoStars};
+use crate::{
+    convert,
+    unranked::UnrankedConfig,
+    util::{cache::DifficultyCache, math::difficulty_range, mode::check_mode},
+    Error, IfFcAttributes, ScoreState,
+};
+
+use super::{TaikoDifficultyAttributes, TaikoPerformanceAttributes, TaikoStars, MODE};
 
 /// Performance calculator on osu!taiko maps.
 ///
@@ -36,8 +43,9 @@ use super::{TaikoDifficultyAttributes, TaikoPerformanceAttributes, TaikoStars};
 #[derive(Clone, Debug)]
 #[allow(clippy::upper_case_acronyms)]
 pub struct TaikoPP<'map> {
-    map: &'map Beatmap,
+    map: Cow<'map, Beatmap>,
     attributes: Option<TaikoDifficultyAttributes>,
+    cache: DifficultyCache<TaikoDifficultyAttributes>,
     mods: u32,
     combo: Option<usize>,
     acc: f64,
@@ -46,6 +54,8 @@ pub struct TaikoPP<'map> {
     pub(crate) n300: Option<usize>,
     pub(crate) n100: Option<usize>,
     pub(crate) n_misses: usize,
+
+    unranked: UnrankedConfig,
 }
 
 impl<'map> TaikoPP<'map> {
@@ -53,8 +63,9 @@ impl<'map> TaikoPP<'map> {
     #[inline]
     pub fn new(map: &'map Beatmap) -> Self {
         Self {
-            map,
+            map: convert::taiko(map),
             attributes: None,
+            cache: DifficultyCache::default(),
             mods: 0,
             combo: None,
             acc: 1.0,
@@ -62,6 +73,8 @@ impl<'map> TaikoPP<'map> {
             passed_objects: None,
             n300: None,
             n100: None,
+
+            unranked: UnrankedConfig::default(),
         }
     }
 
@@ -83,10 +96,20 @@ impl<'map> TaikoPP<'map> {
     #[inline]
     pub fn mods(mut self, mods: u32) -> Self {
         self.mods = mods;
+        self.cache.reset();
 
         self
     }
 
+    /// Specify mods through their acronyms, e.g. `"HDDT"`.
+    ///
+    /// Returns an error for unknown acronyms and for mods
+    /// that did not exist yet at the time of this version.
+    #[inline]
+    pub fn mods_str(self, acronyms: &str) -> Result<Self, Error> {
+        super::parse_mods(acronyms).map(|mods| self.mods(mods))
+    }
+
     /// Specify the max combo of the play.
     #[inline]
     pub fn combo(mut self, combo: usize) -> Self {
@@ -137,19 +160,21 @@ impl<'map> TaikoPP<'map> {
     #[inline]
     pub fn passed_objects(mut self, passed_objects: usize) -> Self {
         self.passed_objects.replace(passed_objects);
+        self.cache.reset();
 
         self
     }
 
-    /// Provide parameters through a [`TaikoScoreState`].
+    /// Provide parameters through a [`ScoreState`].
     #[inline]
-    pub fn state(mut self, state: TaikoScoreState) -> Self {
-        let TaikoScoreState {
+    pub fn state(mut self, state: impl Into<ScoreState>) -> Self {
+        let ScoreState {
             max_combo,
             n300,
             n100,
             n_misses,
-        } = state;
+            ..
+        } = state.into();
 
         self.combo = Some(max_combo);
         self.n300 = Some(n300);
@@ -159,13 +184,76 @@ impl<'map> TaikoPP<'map> {
         self
     }
 
+    /// Specify whether [`try_calculate`](Self::try_calculate) should accept mods
+    /// that bancho never awarded pp for, i.e. AT, CN, TP, and RX.
+    ///
+    /// Useful for private servers, defaults to `false`.
+    #[inline]
+    pub fn allow_unranked(mut self, allow_unranked: bool) -> Self {
+        self.unranked.allow_unranked = allow_unranked;
+
+        self
+    }
+
+    /// Same as [`calculate`](Self::calculate) but returns an error if the map can
+    /// not be calculated by this version or if the mods contain unranked mods.
+    pub fn try_calculate(self) -> Result<TaikoPerformanceAttributes, Error> {
+        check_mode(self.map.mode, MODE)?;
+        self.unranked.check(self.mods)?;
+
+        Ok(self.calculate())
+    }
+
+    /// Calculate the performance of the score and of its full combo.
+    ///
+    /// For the full combo, the misses are counted as 300s and the combo is the map's
+    /// max combo. If only the accuracy was specified, it is kept as it is.
+    pub fn calculate_if_fc(self) -> IfFcAttributes<TaikoPerformanceAttributes> {
+        let fc = self.if_fc();
+
+        IfFcAttributes {
+            score: self.calculate(),
+            fc: fc.calculate(),
+        }
+    }
+
+    /// Same as [`calculate_if_fc`](Self::calculate_if_fc) but returns an error if the map can
+    /// not be calculated by this version or if the mods contain unranked mods.
+    pub fn try_calculate_if_fc(self) -> Result<IfFcAttributes<TaikoPerformanceAttributes>, Error> {
+        check_mode(self.map.mode, MODE)?;
+        self.unranked.check(self.mods)?;
+
+        Ok(self.calculate_if_fc())
+    }
+
+    fn if_fc(&self) -> Self {
+        let mut fc = self.clone();
+        fc.combo = None;
+        fc.n_misses = 0;
+
+        if let Some(ref mut n300) = fc.n300 {
+            *n300 += self.n_misses;
+        }
+
+        fc
+    }
+
     /// Calculate all performance related values, including pp and stars.
+    ///
+    /// Maps of other modes, except for converted osu!standard maps, result in default attributes,
+    /// [`try_calculate`](Self::try_calculate) returns an error for them instead.
     pub fn calculate(mut self) -> TaikoPerformanceAttributes {
+        if check_mode(self.map.mode, MODE).is_err() {
+            return TaikoPerformanceAttributes::default();
+        }
+
         let attributes = self.attributes.take().unwrap_or_else(|| {
-            TaikoStars::new(self.map)
-                .mods(self.mods)
-                .passed_objects(self.passed_objects.unwrap_or(usize::MAX))
-                .calculate()
+            self.cache.get_or_insert_with(|| {
+                TaikoStars::new(&self.map)
+                    .mods(self.mods)
+                    .passed_objects(self.passed_objects.unwrap_or(usize::MAX))
+                    .calculate()
+            })
         });
 
         if self.n300.or(self.n100).is_some() {
@@ -189,7 +277,7 @@ impl<'map> TaikoPP<'map> {
         }
 
         let inner = TaikoPPInner {
-            map: self.map,
+            map: &self.map,
             attributes,
             mods: self.mods,
             acc: self.acc,
@@ -212,9 +300,8 @@ impl<'map> TaikoPPInner<'map> {
     fn calculate(self) -> TaikoPerformanceAttributes {
         let mut multiplier = 1.1;
 
-        if self.mods.nf() {
-            multiplier *= 0.9;
-        }
+        let nf_multiplier = if self.mods.nf() { 0.9 } else { 1.0 };
+        multiplier *= nf_multiplier;
 
         if self.mods.hd() {
             multiplier *= 1.1;
@@ -230,6 +317,7 @@ impl<'map> TaikoPPInner<'map> {
             pp,
             pp_acc: acc_value,
             pp_strain: strain_value,
+            nf_multiplier,
         }
     }
 
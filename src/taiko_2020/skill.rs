@@ -1,5 +1,7 @@
 use super::{DifficultyObject, SkillKind};
 
+use crate::util::sum::StrainSum;
+
 use std::cmp::Ordering;
 
 const DECAY_WEIGHT: f64 = 0.9;
@@ -110,7 +112,7 @@ impl Skill {
 
     #[inline]
     pub(crate) fn difficulty_value(&self, peaks: &mut [f64]) -> f64 {
-        let mut difficulty = 0.0;
+        let mut difficulty = StrainSum::default();
         let mut weight = 1.0;
 
         peaks.sort_unstable_by(|a, b| b.partial_cmp(a).unwrap_or(Ordering::Equal));
@@ -120,7 +122,7 @@ impl Skill {
             weight *= DECAY_WEIGHT;
         }
 
-        difficulty
+        difficulty.value()
     }
 
     #[inline]
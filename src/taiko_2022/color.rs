@@ -0,0 +1,225 @@
+use std::{f64::consts::E, ops::Range};
+
+use super::difficulty_object::{DifficultyObject, HitType};
+
+const MAX_REPETITION_INTERVAL: usize = 16;
+
+/// Consecutive objects of the same hit type, e.g. `kkk` or `dd`.
+struct MonoStreak {
+    first_object: usize,
+    hit_type: Option<HitType>,
+    run_len: usize,
+}
+
+/// Consecutive mono streaks of the same length, e.g. `kkdd`.
+struct AlternatingMonoPattern {
+    mono_streaks: Range<usize>,
+}
+
+impl AlternatingMonoPattern {
+    fn first_streak<'s>(&self, mono_streaks: &'s [MonoStreak]) -> &'s MonoStreak {
+        &mono_streaks[self.mono_streaks.start]
+    }
+
+    fn has_identical_mono_len(&self, other: &Self, mono_streaks: &[MonoStreak]) -> bool {
+        self.first_streak(mono_streaks).run_len == other.first_streak(mono_streaks).run_len
+    }
+
+    fn is_repetition_of(&self, other: &Self, mono_streaks: &[MonoStreak]) -> bool {
+        self.has_identical_mono_len(other, mono_streaks)
+            && self.mono_streaks.len() == other.mono_streaks.len()
+            && self.first_streak(mono_streaks).hit_type == other.first_streak(mono_streaks).hit_type
+    }
+}
+
+/// Consecutive alternating mono patterns that are repetitions of each other, e.g. `kkdd kkdd`.
+struct RepeatingHitPatterns {
+    patterns: Range<usize>,
+    repetition_interval: usize,
+}
+
+/// Encode the objects into mono streaks, alternating mono patterns, and repeating hit patterns
+/// and assign the difficulty of each encoding to its first object.
+pub(crate) fn assign_color_difficulties(objects: &mut [DifficultyObject<'_>]) {
+    let mono_streaks = encode_mono_streaks(objects);
+    let patterns = encode_alternating_mono_patterns(&mono_streaks);
+    let repeating_patterns = encode_repeating_hit_patterns(&patterns, &mono_streaks);
+
+    for repeating in repeating_patterns.iter() {
+        let repeating_difficulty =
+            2.0 * (1.0 - sigmoid(repeating.repetition_interval as f64, 2.0, 2.0, 0.5, 1.0));
+
+        let first_pattern = &patterns[repeating.patterns.start];
+        objects[first_pattern.first_streak(&mono_streaks).first_object].color +=
+            repeating_difficulty;
+
+        for (i, pattern) in patterns[repeating.patterns.clone()].iter().enumerate() {
+            let pattern_difficulty = sigmoid(i as f64, 2.0, 2.0, 0.5, 1.0) * repeating_difficulty;
+            objects[pattern.first_streak(&mono_streaks).first_object].color += pattern_difficulty;
+
+            for (j, streak) in mono_streaks[pattern.mono_streaks.clone()]
+                .iter()
+                .enumerate()
+            {
+                objects[streak.first_object].color +=
+                    sigmoid(j as f64, 2.0, 2.0, 0.5, 1.0) * pattern_difficulty * 0.5;
+            }
+        }
+    }
+}
+
+fn encode_mono_streaks(objects: &[DifficultyObject<'_>]) -> Vec<MonoStreak> {
+    let mut mono_streaks: Vec<MonoStreak> = Vec::new();
+    let mut prev_note_type = None;
+
+    for (i, h) in objects.iter().enumerate() {
+        // * This ignores all non-note objects, which may or may not be the desired behaviour
+        let prev_type = h.hit_type.and(prev_note_type);
+
+        // * If this is the first object in the list or the colour changed, create a new mono streak
+        match mono_streaks.last_mut() {
+            Some(streak) if prev_type.is_some() && h.hit_type == prev_type => streak.run_len += 1,
+            _ => mono_streaks.push(MonoStreak {
+                first_object: i,
+                hit_type: h.hit_type,
+                run_len: 1,
+            }),
+        }
+
+        if h.hit_type.is_some() {
+            prev_note_type = h.hit_type;
+        }
+    }
+
+    mono_streaks
+}
+
+fn encode_alternating_mono_patterns(mono_streaks: &[MonoStreak]) -> Vec<AlternatingMonoPattern> {
+    let mut patterns = Vec::new();
+    let mut start = 0;
+
+    for (i, streak) in mono_streaks.iter().enumerate() {
+        // * Start a new AlternatingMonoPattern if the previous MonoStreak has a different mono length,
+        // * or if this is the last MonoStreak in the list.
+        let ends_pattern = mono_streaks
+            .get(i + 1)
+            .map_or(true, |next| next.run_len != streak.run_len);
+
+        if ends_pattern {
+            patterns.push(AlternatingMonoPattern {
+                mono_streaks: start..i + 1,
+            });
+
+            start = i + 1;
+        }
+    }
+
+    patterns
+}
+
+fn encode_repeating_hit_patterns(
+    patterns: &[AlternatingMonoPattern],
+    mono_streaks: &[MonoStreak],
+) -> Vec<RepeatingHitPatterns> {
+    let is_coupled = |i: usize| {
+        i + 2 < patterns.len() && patterns[i].is_repetition_of(&patterns[i + 2], mono_streaks)
+    };
+
+    let mut repeating_patterns = Vec::new();
+    let mut i = 0;
+
+    while i < patterns.len() {
+        let start = i;
+
+        // * Determine if future AlternatingMonoPatterns can be encoded into the current RepeatingHitPattern
+        if is_coupled(i) {
+            // * If so, encode all future AlternatingMonoPatterns that are repetitions
+            while is_coupled(i) {
+                i += 1;
+            }
+
+            // * Skip over viewed data and add the rest to the payload
+            i += 2;
+        } else {
+            // * If not, only encode the current AlternatingMonoPattern
+            i += 1;
+        }
+
+        repeating_patterns.push(RepeatingHitPatterns {
+            patterns: start..i,
+            repetition_interval: MAX_REPETITION_INTERVAL + 1,
+        });
+    }
+
+    for curr in 1..repeating_patterns.len() {
+        let interval = (1..MAX_REPETITION_INTERVAL)
+            .zip((0..curr).rev())
+            .find(|&(_, prev)| {
+                is_repetition_of(
+                    &repeating_patterns[curr],
+                    &repeating_patterns[prev],
+                    patterns,
+                    mono_streaks,
+                )
+            })
+            .map_or(MAX_REPETITION_INTERVAL + 1, |(interval, _)| interval);
+
+        repeating_patterns[curr].repetition_interval = interval;
+    }
+
+    repeating_patterns
+}
+
+fn is_repetition_of(
+    this: &RepeatingHitPatterns,
+    other: &RepeatingHitPatterns,
+    patterns: &[AlternatingMonoPattern],
+    mono_streaks: &[MonoStreak],
+) -> bool {
+    if this.patterns.len() != other.patterns.len() {
+        return false;
+    }
+
+    patterns[this.patterns.clone()]
+        .iter()
+        .zip(patterns[other.patterns.clone()].iter())
+        .take(2)
+        .all(|(a, b)| a.has_identical_mono_len(b, mono_streaks))
+}
+
+fn sigmoid(val: f64, center: f64, width: f64, middle: f64, height: f64) -> f64 {
+    let sigmoid = (E * -(val - center) / width).tanh();
+
+    sigmoid * (height / 2.0) + middle
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn streaks(run_lens: &[usize]) -> Vec<MonoStreak> {
+        run_lens
+            .iter()
+            .zip([HitType::Centre, HitType::Rim].iter().cycle())
+            .map(|(&run_len, &hit_type)| MonoStreak {
+                first_object: 0,
+                hit_type: Some(hit_type),
+                run_len,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn repeating_patterns() {
+        // kd kkdd kd kkdd kd
+        let mono_streaks = streaks(&[1, 1, 2, 2, 1, 1, 2, 2, 1, 1]);
+        let patterns = encode_alternating_mono_patterns(&mono_streaks);
+
+        assert_eq!(patterns.len(), 5);
+
+        let repeating = encode_repeating_hit_patterns(&patterns, &mono_streaks);
+
+        assert_eq!(repeating.len(), 1);
+        assert_eq!(repeating[0].patterns, 0..5);
+    }
+}
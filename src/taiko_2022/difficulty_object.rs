@@ -0,0 +1,73 @@
+use super::{closest_rhythm, taiko_object::TaikoObject, HitObjectRhythm, Rim};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum HitType {
+    Centre,
+    Rim,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct DifficultyObject<'o> {
+    pub(crate) idx: usize,
+    pub(crate) base: TaikoObject<'o>,
+    pub(crate) delta: f64,
+    pub(crate) rhythm: &'static HitObjectRhythm,
+    pub(crate) start_time: f64,
+    /// `None` for drumrolls and swells.
+    pub(crate) hit_type: Option<HitType>,
+    /// The color difficulty, only non-zero for the first object of a color encoding.
+    pub(crate) color: f64,
+    /// The start time of the second previous note of the same hit type.
+    pub(crate) key_prev_time: Option<f64>,
+}
+
+impl<'o> DifficultyObject<'o> {
+    #[inline]
+    pub(crate) fn new(
+        idx: usize,
+        base: TaikoObject<'o>,
+        prev: TaikoObject<'o>,
+        prev_prev: TaikoObject<'o>,
+        clock_rate: f64,
+    ) -> Self {
+        let delta = (base.h.start_time - prev.h.start_time) / clock_rate;
+        let rhythm = closest_rhythm(delta, prev.h, prev_prev.h, clock_rate);
+
+        let hit_type = if !base.h.is_circle() {
+            None
+        } else if base.sound.is_rim() {
+            Some(HitType::Rim)
+        } else {
+            Some(HitType::Centre)
+        };
+
+        Self {
+            idx,
+            base,
+            delta,
+            rhythm,
+            start_time: base.h.start_time / clock_rate,
+            hit_type,
+            color: 0.0,
+            key_prev_time: None,
+        }
+    }
+}
+
+/// Assign to each note the start time of the note that was hit by the same key,
+/// i.e. the second previous note of the same hit type.
+pub(crate) fn assign_key_prev_times(objects: &mut [DifficultyObject<'_>]) {
+    let mut centres = Vec::new();
+    let mut rims = Vec::new();
+
+    for h in objects.iter_mut() {
+        let mono = match h.hit_type {
+            Some(HitType::Centre) => &mut centres,
+            Some(HitType::Rim) => &mut rims,
+            None => continue,
+        };
+
+        h.key_prev_time = mono.len().checked_sub(2).map(|i| mono[i]);
+        mono.push(h.start_time);
+    }
+}
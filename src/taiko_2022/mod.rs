@@ -0,0 +1,398 @@
+mod color;
+mod difficulty_object;
+mod hitobject_rhythm;
+mod pp;
+mod rim;
+mod skill;
+mod skill_kind;
+mod taiko_object;
+
+use color::assign_color_difficulties;
+use difficulty_object::{assign_key_prev_times, DifficultyObject};
+use hitobject_rhythm::{closest_rhythm, HitObjectRhythm};
+pub use pp::*;
+use rim::Rim;
+use rosu_pp::{taiko::TaikoScoreState, Beatmap, GameMode, Mods};
+use skill_kind::SkillKind;
+use taiko_object::IntoTaikoObjectIter;
+
+use self::skill::Skills;
+use crate::{
+    convert,
+    mods::{self, ScoreMultipliers},
+    object_count::count_objects,
+    util::{math::difficulty_range, mode::check_mode, sum::StrainSum},
+    Error,
+};
+
+use std::{borrow::Cow, cmp::Ordering};
+
+const SECTION_LEN: f64 = 400.0;
+
+const DIFFICULTY_MULTIPLIER: f64 = 1.35;
+const FINAL_MULTIPLIER: f64 = 0.0625;
+
+const COLOR_SKILL_MULTIPLIER: f64 = 0.375 * FINAL_MULTIPLIER;
+const RHYTHM_SKILL_MULTIPLIER: f64 = 0.2 * FINAL_MULTIPLIER;
+const STAMINA_SKILL_MULTIPLIER: f64 = 0.375 * FINAL_MULTIPLIER;
+
+/// The approximate date at which this version went live, formatted as `YYYY-MM-DD`.
+pub const VERSION_DATE: &str = "2022-09-28";
+
+/// The name of this version within its mode.
+pub const VERSION_NAME: &str = "2022";
+
+/// A short description of what changed in this version.
+pub const VERSION_DESCRIPTION: &str =
+    "Rework of the colour and stamina skills with a new pp balance.";
+
+/// The mode this version calculates, i.e. osu!taiko.
+pub const MODE: GameMode = GameMode::Taiko;
+
+crate::calculator::difficulty_calculator!(TaikoCalculator, Taiko2022);
+
+/// Parse mod acronyms like `"HDDT"` or `"HD,DT"` into the mods value of this version.
+///
+/// Mods that did not exist yet at the time of this version cause an error.
+#[inline]
+pub fn parse_mods(acronyms: &str) -> Result<u32, Error> {
+    mods::parse_available(acronyms, mods::ALL)
+}
+
+/// The score multiplier of the mods in this version.
+#[inline]
+pub fn score_multiplier(mods: u32) -> f32 {
+    ScoreMultipliers::TAIKO.multiplier(mods)
+}
+
+/// Calculate the difficulty and the performance of a score in one pass.
+///
+/// Calling [`TaikoStars`] and then [`TaikoPP`] without passing the attributes along calculates
+/// the difficulty twice, this function avoids that.
+pub fn stars_and_pp(
+    map: &Beatmap,
+    mods: u32,
+    state: TaikoScoreState,
+) -> (TaikoDifficultyAttributes, TaikoPerformanceAttributes) {
+    let performance = TaikoPP::new(map).mods(mods).state(state).calculate();
+
+    (performance.difficulty, performance)
+}
+
+/// The maximum combo of an osu!taiko map, i.e. the amount of circles.
+///
+/// Considerably cheaper than calculating the stars, e.g. when validating scores.
+#[inline]
+pub fn max_combo(map: &Beatmap) -> usize {
+    convert::taiko(map)
+        .hit_objects
+        .iter()
+        .filter(|h| h.is_circle())
+        .count()
+}
+
+/// Difficulty calculator on osu!taiko maps.
+///
+/// # Example
+///
+/// ```
+/// use rosu_pp::Beatmap;
+/// use rosu_pp_older::taiko_2022::TaikoStars;
+///
+/// # /*
+/// let map: Beatmap = ...
+/// # */
+/// # let map = Beatmap::default();
+///
+/// let difficulty_attrs = TaikoStars::new(&map)
+///     .mods(8 + 64) // HDDT
+///     .calculate();
+///
+/// println!("Stars: {}", difficulty_attrs.stars);
+/// ```
+#[derive(Clone, Debug)]
+pub struct TaikoStars<'map> {
+    map: Cow<'map, Beatmap>,
+    mods: u32,
+    passed_objects: Option<usize>,
+    clock_rate: Option<f64>,
+}
+
+impl<'map> TaikoStars<'map> {
+    /// Create a new difficulty calculator for osu!taiko maps.
+    #[inline]
+    pub fn new(map: &'map Beatmap) -> Self {
+        Self {
+            map: convert::taiko(map),
+            mods: 0,
+            passed_objects: None,
+            clock_rate: None,
+        }
+    }
+
+    /// Specify mods through their bit values.
+    ///
+    /// See [https://github.com/ppy/osu-api/wiki#mods](https://github.com/ppy/osu-api/wiki#mods)
+    #[inline]
+    pub fn mods(mut self, mods: u32) -> Self {
+        self.mods = mods;
+
+        self
+    }
+
+    /// Specify mods through their acronyms, e.g. `"HDDT"`.
+    ///
+    /// Returns an error for unknown acronyms and for mods
+    /// that did not exist yet at the time of this version.
+    #[inline]
+    pub fn mods_str(self, acronyms: &str) -> Result<Self, Error> {
+        parse_mods(acronyms).map(|mods| self.mods(mods))
+    }
+
+    /// Amount of passed objects for partial plays, e.g. a fail.
+    #[inline]
+    pub fn passed_objects(mut self, passed_objects: usize) -> Self {
+        self.passed_objects = Some(passed_objects);
+
+        self
+    }
+
+    /// Adjust the clock rate used in the calculation, e.g. `1.4` for a lazer DT
+    /// score that was set at 1.4x.
+    ///
+    /// If none is specified, the clock rate of the mods is used.
+    #[inline]
+    pub fn clock_rate(mut self, clock_rate: f64) -> Self {
+        self.clock_rate = Some(clock_rate);
+
+        self
+    }
+
+    /// Calculate all difficulty related values, including stars.
+    ///
+    /// Maps of other modes, except for converted osu!standard maps, result in default attributes.
+    pub fn calculate(self) -> TaikoDifficultyAttributes {
+        if check_mode(self.map.mode, MODE).is_err() {
+            return TaikoDifficultyAttributes::default();
+        }
+
+        let take = self.passed_objects.unwrap_or(self.map.hit_objects.len());
+        let (n_circles, n_sliders, n_spinners) =
+            count_objects(self.map.hit_objects.iter().take(take));
+
+        let clock_rate = self.clock_rate.unwrap_or_else(|| self.mods.clock_rate());
+        let skills = calculate_skills(&self.map, take, clock_rate);
+        let mut buf = vec![0.0; skills.strain_peaks_len()];
+
+        skills.color.copy_strain_peaks(&mut buf);
+        let color_rating = skills.color.difficulty_value(&mut buf)
+            * COLOR_SKILL_MULTIPLIER
+            * DIFFICULTY_MULTIPLIER;
+
+        skills.rhythm.copy_strain_peaks(&mut buf);
+        let rhythm_rating = skills.rhythm.difficulty_value(&mut buf)
+            * RHYTHM_SKILL_MULTIPLIER
+            * DIFFICULTY_MULTIPLIER;
+
+        skills.stamina.copy_strain_peaks(&mut buf);
+        let stamina_rating = skills.stamina.difficulty_value(&mut buf)
+            * STAMINA_SKILL_MULTIPLIER
+            * DIFFICULTY_MULTIPLIER;
+
+        let combined_rating = combined_difficulty(&mut buf, &skills) * DIFFICULTY_MULTIPLIER;
+        let stars = rescale(combined_rating * 1.4);
+
+        let mut od = self.map.od as f64;
+
+        if self.mods.hr() {
+            od = (od * 1.4).min(10.0);
+        } else if self.mods.ez() {
+            od *= 0.5;
+        }
+
+        let hit_window = difficulty_range(od, 20.0, 35.0, 50.0).floor() / clock_rate;
+
+        TaikoDifficultyAttributes {
+            stamina: stamina_rating,
+            rhythm: rhythm_rating,
+            color: color_rating,
+            peak: combined_rating,
+            hit_window,
+            stars,
+            max_combo: n_circles,
+            n_circles,
+            n_sliders,
+            n_spinners,
+        }
+    }
+}
+
+fn calculate_skills(map: &Beatmap, take: usize, clock_rate: f64) -> Skills {
+    let mut skills = Skills::new();
+
+    let mut diff_objects: Vec<_> = map
+        .taiko_objects()
+        .take(take)
+        .skip(2)
+        .zip(map.taiko_objects().skip(1))
+        .zip(map.taiko_objects())
+        .enumerate()
+        .map(|(idx, ((base, prev), prev_prev))| {
+            DifficultyObject::new(idx, base, prev, prev_prev, clock_rate)
+        })
+        .collect();
+
+    assign_color_difficulties(&mut diff_objects);
+    assign_key_prev_times(&mut diff_objects);
+
+    let mut diff_objects = diff_objects.iter();
+
+    // Handle first element distinctly
+    let h = match diff_objects.next() {
+        Some(h) => h,
+        None => return skills,
+    };
+
+    let mut curr_section_end = (h.start_time / SECTION_LEN).ceil() * SECTION_LEN;
+    skills.process(h);
+
+    // Handle all other objects
+    for h in diff_objects {
+        while h.start_time > curr_section_end {
+            skills.save_peak_and_start_new_section(curr_section_end);
+            curr_section_end += SECTION_LEN;
+        }
+
+        skills.process(h);
+    }
+
+    skills.save_current_peak();
+
+    skills
+}
+
+#[inline]
+fn rescale(stars: f64) -> f64 {
+    if stars < 0.0 {
+        stars
+    } else {
+        10.43 * (stars / 8.0).ln_1p()
+    }
+}
+
+/// Combine the section peaks of all skills and sum them up like a single skill.
+fn combined_difficulty(peaks: &mut Vec<f64>, skills: &Skills) -> f64 {
+    peaks.clear();
+
+    let iter = skills
+        .color
+        .strain_peaks
+        .iter()
+        .zip(skills.rhythm.strain_peaks.iter())
+        .zip(skills.stamina.strain_peaks.iter())
+        .map(|((&color, &rhythm), &stamina)| {
+            let color_peak = color * COLOR_SKILL_MULTIPLIER;
+            let rhythm_peak = rhythm * RHYTHM_SKILL_MULTIPLIER;
+            let stamina_peak = stamina * STAMINA_SKILL_MULTIPLIER;
+
+            let peak = norm(1.5, color_peak, stamina_peak);
+
+            norm(2.0, peak, rhythm_peak)
+        })
+        // * Sections with 0 strain are excluded to avoid worst-case time complexity of the following sort.
+        .filter(|&peak| peak > 0.0);
+
+    peaks.extend(iter);
+    peaks.sort_unstable_by(|a, b| b.partial_cmp(a).unwrap_or(Ordering::Equal));
+
+    let mut difficulty = StrainSum::default();
+    let mut weight = 1.0;
+
+    for strain in peaks {
+        difficulty += *strain * weight;
+        weight *= 0.9;
+    }
+
+    difficulty.value()
+}
+
+#[inline]
+fn norm(p: f64, a: f64, b: f64) -> f64 {
+    (a.powf(p) + b.powf(p)).powf(p.recip())
+}
+
+/// The result of a difficulty calculation on an osu!taiko map.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct TaikoDifficultyAttributes {
+    /// The difficulty of the stamina skill.
+    pub stamina: f64,
+    /// The difficulty of the rhythm skill.
+    pub rhythm: f64,
+    /// The difficulty of the color skill.
+    pub color: f64,
+    /// The difficulty of the combined section peaks of all skills.
+    pub peak: f64,
+    /// The hit window of a 300 in milliseconds, already divided by the clock rate.
+    pub hit_window: f64,
+    /// The final star rating.
+    pub stars: f64,
+    /// The maximum combo.
+    pub max_combo: usize,
+    /// The amount of notes.
+    pub n_circles: usize,
+    /// The amount of drumrolls.
+    pub n_sliders: usize,
+    /// The amount of swells.
+    pub n_spinners: usize,
+}
+
+impl TaikoDifficultyAttributes {
+    /// Return the maximum combo.
+    #[inline]
+    pub fn max_combo(&self) -> usize {
+        self.max_combo
+    }
+}
+
+/// The result of a performance calculation on an osu!taiko map.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct TaikoPerformanceAttributes {
+    /// The difficulty attributes that were used for the performance calculation
+    pub difficulty: TaikoDifficultyAttributes,
+    /// The final performance points.
+    pub pp: f64,
+    /// The accuracy portion of the final pp.
+    pub pp_acc: f64,
+    /// The strain portion of the final pp.
+    pub pp_strain: f64,
+    /// The misses scaled up for maps with less than 1000 hits.
+    pub effective_miss_count: f64,
+}
+
+impl TaikoPerformanceAttributes {
+    /// Return the star value.
+    #[inline]
+    pub fn stars(&self) -> f64 {
+        self.difficulty.stars
+    }
+
+    /// Return the performance point value.
+    #[inline]
+    pub fn pp(&self) -> f64 {
+        self.pp
+    }
+
+    /// Return the maximum combo of the map.
+    #[inline]
+    pub fn max_combo(&self) -> usize {
+        self.difficulty.max_combo
+    }
+}
+
+impl From<TaikoPerformanceAttributes> for TaikoDifficultyAttributes {
+    fn from(attributes: TaikoPerformanceAttributes) -> Self {
+        attributes.difficulty
+    }
+}
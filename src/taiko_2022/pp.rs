@@ -0,0 +1,413 @@
+use std::borrow::Cow;
+
+use rosu_pp::{Beatmap, Mods};
+
+use crate::{
+    convert,
+    unranked::UnrankedConfig,
+    util::{cache::DifficultyCache, mode::check_mode},
+    Error, IfFcAttributes, ScoreState,
+};
+
+use super::{TaikoDifficultyAttributes, TaikoPerformanceAttributes, TaikoStars, MODE};
+
+/// Performance calculator on osu!taiko maps.
+///
+/// # Example
+///
+/// ```
+/// use rosu_pp::Beatmap;
+/// use rosu_pp_older::taiko_2022::TaikoPP;
+///
+/// # /*
+/// let map: Beatmap = ...
+/// # */
+/// # let map = Beatmap::default();
+///
+/// let pp_result = TaikoPP::new(&map)
+///     .mods(8 + 64) // HDDT
+///     .combo(1234)
+///     .misses(1)
+///     .accuracy(98.5)
+///     .calculate();
+///
+/// println!("PP: {} | Stars: {}", pp_result.pp(), pp_result.stars());
+///
+/// let next_result = TaikoPP::new(&map)
+///     .attributes(pp_result)  // reusing previous results for performance
+///     .mods(8 + 64)           // has to be the same to reuse attributes
+///     .accuracy(99.5)
+///     .calculate();
+///
+/// println!("PP: {} | Stars: {}", next_result.pp(), next_result.stars());
+/// ```
+#[derive(Clone, Debug)]
+#[allow(clippy::upper_case_acronyms)]
+pub struct TaikoPP<'map> {
+    map: Cow<'map, Beatmap>,
+    attributes: Option<TaikoDifficultyAttributes>,
+    cache: DifficultyCache<TaikoDifficultyAttributes>,
+    mods: u32,
+    combo: Option<usize>,
+    acc: f64,
+    passed_objects: Option<usize>,
+
+    pub(crate) n300: Option<usize>,
+    pub(crate) n100: Option<usize>,
+    pub(crate) n_misses: usize,
+
+    unranked: UnrankedConfig,
+}
+
+impl<'map> TaikoPP<'map> {
+    /// Create a new performance calculator for osu!taiko maps.
+    #[inline]
+    pub fn new(map: &'map Beatmap) -> Self {
+        Self {
+            map: convert::taiko(map),
+            attributes: None,
+            cache: DifficultyCache::default(),
+            mods: 0,
+            combo: None,
+            acc: 1.0,
+            n_misses: 0,
+            passed_objects: None,
+            n300: None,
+            n100: None,
+
+            unranked: UnrankedConfig::default(),
+        }
+    }
+
+    /// Provide the result of a previous difficulty or performance calculation.
+    /// If you already calculated the attributes for the current map-mod combination,
+    /// be sure to put them in here so that they don't have to be recalculated.
+    #[inline]
+    pub fn attributes(mut self, attributes: impl TaikoAttributeProvider) -> Self {
+        if let Some(attributes) = attributes.attributes() {
+            self.attributes.replace(attributes);
+        }
+
+        self
+    }
+
+    /// Specify mods through their bit values.
+    ///
+    /// See [https://github.com/ppy/osu-api/wiki#mods](https://github.com/ppy/osu-api/wiki#mods)
+    #[inline]
+    pub fn mods(mut self, mods: u32) -> Self {
+        self.mods = mods;
+        self.cache.reset();
+
+        self
+    }
+
+    /// Specify mods through their acronyms, e.g. `"HDDT"`.
+    ///
+    /// Returns an error for unknown acronyms and for mods
+    /// that did not exist yet at the time of this version.
+    #[inline]
+    pub fn mods_str(self, acronyms: &str) -> Result<Self, Error> {
+        super::parse_mods(acronyms).map(|mods| self.mods(mods))
+    }
+
+    /// Specify the max combo of the play.
+    #[inline]
+    pub fn combo(mut self, combo: usize) -> Self {
+        self.combo.replace(combo);
+
+        self
+    }
+
+    /// Specify the amount of 300s of a play.
+    #[inline]
+    pub fn n300(mut self, n300: usize) -> Self {
+        self.n300.replace(n300);
+
+        self
+    }
+
+    /// Specify the amount of 100s of a play.
+    #[inline]
+    pub fn n100(mut self, n100: usize) -> Self {
+        self.n100.replace(n100);
+
+        self
+    }
+
+    /// Specify the amount of misses of the play.
+    #[inline]
+    pub fn misses(mut self, n_misses: usize) -> Self {
+        self.n_misses = n_misses.min(self.map.n_circles as usize);
+
+        self
+    }
+
+    /// Set the accuracy between 0.0 and 100.0.
+    #[inline]
+    pub fn accuracy(mut self, acc: f64) -> Self {
+        self.acc = acc / 100.0;
+        self.n300.take();
+        self.n100.take();
+
+        self
+    }
+
+    /// Amount of passed objects for partial plays, e.g. a fail.
+    #[inline]
+    pub fn passed_objects(mut self, passed_objects: usize) -> Self {
+        self.passed_objects.replace(passed_objects);
+        self.cache.reset();
+
+        self
+    }
+
+    /// Provide parameters through a [`ScoreState`].
+    #[inline]
+    pub fn state(mut self, state: impl Into<ScoreState>) -> Self {
+        let ScoreState {
+            max_combo,
+            n300,
+            n100,
+            n_misses,
+            ..
+        } = state.into();
+
+        self.combo = Some(max_combo);
+        self.n300 = Some(n300);
+        self.n100 = Some(n100);
+        self.n_misses = n_misses;
+
+        self
+    }
+
+    /// Specify whether [`try_calculate`](Self::try_calculate) should accept mods
+    /// that bancho never awarded pp for, i.e. AT, CN, TP, and RX.
+    ///
+    /// Useful for private servers, defaults to `false`.
+    #[inline]
+    pub fn allow_unranked(mut self, allow_unranked: bool) -> Self {
+        self.unranked.allow_unranked = allow_unranked;
+
+        self
+    }
+
+    /// Same as [`calculate`](Self::calculate) but returns an error if the map can
+    /// not be calculated by this version or if the mods contain unranked mods.
+    pub fn try_calculate(self) -> Result<TaikoPerformanceAttributes, Error> {
+        check_mode(self.map.mode, MODE)?;
+        self.unranked.check(self.mods)?;
+
+        Ok(self.calculate())
+    }
+
+    /// Calculate the performance of the score and of its full combo.
+    ///
+    /// For the full combo, the misses are counted as 300s and the combo is the map's
+    /// max combo. If only the accuracy was specified, it is kept as it is.
+    pub fn calculate_if_fc(self) -> IfFcAttributes<TaikoPerformanceAttributes> {
+        let fc = self.if_fc();
+
+        IfFcAttributes {
+            score: self.calculate(),
+            fc: fc.calculate(),
+        }
+    }
+
+    /// Same as [`calculate_if_fc`](Self::calculate_if_fc) but returns an error if the map can
+    /// not be calculated by this version or if the mods contain unranked mods.
+    pub fn try_calculate_if_fc(self) -> Result<IfFcAttributes<TaikoPerformanceAttributes>, Error> {
+        check_mode(self.map.mode, MODE)?;
+        self.unranked.check(self.mods)?;
+
+        Ok(self.calculate_if_fc())
+    }
+
+    fn if_fc(&self) -> Self {
+        let mut fc = self.clone();
+        fc.combo = None;
+        fc.n_misses = 0;
+
+        if let Some(ref mut n300) = fc.n300 {
+            *n300 += self.n_misses;
+        }
+
+        fc
+    }
+
+    /// Calculate all performance related values, including pp and stars.
+    ///
+    /// Maps of other modes, except for converted osu!standard maps, result in default attributes,
+    /// [`try_calculate`](Self::try_calculate) returns an error for them instead.
+    pub fn calculate(mut self) -> TaikoPerformanceAttributes {
+        if check_mode(self.map.mode, MODE).is_err() {
+            return TaikoPerformanceAttributes::default();
+        }
+
+        let attributes = self.attributes.take().unwrap_or_else(|| {
+            self.cache.get_or_insert_with(|| {
+                TaikoStars::new(&self.map)
+                    .mods(self.mods)
+                    .passed_objects(self.passed_objects.unwrap_or(usize::MAX))
+                    .calculate()
+            })
+        });
+
+        let total = attributes.max_combo;
+        let misses = self.n_misses.min(total);
+
+        let n_hits = if self.n300.or(self.n100).is_some() {
+            let mut n300 = self.n300.unwrap_or(0).min(total - misses);
+            let mut n100 = self.n100.unwrap_or(0).min(total - n300 - misses);
+
+            let given = n300 + n100 + misses;
+            let missing = total - given;
+
+            match (self.n300, self.n100) {
+                (Some(_), Some(_)) => n300 += missing,
+                (Some(_), None) => n100 += missing,
+                (None, Some(_)) => n300 += missing,
+                (None, None) => unreachable!(),
+            };
+
+            self.acc = (2 * n300 + n100) as f64 / (2 * (n300 + n100 + misses)) as f64;
+
+            n300 + n100
+        } else {
+            total - misses
+        };
+
+        let inner = TaikoPPInner {
+            attributes,
+            mods: self.mods,
+            acc: self.acc,
+            n_misses: misses,
+            n_hits,
+        };
+
+        inner.calculate()
+    }
+}
+
+struct TaikoPPInner {
+    attributes: TaikoDifficultyAttributes,
+    mods: u32,
+    acc: f64,
+    n_misses: usize,
+    n_hits: usize,
+}
+
+impl TaikoPPInner {
+    fn calculate(self) -> TaikoPerformanceAttributes {
+        // * The effectiveMissCount is calculated by gaining a ratio for totalSuccessfulHits
+        // * and increasing the miss penalty for shorter object counts lower than 1000.
+        let effective_miss_count = if self.n_hits > 0 {
+            (1000.0 / self.n_hits as f64).max(1.0) * self.n_misses as f64
+        } else {
+            0.0
+        };
+
+        let mut multiplier = 1.13;
+
+        if self.mods.hd() {
+            multiplier *= 1.075;
+        }
+
+        if self.mods.ez() {
+            multiplier *= 0.975;
+        }
+
+        let strain_value = self.compute_strain_value(effective_miss_count);
+        let acc_value = self.compute_accuracy_value();
+
+        let pp = (strain_value.powf(1.1) + acc_value.powf(1.1)).powf(1.0 / 1.1) * multiplier;
+
+        TaikoPerformanceAttributes {
+            difficulty: self.attributes,
+            pp,
+            pp_acc: acc_value,
+            pp_strain: strain_value,
+            effective_miss_count,
+        }
+    }
+
+    fn compute_strain_value(&self, effective_miss_count: f64) -> f64 {
+        let attributes = &self.attributes;
+        let exp_base = 5.0 * (attributes.stars / 0.115).max(1.0) - 4.0;
+        let mut strain = exp_base.powf(2.25) / 1150.0;
+
+        // Longer maps are worth more
+        let len_bonus = 1.0 + 0.1 * (attributes.max_combo as f64 / 1500.0).min(1.0);
+        strain *= len_bonus;
+
+        // Penalize misses exponentially
+        strain *= 0.986_f64.powf(effective_miss_count);
+
+        // EZ penalty
+        if self.mods.ez() {
+            strain *= 0.985;
+        }
+
+        // HD bonus
+        if self.mods.hd() {
+            strain *= 1.025;
+        }
+
+        // HR bonus
+        if self.mods.hr() {
+            strain *= 1.05;
+        }
+
+        // FL bonus
+        if self.mods.fl() {
+            strain *= 1.05 * len_bonus;
+        }
+
+        // Scale with accuracy
+        strain * self.acc * self.acc
+    }
+
+    #[inline]
+    fn compute_accuracy_value(&self) -> f64 {
+        let attributes = &self.attributes;
+
+        if attributes.hit_window <= 0.0 {
+            return 0.0;
+        }
+
+        let mut acc_value = (60.0 / attributes.hit_window).powf(1.1)
+            * self.acc.powi(8)
+            * attributes.stars.powf(0.4)
+            * 27.0;
+
+        let len_bonus = (attributes.max_combo as f64 / 1500.0).powf(0.3).min(1.15);
+        acc_value *= len_bonus;
+
+        // Slight HDFL bonus, clamped to prevent negative values
+        if self.mods.fl() && self.mods.hd() {
+            acc_value *= (1.075 * len_bonus).max(1.05);
+        }
+
+        acc_value
+    }
+}
+
+/// Abstract type to provide flexibility when passing difficulty attributes to a performance calculation.
+pub trait TaikoAttributeProvider {
+    /// Provide the actual difficulty attributes.
+    fn attributes(self) -> Option<TaikoDifficultyAttributes>;
+}
+
+impl TaikoAttributeProvider for TaikoDifficultyAttributes {
+    #[inline]
+    fn attributes(self) -> Option<TaikoDifficultyAttributes> {
+        Some(self)
+    }
+}
+
+impl TaikoAttributeProvider for TaikoPerformanceAttributes {
+    #[inline]
+    fn attributes(self) -> Option<TaikoDifficultyAttributes> {
+        Some(self.difficulty)
+    }
+}
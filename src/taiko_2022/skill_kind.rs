@@ -0,0 +1,142 @@
+use crate::util::limited_queue::LimitedQueue;
+
+use super::{DifficultyObject, HitObjectRhythm};
+
+use std::ops::Index;
+
+const RHYTHM_STRAIN_DECAY: f64 = 0.96;
+const RHYTHM_HISTORY_MAX_LEN: usize = 8;
+
+#[derive(Clone, Debug)]
+pub(crate) enum SkillKind {
+    Color,
+    Rhythm {
+        rhythm_history: LimitedQueue<(usize, &'static HitObjectRhythm)>, // (idx, rhythm)
+        notes_since_rhythm_change: usize,
+        current_strain: f64,
+    },
+    Stamina,
+}
+
+impl SkillKind {
+    #[inline]
+    pub(crate) fn rhythm() -> Self {
+        Self::Rhythm {
+            rhythm_history: LimitedQueue::new(RHYTHM_HISTORY_MAX_LEN),
+            notes_since_rhythm_change: 0,
+            current_strain: 0.0,
+        }
+    }
+
+    pub(crate) fn strain_value_of(&mut self, current: &DifficultyObject<'_>) -> f64 {
+        match self {
+            Self::Color => current.color,
+            Self::Rhythm {
+                rhythm_history,
+                notes_since_rhythm_change,
+                current_strain,
+            } => {
+                let base_is_circle = current.base.h.is_circle();
+
+                if !base_is_circle {
+                    *current_strain = 0.0;
+                    *notes_since_rhythm_change = 0;
+
+                    return 0.0;
+                }
+
+                *current_strain *= RHYTHM_STRAIN_DECAY;
+                *notes_since_rhythm_change += 1;
+
+                if current.rhythm.difficulty.abs() <= f64::EPSILON {
+                    return 0.0;
+                }
+
+                let mut strain = current.rhythm.difficulty;
+
+                rhythm_history.push((current.idx, current.rhythm));
+
+                let mut reps_penalty = 1.0;
+
+                for most_recent_patterns_to_compare in 2..=RHYTHM_HISTORY_MAX_LEN / 2 {
+                    let iter = (0..rhythm_history
+                        .len()
+                        .saturating_sub(most_recent_patterns_to_compare))
+                        .rev();
+
+                    for start in iter {
+                        let different_pattern = (0..most_recent_patterns_to_compare).any(|i| {
+                            let to_compare =
+                                rhythm_history.len() + i - most_recent_patterns_to_compare;
+
+                            rhythm_history.index(start + i).1 != rhythm_history.index(to_compare).1
+                        });
+
+                        if different_pattern {
+                            continue;
+                        }
+
+                        reps_penalty *=
+                            repetition_penalty(current.idx - rhythm_history.index(start).0);
+
+                        break;
+                    }
+                }
+
+                let speed_penalty = if current.delta < 80.0 {
+                    1.0
+                } else if current.delta < 210.0 {
+                    (1.4 - 0.005 * current.delta).max(0.0)
+                } else {
+                    *current_strain = 0.0;
+                    *notes_since_rhythm_change = 0;
+
+                    0.0
+                };
+
+                strain *= reps_penalty;
+                strain *= pattern_len_penalty(*notes_since_rhythm_change);
+                strain *= speed_penalty;
+
+                *notes_since_rhythm_change = 0;
+                *current_strain += strain;
+
+                *current_strain
+            }
+            Self::Stamina => {
+                if !current.base.h.is_circle() {
+                    return 0.0;
+                }
+
+                // * Find the previous hit object hit by the current key, which is two notes of the same colour prior.
+                match current.key_prev_time {
+                    // * Add a base strain to all objects
+                    Some(key_prev_time) => 0.5 + speed_bonus(current.start_time - key_prev_time),
+                    // * There is no previous hit object hit by the current key
+                    None => 0.0,
+                }
+            }
+        }
+    }
+}
+
+#[inline]
+fn pattern_len_penalty(pattern_len: usize) -> f64 {
+    let pattern_len = pattern_len as f64;
+    let short_pattern_penalty = (0.15 * pattern_len).min(1.0);
+    let long_pattern_penalty = (2.5 - 0.15 * pattern_len).clamp(0.0, 1.0);
+
+    short_pattern_penalty.min(long_pattern_penalty)
+}
+
+#[inline]
+fn speed_bonus(interval: f64) -> f64 {
+    // * Cap to 600bpm 1/4, 25ms note interval, 50ms key interval
+    // * This a is temporary measure to prevent absurdly high speed mono convert maps being rated too high
+    30.0 / interval.max(50.0)
+}
+
+#[inline]
+fn repetition_penalty(notes_since: usize) -> f64 {
+    (0.032 * notes_since as f64).min(1.0)
+}
@@ -7,31 +7,213 @@ use difficulty_object::DifficultyObject;
 pub use pp::*;
 use strain::Strain;
 
-use rosu_pp::{Beatmap, Mods};
+use rosu_pp::{taiko::TaikoScoreState, Beatmap, GameMode, Mods};
+
+use crate::{
+    convert,
+    mods::{self, ScoreMultipliers},
+    object_count::count_objects,
+    util::mode::check_mode,
+    Error,
+};
 
 const SECTION_LEN: f32 = 400.0;
 
 const STAR_SCALING_FACTOR: f32 = 0.04125;
 
+/// The approximate date at which this version went live, formatted as `YYYY-MM-DD`.
+pub const VERSION_DATE: &str = "2014-01-01";
+
+/// The name of this version within its mode.
+pub const VERSION_NAME: &str = "ppv1";
+
+/// A short description of what changed in this version.
+pub const VERSION_DESCRIPTION: &str = "The original ppv2 strain and accuracy values of osu!taiko.";
+
+/// The mode this version calculates, i.e. osu!taiko.
+pub const MODE: GameMode = GameMode::Taiko;
+
+crate::calculator::difficulty_calculator!(TaikoCalculator, TaikoPpv1);
+
+/// Parse mod acronyms like `"HDDT"` or `"HD,DT"` into the mods value of this version.
+///
+/// Mods that did not exist yet at the time of this version cause an error.
+#[inline]
+pub fn parse_mods(acronyms: &str) -> Result<u32, Error> {
+    mods::parse_available(acronyms, mods::BEFORE_SCORE_V2)
+}
+
+/// The score multiplier of the mods in this version.
+#[inline]
+pub fn score_multiplier(mods: u32) -> f32 {
+    ScoreMultipliers::TAIKO.multiplier(mods)
+}
+
+/// Calculate the difficulty and the performance of a score in one pass.
+///
+/// Calling [`stars`] and then [`TaikoPP`] without passing the attributes along calculates
+/// the difficulty twice, this function avoids that.
+pub fn stars_and_pp(
+    map: &Beatmap,
+    mods: u32,
+    state: TaikoScoreState,
+) -> (TaikoDifficultyAttributes, TaikoPerformanceAttributes) {
+    let TaikoScoreState {
+        max_combo,
+        n300,
+        n100,
+        n_misses,
+    } = state;
+
+    let performance = TaikoPP::new(map)
+        .mods(mods)
+        .combo(max_combo)
+        .n300(n300)
+        .n100(n100)
+        .misses(n_misses)
+        .calculate();
+
+    (performance.difficulty.clone(), performance)
+}
+
+/// The maximum combo of an osu!taiko map, i.e. the amount of circles.
+///
+/// Considerably cheaper than calculating the stars, e.g. when validating scores.
+#[inline]
+pub fn max_combo(map: &Beatmap) -> usize {
+    convert::taiko(map).n_circles as usize
+}
+
+/// Difficulty calculator on osu!taiko maps.
+///
+/// # Example
+///
+/// ```
+/// use rosu_pp::Beatmap;
+/// use rosu_pp_older::taiko_ppv1::TaikoStars;
+///
+/// # /*
+/// let map: Beatmap = ...
+/// # */
+/// # let map = Beatmap::default();
+///
+/// let difficulty_attrs = TaikoStars::new(&map)
+///     .mods(8 + 64) // HDDT
+///     .calculate();
+///
+/// println!("Stars: {}", difficulty_attrs.stars);
+/// ```
+#[derive(Clone, Debug)]
+pub struct TaikoStars<'map> {
+    map: &'map Beatmap,
+    mods: u32,
+    passed_objects: Option<usize>,
+    clock_rate: Option<f64>,
+}
+
+impl<'map> TaikoStars<'map> {
+    /// Create a new difficulty calculator for osu!taiko maps.
+    #[inline]
+    pub fn new(map: &'map Beatmap) -> Self {
+        Self {
+            map,
+            mods: 0,
+            passed_objects: None,
+            clock_rate: None,
+        }
+    }
+
+    /// Specify mods through their bit values.
+    ///
+    /// See [https://github.com/ppy/osu-api/wiki#mods](https://github.com/ppy/osu-api/wiki#mods)
+    #[inline]
+    pub fn mods(mut self, mods: u32) -> Self {
+        self.mods = mods;
+
+        self
+    }
+
+    /// Specify mods through their acronyms, e.g. `"HDDT"`.
+    ///
+    /// Returns an error for unknown acronyms and for mods
+    /// that did not exist yet at the time of this version.
+    #[inline]
+    pub fn mods_str(self, acronyms: &str) -> Result<Self, Error> {
+        parse_mods(acronyms).map(|mods| self.mods(mods))
+    }
+
+    /// Amount of passed objects for partial plays, e.g. a fail.
+    #[inline]
+    pub fn passed_objects(mut self, passed_objects: usize) -> Self {
+        self.passed_objects = Some(passed_objects);
+
+        self
+    }
+
+    /// Adjust the clock rate used in the calculation, e.g. `1.4` for a lazer DT
+    /// score that was set at 1.4x.
+    ///
+    /// If none is specified, the clock rate of the mods is used.
+    #[inline]
+    pub fn clock_rate(mut self, clock_rate: f64) -> Self {
+        self.clock_rate = Some(clock_rate);
+
+        self
+    }
+
+    /// Calculate all difficulty related values, including stars.
+    ///
+    /// Maps of other modes, except for converted osu!standard maps, result in default attributes.
+    #[inline]
+    pub fn calculate(self) -> TaikoDifficultyAttributes {
+        let clock_rate = self.clock_rate.unwrap_or_else(|| self.mods.clock_rate());
+
+        stars_with_clock_rate(self.map, self.passed_objects, clock_rate)
+    }
+}
+
 /// Star calculation for osu!taiko maps.
 ///
 /// In case of a partial play, e.g. a fail, one can specify the amount of passed objects.
+///
+/// Maps of other modes, except for converted osu!standard maps, result in default attributes.
 pub fn stars(
     map: &Beatmap,
     mods: impl Mods,
     passed_objects: Option<usize>,
 ) -> TaikoDifficultyAttributes {
+    stars_with_clock_rate(map, passed_objects, mods.clock_rate())
+}
+
+/// Star calculation for osu!taiko maps with a custom clock rate.
+///
+/// The clock rate replaces the one of DT or HT, e.g. `1.4` for a lazer DT
+/// score that was set at 1.4x. Other mods do not affect the stars of this version.
+pub fn stars_with_clock_rate(
+    map: &Beatmap,
+    passed_objects: Option<usize>,
+    clock_rate: f64,
+) -> TaikoDifficultyAttributes {
+    if check_mode(map.mode, MODE).is_err() {
+        return TaikoDifficultyAttributes::default();
+    }
+
+    let map = convert::taiko(map);
     let take = passed_objects.unwrap_or(map.hit_objects.len());
     let max_combo = map.n_circles as usize;
+    let (n_circles, n_sliders, n_spinners) = count_objects(map.hit_objects.iter().take(take));
 
     if take < 2 {
         return TaikoDifficultyAttributes {
             stars: 0.0,
             max_combo,
+            n_circles,
+            n_sliders,
+            n_spinners,
         };
     }
 
-    let clock_rate = mods.clock_rate() as f32;
+    let clock_rate = clock_rate as f32;
     let section_len = SECTION_LEN * clock_rate;
 
     // No strain for first object
@@ -74,16 +256,32 @@ pub fn stars(
 
     let stars = (strain.difficulty_value() * STAR_SCALING_FACTOR) as f64;
 
-    TaikoDifficultyAttributes { stars, max_combo }
+    TaikoDifficultyAttributes {
+        stars,
+        max_combo,
+        n_circles,
+        n_sliders,
+        n_spinners,
+    }
 }
 
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct TaikoDifficultyAttributes {
     /// The final star rating.
     pub stars: f64,
     /// The maximum combo.
     pub max_combo: usize,
+    /// The amount of notes.
+    pub n_circles: usize,
+    /// The amount of drumrolls.
+    pub n_sliders: usize,
+    /// The amount of swells.
+    pub n_spinners: usize,
 }
 
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct TaikoPerformanceAttributes {
     /// The difficulty attributes that were used for the performance calculation
     pub difficulty: TaikoDifficultyAttributes,
@@ -93,4 +291,6 @@ pub struct TaikoPerformanceAttributes {
     pub pp_acc: f64,
     /// The strain portion of the final pp.
     pub pp_strain: f64,
+    /// The multiplier that was applied due to the NoFail mod.
+    pub nf_multiplier: f64,
 }
@@ -1,6 +1,14 @@
-use crate::util::math::difficulty_range;
+use crate::{
+    convert,
+    object_count::count_objects,
+    unranked::UnrankedConfig,
+    util::{cache::DifficultyCache, math::difficulty_range, mode::check_mode},
+    Error, IfFcAttributes, ScoreState,
+};
 
-use super::{stars, TaikoDifficultyAttributes, TaikoPerformanceAttributes};
+use super::{stars, TaikoDifficultyAttributes, TaikoPerformanceAttributes, MODE};
+
+use std::borrow::Cow;
 
 use rosu_pp::{Beatmap, DifficultyAttributes, Mods, PerformanceAttributes};
 
@@ -33,8 +41,9 @@ use rosu_pp::{Beatmap, DifficultyAttributes, Mods, PerformanceAttributes};
 /// ```
 #[derive(Clone, Debug)]
 pub struct TaikoPP<'m> {
-    map: &'m Beatmap,
+    map: Cow<'m, Beatmap>,
     stars: Option<f32>,
+    cache: DifficultyCache<f32>,
     mods: u32,
     max_combo: usize,
     combo: Option<usize>,
@@ -44,22 +53,29 @@ pub struct TaikoPP<'m> {
 
     n300: Option<usize>,
     n100: Option<usize>,
+
+    unranked: UnrankedConfig,
 }
 
 impl<'m> TaikoPP<'m> {
     #[inline]
     pub fn new(map: &'m Beatmap) -> Self {
+        let map = convert::taiko(map);
+
         Self {
+            max_combo: map.n_circles as usize,
             map,
             stars: None,
+            cache: DifficultyCache::default(),
             mods: 0,
-            max_combo: map.n_circles as usize,
             combo: None,
             acc: 1.0,
             n_misses: 0,
             passed_objects: None,
             n300: None,
             n100: None,
+
+            unranked: UnrankedConfig::default(),
         }
     }
 
@@ -83,10 +99,20 @@ impl<'m> TaikoPP<'m> {
     #[inline]
     pub fn mods(mut self, mods: u32) -> Self {
         self.mods = mods;
+        self.cache.reset();
 
         self
     }
 
+    /// Specify mods through their acronyms, e.g. `"HDDT"`.
+    ///
+    /// Returns an error for unknown acronyms and for mods
+    /// that did not exist yet at the time of this version.
+    #[inline]
+    pub fn mods_str(self, acronyms: &str) -> Result<Self, Error> {
+        super::parse_mods(acronyms).map(|mods| self.mods(mods))
+    }
+
     /// Specify the max combo of the play.
     #[inline]
     pub fn combo(mut self, combo: usize) -> Self {
@@ -133,15 +159,98 @@ impl<'m> TaikoPP<'m> {
     #[inline]
     pub fn passed_objects(mut self, passed_objects: usize) -> Self {
         self.passed_objects.replace(passed_objects);
+        self.cache.reset();
+
+        self
+    }
+
+    /// Provide parameters through a [`ScoreState`].
+    #[inline]
+    pub fn state(mut self, state: impl Into<ScoreState>) -> Self {
+        let ScoreState {
+            max_combo,
+            n300,
+            n100,
+            n_misses,
+            ..
+        } = state.into();
+
+        self.combo = Some(max_combo);
+        self.n300 = Some(n300);
+        self.n100 = Some(n100);
+        self.n_misses = n_misses;
+
+        self
+    }
+
+    /// Specify whether [`try_calculate`](Self::try_calculate) should accept mods
+    /// that bancho never awarded pp for, i.e. AT, CN, TP, and RX.
+    ///
+    /// Useful for private servers, defaults to `false`.
+    #[inline]
+    pub fn allow_unranked(mut self, allow_unranked: bool) -> Self {
+        self.unranked.allow_unranked = allow_unranked;
 
         self
     }
 
+    /// Same as [`calculate`](Self::calculate) but returns an error if the map can
+    /// not be calculated by this version or if the mods contain unranked mods.
+    pub fn try_calculate(self) -> Result<TaikoPerformanceAttributes, Error> {
+        check_mode(self.map.mode, MODE)?;
+        self.unranked.check(self.mods)?;
+
+        Ok(self.calculate())
+    }
+
+    /// Calculate the performance of the score and of its full combo.
+    ///
+    /// For the full combo, the misses are counted as 300s and the combo is the map's
+    /// max combo. If only the accuracy was specified, it is kept as it is.
+    pub fn calculate_if_fc(self) -> IfFcAttributes<TaikoPerformanceAttributes> {
+        let fc = self.if_fc();
+
+        IfFcAttributes {
+            score: self.calculate(),
+            fc: fc.calculate(),
+        }
+    }
+
+    /// Same as [`calculate_if_fc`](Self::calculate_if_fc) but returns an error if the map can
+    /// not be calculated by this version or if the mods contain unranked mods.
+    pub fn try_calculate_if_fc(self) -> Result<IfFcAttributes<TaikoPerformanceAttributes>, Error> {
+        check_mode(self.map.mode, MODE)?;
+        self.unranked.check(self.mods)?;
+
+        Ok(self.calculate_if_fc())
+    }
+
+    fn if_fc(&self) -> Self {
+        let mut fc = self.clone();
+        fc.combo = None;
+        fc.n_misses = 0;
+
+        if let Some(ref mut n300) = fc.n300 {
+            *n300 += self.n_misses;
+        }
+
+        fc
+    }
+
     /// Returns an object which contains the pp and stars.
+    ///
+    /// Maps of other modes, except for converted osu!standard maps, result in default attributes,
+    /// [`try_calculate`](Self::try_calculate) returns an error for them instead.
     pub fn calculate(mut self) -> TaikoPerformanceAttributes {
-        let stars = self
-            .stars
-            .unwrap_or_else(|| stars(self.map, self.mods, self.passed_objects).stars as f32);
+        if check_mode(self.map.mode, MODE).is_err() {
+            return TaikoPerformanceAttributes::default();
+        }
+
+        let stars = self.stars.unwrap_or_else(|| {
+            self.cache.get_or_insert_with(|| {
+                stars(&self.map, self.mods, self.passed_objects).stars as f32
+            })
+        });
 
         if self.n300.or(self.n100).is_some() {
             let total = self.map.n_circles as usize;
@@ -165,9 +274,8 @@ impl<'m> TaikoPP<'m> {
 
         let mut multiplier = 1.1;
 
-        if self.mods.nf() {
-            multiplier *= 0.9;
-        }
+        let nf_multiplier = if self.mods.nf() { 0.9 } else { 1.0 };
+        multiplier *= nf_multiplier;
 
         if self.mods.hd() {
             multiplier *= 1.1;
@@ -178,14 +286,22 @@ impl<'m> TaikoPP<'m> {
 
         let pp = (strain_value.powf(1.1) + acc_value.powf(1.1)).powf(1.0 / 1.1) * multiplier;
 
+        let take = self.passed_objects.unwrap_or(self.map.hit_objects.len());
+        let (n_circles, n_sliders, n_spinners) =
+            count_objects(self.map.hit_objects.iter().take(take));
+
         TaikoPerformanceAttributes {
             difficulty: TaikoDifficultyAttributes {
                 stars: stars as f64,
                 max_combo: self.map.n_circles as usize,
+                n_circles,
+                n_sliders,
+                n_spinners,
             },
             pp: pp as f64,
             pp_acc: acc_value as f64,
             pp_strain: strain_value as f64,
+            nf_multiplier: nf_multiplier as f64,
         }
     }
 
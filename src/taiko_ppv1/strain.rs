@@ -1,5 +1,7 @@
 use super::DifficultyObject;
 
+use crate::util::sum::StrainSum;
+
 use std::cmp::Ordering;
 
 const RHYTHM_CHANGE_BASE_THRESHOLD: f32 = 0.2;
@@ -131,7 +133,7 @@ impl Strain {
 
     #[inline]
     pub(crate) fn difficulty_value(&mut self) -> f32 {
-        let mut difficulty = 0.0;
+        let mut difficulty = StrainSum::default();
         let mut weight = 1.0;
 
         self.strain_peaks
@@ -142,7 +144,7 @@ impl Strain {
             weight *= DECAY_WEIGHT;
         }
 
-        difficulty
+        difficulty.value()
     }
 }
 
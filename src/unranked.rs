@@ -0,0 +1,212 @@
+//! Opt-in handling for mods that were never ranked on bancho.
+//!
+//! By default every version treats these mods the way its official
+//! calculator did. Private servers that award pp for them anyway can
+//! pick one of the community conventions or plug in their own formula.
+
+use std::{fmt::Debug, sync::Arc};
+
+use rosu_pp::Mods;
+
+use crate::{
+    util::mods::{AUTOPILOT, AUTOPLAY, CINEMA, RELAX, TARGET_PRACTICE},
+    Error,
+};
+
+/// Mods that invalidate a score on bancho.
+const SCORE_INVALIDATING: u32 = AUTOPLAY | CINEMA | TARGET_PRACTICE;
+
+/// The individual pp values of a play before they are combined into the final pp.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct PpComponents {
+    /// The aim portion.
+    pub aim: f64,
+    /// The speed portion.
+    pub speed: f64,
+    /// The accuracy portion.
+    pub acc: f64,
+    /// The flashlight portion, always `0.0` for versions without a flashlight skill.
+    pub flashlight: f64,
+}
+
+/// Custom pp formula for Relax plays.
+pub trait RelaxFormula: Debug + Send + Sync {
+    /// Adjust the pp components of a Relax play.
+    fn adjust(&self, components: &mut PpComponents);
+}
+
+/// How the Relax mod should be handled by a performance calculation.
+#[derive(Clone, Debug)]
+pub enum RelaxMode {
+    /// Relax is treated like nomod.
+    Nomod,
+    /// Speed pp are dropped entirely.
+    ///
+    /// This is what the popular community servers did at the time since
+    /// tapping is done by the game so speed should not be rewarded.
+    NoSpeed,
+    /// The pp components are adjusted through a custom formula.
+    Custom(Arc<dyn RelaxFormula>),
+}
+
+impl RelaxMode {
+    pub(crate) fn adjust(&self, components: &mut PpComponents) {
+        match self {
+            Self::Nomod => {}
+            Self::NoSpeed => components.speed = 0.0,
+            Self::Custom(formula) => formula.adjust(components),
+        }
+    }
+}
+
+impl Default for RelaxMode {
+    #[inline]
+    fn default() -> Self {
+        Self::Nomod
+    }
+}
+
+/// Custom pp formula for Autopilot plays.
+pub trait AutopilotFormula: Debug + Send + Sync {
+    /// Adjust the pp components of an Autopilot play.
+    fn adjust(&self, components: &mut PpComponents);
+}
+
+/// How the Autopilot mod should be handled by a performance calculation.
+#[derive(Clone, Debug)]
+pub enum AutopilotMode {
+    /// Autopilot plays are rejected with [`Error::UnrankedMods`] when calculating
+    /// through `try_calculate` and are worth 0 pp otherwise.
+    Reject,
+    /// Aim pp are dropped entirely.
+    ///
+    /// This is what the popular community servers did at the time since
+    /// cursor movement is done by the game so aim should not be rewarded.
+    NoAim,
+    /// The pp components are adjusted through a custom formula.
+    Custom(Arc<dyn AutopilotFormula>),
+}
+
+impl AutopilotMode {
+    pub(crate) fn adjust(&self, components: &mut PpComponents) {
+        match self {
+            Self::Reject => *components = PpComponents::default(),
+            Self::NoAim => components.aim = 0.0,
+            Self::Custom(formula) => formula.adjust(components),
+        }
+    }
+}
+
+impl Default for AutopilotMode {
+    #[inline]
+    fn default() -> Self {
+        Self::Reject
+    }
+}
+
+/// The unranked mod configuration of a performance calculator.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct UnrankedConfig {
+    pub(crate) relax: Option<RelaxMode>,
+    pub(crate) autopilot: AutopilotMode,
+    pub(crate) allow_unranked: bool,
+}
+
+impl UnrankedConfig {
+    /// Check whether the mods can be handled with this configuration.
+    ///
+    /// Unless unranked mods are allowed, AT, CN, TP, and RX without a
+    /// configured [`RelaxMode`] are rejected since bancho never awarded pp for them.
+    pub(crate) fn check(&self, mods: u32) -> Result<(), Error> {
+        if !self.allow_unranked {
+            let mut unranked = mods & SCORE_INVALIDATING;
+
+            if mods.rx() && self.relax.is_none() {
+                unranked |= RELAX;
+            }
+
+            if unranked > 0 {
+                return Err(Error::UnrankedMods(unranked));
+            }
+        }
+
+        if mods & AUTOPILOT > 0 && matches!(self.autopilot, AutopilotMode::Reject) {
+            return Err(Error::UnrankedMods(AUTOPILOT));
+        }
+
+        Ok(())
+    }
+
+    /// Whether the pp components need to be adjusted for the mods.
+    pub(crate) fn applies_to(&self, mods: u32) -> bool {
+        (mods.rx() && self.relax.is_some()) || mods & AUTOPILOT > 0
+    }
+
+    pub(crate) fn adjust(&self, mods: u32, components: &mut PpComponents) {
+        if let Some(relax) = self.relax.as_ref().filter(|_| mods.rx()) {
+            relax.adjust(components);
+        }
+
+        if mods & AUTOPILOT > 0 {
+            self.autopilot.adjust(components);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn relax_no_speed() {
+        let mut components = PpComponents {
+            aim: 100.0,
+            speed: 80.0,
+            acc: 50.0,
+            flashlight: 0.0,
+        };
+
+        RelaxMode::NoSpeed.adjust(&mut components);
+
+        assert_eq!(components.speed, 0.0);
+        assert_eq!(components.aim, 100.0);
+    }
+
+    #[test]
+    fn autopilot_rejected_by_default() {
+        let config = UnrankedConfig::default();
+
+        assert_eq!(config.check(AUTOPILOT), Err(Error::UnrankedMods(AUTOPILOT)));
+        assert_eq!(config.check(8 + 64), Ok(()));
+    }
+
+    #[test]
+    fn autopilot_rejected_without_pp() {
+        let mut components = PpComponents {
+            aim: 100.0,
+            speed: 80.0,
+            acc: 50.0,
+            flashlight: 20.0,
+        };
+
+        UnrankedConfig::default().adjust(AUTOPILOT, &mut components);
+
+        assert_eq!(components, PpComponents::default());
+    }
+
+    #[test]
+    fn score_invalidating_mods() {
+        let mut config = UnrankedConfig::default();
+
+        assert_eq!(
+            config.check(8 + AUTOPLAY + RELAX),
+            Err(Error::UnrankedMods(AUTOPLAY + RELAX))
+        );
+
+        config.relax = Some(RelaxMode::Nomod);
+        assert_eq!(config.check(RELAX), Ok(()));
+
+        config.allow_unranked = true;
+        assert_eq!(config.check(CINEMA + TARGET_PRACTICE), Ok(()));
+    }
+}
@@ -0,0 +1,71 @@
+use std::sync::{Arc, Mutex};
+
+/// Difficulty attributes that are shared between clones of a pp calculator.
+///
+/// Calculating multiple scores on clones of the same calculator, e.g. for
+/// pp at 95%, 98%, 99%, and 100%, then only processes the map once.
+/// Setters that affect the difficulty have to call [`reset`](Self::reset)
+/// so that the calculator no longer shares attributes with its clones.
+#[derive(Debug)]
+pub(crate) struct DifficultyCache<A>(Arc<Mutex<Option<A>>>);
+
+impl<A: Clone> DifficultyCache<A> {
+    /// The cached attributes or, if there are none yet, the attributes of
+    /// `calculate` which are then cached.
+    ///
+    /// The lock is held while calculating so that concurrent clones
+    /// wait for the result instead of calculating it themselves.
+    pub(crate) fn get_or_insert_with(&self, calculate: impl FnOnce() -> A) -> A {
+        self.0
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get_or_insert_with(calculate)
+            .clone()
+    }
+
+    /// Cache the attributes, replacing previous attributes.
+    pub(crate) fn insert(&self, attributes: A) {
+        self.0
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .replace(attributes);
+    }
+
+    /// Detach from the attributes that are shared with clones.
+    pub(crate) fn reset(&mut self) {
+        self.0 = Arc::default();
+    }
+}
+
+impl<A> Clone for DifficultyCache<A> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<A> Default for DifficultyCache<A> {
+    #[inline]
+    fn default() -> Self {
+        Self(Arc::default())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn shared_between_clones() {
+        let cache = DifficultyCache::default();
+        let mut clone = cache.clone();
+
+        assert_eq!(cache.get_or_insert_with(|| 5.0), 5.0);
+        assert_eq!(clone.get_or_insert_with(|| 6.0), 5.0);
+
+        clone.reset();
+
+        assert_eq!(clone.get_or_insert_with(|| 6.0), 6.0);
+        assert_eq!(cache.get_or_insert_with(|| 7.0), 5.0);
+    }
+}
@@ -0,0 +1,213 @@
+//! Conversions between dates and days since 1970-01-01 as used for the dates of versions.
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// The days between 1970-01-01 and the given date.
+pub const fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    // https://howardhinnant.github.io/date_algorithms.html#days_from_civil
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = if year >= 0 { year } else { year - 399 } / 400;
+    let year_of_era = year - era * 400;
+    let month_idx = if month > 2 { month - 3 } else { month + 9 };
+    let day_of_year = (153 * month_idx + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// The year, month, and day of the given days since 1970-01-01.
+pub const fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    // https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let days = days + 719_468;
+    let era = if days >= 0 { days } else { days - 146_096 } / 146_097;
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_idx = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_idx + 2) / 5 + 1;
+    let month = if month_idx < 10 {
+        month_idx + 3
+    } else {
+        month_idx - 9
+    };
+    let year = year_of_era + era * 400 + (month <= 2) as i64;
+
+    (year, month, day)
+}
+
+/// The days between 1970-01-01 and a date formatted as `YYYY-MM-DD`.
+pub fn parse_days(date: &str) -> Option<i64> {
+    let mut parts = date.splitn(3, '-').map(str::parse::<i64>);
+
+    match (parts.next()?, parts.next()?, parts.next()?) {
+        (Ok(year), Ok(month), Ok(day)) => Some(days_from_civil(year, month, day)),
+        _ => None,
+    }
+}
+
+/// The days between 1970-01-01 and a loosely formatted date.
+///
+/// The year has four digits, the month is either a number or an english month name,
+/// and the parts may be separated by `-` or `_`, e.g. `2021-07`, `july2021`, or `2015_april`.
+/// Missing months and days default to the first one, see [`parse_loose_span`]
+/// for the last day of the date instead.
+pub fn parse_loose_days(date: &str) -> Option<i64> {
+    parse_loose_span(date).map(|(first, _)| first)
+}
+
+/// The first and the last day since 1970-01-01 of a loosely formatted date,
+/// see [`parse_loose_days`] for the format.
+///
+/// A date without day spans its whole month, e.g. `2021-07` spans from
+/// `2021-07-01` to `2021-07-31`, and a date without month spans its whole year.
+pub fn parse_loose_span(date: &str) -> Option<(i64, i64)> {
+    let (mut year, mut month, mut day) = (None, None, None);
+    let mut rest = date;
+
+    while !rest.is_empty() {
+        let first = rest.as_bytes()[0];
+
+        if first == b'-' || first == b'_' {
+            rest = &rest[1..];
+
+            continue;
+        }
+
+        let is_digit = first.is_ascii_digit();
+
+        let len = rest
+            .bytes()
+            .position(|byte| byte.is_ascii_digit() != is_digit || !byte.is_ascii_alphanumeric())
+            .unwrap_or(rest.len());
+
+        let (token, next) = rest.split_at(len);
+        rest = next;
+
+        if !is_digit {
+            if month.is_some() {
+                return None;
+            }
+
+            month = Some(month_from_name(token)?);
+        } else if token.len() == 4 && year.is_none() {
+            year = Some(token.parse().ok()?);
+        } else if month.is_none() {
+            month = Some(token.parse().ok()?);
+        } else if day.is_none() {
+            day = Some(token.parse().ok()?);
+        } else {
+            return None;
+        }
+    }
+
+    let year = year?;
+
+    if !month.map_or(true, |month| (1..=12).contains(&month))
+        || !day.map_or(true, |day| (1..=31).contains(&day))
+    {
+        return None;
+    }
+
+    let span = match (month, day) {
+        (Some(month), Some(day)) => {
+            let days = days_from_civil(year, month, day);
+
+            (days, days)
+        }
+        (Some(month), None) => (
+            days_from_civil(year, month, 1),
+            days_from_civil(year, month + 1, 1) - 1,
+        ),
+        (None, _) => (
+            days_from_civil(year, 1, 1),
+            days_from_civil(year + 1, 1, 1) - 1,
+        ),
+    };
+
+    Some(span)
+}
+
+fn month_from_name(name: &str) -> Option<i64> {
+    const MONTHS: [&str; 12] = [
+        "january",
+        "february",
+        "march",
+        "april",
+        "may",
+        "june",
+        "july",
+        "august",
+        "september",
+        "october",
+        "november",
+        "december",
+    ];
+
+    let name = name.to_ascii_lowercase();
+
+    if name.len() < 3 {
+        return None;
+    }
+
+    MONTHS
+        .iter()
+        .position(|month| month.starts_with(&name))
+        .map(|idx| idx as i64 + 1)
+}
+
+/// The days between 1970-01-01 and a unix timestamp in seconds.
+pub const fn timestamp_days(timestamp: i64) -> i64 {
+    timestamp.div_euclid(SECONDS_PER_DAY)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn days() {
+        assert_eq!(parse_days("1970-01-01"), Some(0));
+        assert_eq!(parse_days("2015-04-01"), Some(16_526));
+        assert_eq!(timestamp_days(1_427_846_400), 16_526);
+        assert_eq!(timestamp_days(-1), -1);
+        assert_eq!(parse_days("2015-04"), None);
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(16_526), (2015, 4, 1));
+        assert_eq!(civil_from_days(-1), (1969, 12, 31));
+    }
+
+    #[test]
+    fn loose_days() {
+        assert_eq!(parse_loose_days("2015-04"), parse_days("2015-04-01"));
+        assert_eq!(parse_loose_days("april2015"), parse_days("2015-04-01"));
+        assert_eq!(parse_loose_days("2015_Apr"), parse_days("2015-04-01"));
+        assert_eq!(parse_loose_days("2018"), parse_days("2018-01-01"));
+        assert_eq!(parse_loose_days("2021-07-15"), parse_days("2021-07-15"));
+        assert_eq!(parse_loose_days("ppv1"), None);
+        assert_eq!(parse_loose_days("2015-13"), None);
+        assert_eq!(parse_loose_days("july"), None);
+    }
+
+    #[test]
+    fn loose_span() {
+        let july = (
+            parse_days("2021-07-01").unwrap(),
+            parse_days("2021-07-31").unwrap(),
+        );
+        let december = (
+            parse_days("2021-12-01").unwrap(),
+            parse_days("2021-12-31").unwrap(),
+        );
+        let year = (
+            parse_days("2018-01-01").unwrap(),
+            parse_days("2018-12-31").unwrap(),
+        );
+        let day = parse_days("2021-07-15").unwrap();
+
+        assert_eq!(parse_loose_span("2021-07"), Some(july));
+        assert_eq!(parse_loose_span("december2021"), Some(december));
+        assert_eq!(parse_loose_span("2018"), Some(year));
+        assert_eq!(parse_loose_span("2021-07-15"), Some((day, day)));
+    }
+}
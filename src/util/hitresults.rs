@@ -0,0 +1,50 @@
+/// Estimate the 300s, 100s, and 50s of an osu!standard score from its combo and misses.
+///
+/// The map is assumed to break as rarely as the combo allows, i.e. into pieces of
+/// `combo` length. Each break that the misses do not account for is counted as a
+/// 100, e.g. a dropped slider end, and all other hits are 300s. Without a combo,
+/// the score is a full combo apart from its misses.
+pub fn estimate_osu(
+    n_objects: usize,
+    max_combo: usize,
+    combo: Option<usize>,
+    n_misses: usize,
+) -> (usize, usize, usize) {
+    let n_misses = n_misses.min(n_objects);
+    let n_hits = n_objects - n_misses;
+
+    let combo = combo
+        .filter(|_| max_combo > 0)
+        .map(|combo| combo.min(max_combo));
+
+    let breaks = match combo {
+        Some(0) => n_objects,
+        Some(combo) => max_combo.div_ceil(combo) - 1,
+        None => 0,
+    };
+
+    let n100 = breaks.saturating_sub(n_misses).min(n_hits);
+
+    (n_hits - n100, n100, 0)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn estimate() {
+        // Full combo
+        assert_eq!(estimate_osu(100, 150, Some(150), 0), (100, 0, 0));
+        assert_eq!(estimate_osu(100, 150, None, 2), (98, 0, 0));
+
+        // Three pieces, i.e. two breaks of which one is a miss
+        assert_eq!(estimate_osu(100, 150, Some(50), 1), (98, 1, 0));
+        assert_eq!(estimate_osu(100, 150, Some(49), 0), (97, 3, 0));
+
+        // More misses than breaks
+        assert_eq!(estimate_osu(100, 150, Some(100), 5), (95, 0, 0));
+        assert_eq!(estimate_osu(10, 20, Some(0), 12), (0, 0, 0));
+        assert_eq!(estimate_osu(10, 20, Some(0), 4), (0, 6, 0));
+    }
+}
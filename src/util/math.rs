@@ -1,7 +1,52 @@
+use rosu_pp::Mods;
+
 pub fn lerp(start: f64, end: f64, percent: f64) -> f64 {
     start + (end - start) * percent
 }
 
+const PREEMPT_MIN: f64 = 450.0;
+const PREEMPT_MID: f64 = 1200.0;
+const PREEMPT_MAX: f64 = 1800.0;
+
+/// The preempt time in milliseconds, i.e. how long an object is visible before it has to be hit.
+///
+/// HR and EZ scale the AR first, the clock rate then shortens or extends the preempt.
+pub fn ar_to_preempt(ar: f64, mods: u32) -> f64 {
+    let ar = if mods.hr() {
+        (ar * 1.4).min(10.0)
+    } else if mods.ez() {
+        ar * 0.5
+    } else {
+        ar
+    };
+
+    difficulty_range(ar, PREEMPT_MIN, PREEMPT_MID, PREEMPT_MAX) / mods.clock_rate()
+}
+
+/// The approach rate of a preempt time.
+///
+/// Results are not clamped and may be fractional, e.g. AR 9 with DT results in AR 10.33.
+pub fn preempt_to_ar(preempt: f64) -> f64 {
+    if preempt > PREEMPT_MID {
+        (PREEMPT_MAX - preempt) / 120.0
+    } else {
+        (PREEMPT_MID - preempt) / 150.0 + 5.0
+    }
+}
+
+/// The effective overall difficulty after applying mods, computed through the unrounded 300 hit window.
+pub fn modify_od(base_od: f64, speed_mult: f64, mod_mult: f64) -> f64 {
+    let od = (base_od * mod_mult).min(10.0);
+    let hit_window = (80.0 - 6.0 * od) / speed_mult;
+
+    hit_window_to_od(hit_window)
+}
+
+/// The overall difficulty of an unrounded 300 hit window in milliseconds.
+pub fn hit_window_to_od(hit_window: f64) -> f64 {
+    (80.0 - hit_window) / 6.0
+}
+
 pub fn difficulty_range(val: f64, max: f64, avg: f64, min: f64) -> f64 {
     if val > 5.0 {
         avg + (max - avg) * (val - 5.0) / 5.0
@@ -11,3 +56,28 @@ pub fn difficulty_range(val: f64, max: f64, avg: f64, min: f64) -> f64 {
         avg
     }
 }
+
+/// The circle size after applying HR or EZ the way osu!stable did, computed in `f32`.
+///
+/// HR is capped at 10 while EZ is not clamped. The modern attributes of
+/// rosu-pp compute this in `f64`, which slightly shifts the radius of old versions.
+pub fn stable_cs(cs: f32, mods: u32) -> f32 {
+    if mods.hr() {
+        (cs * 1.3).min(10.0)
+    } else if mods.ez() {
+        cs * 0.5
+    } else {
+        cs
+    }
+}
+
+/// The drain rate after applying HR or EZ, HR is capped at 10.
+pub fn stable_hp(hp: f32, mods: u32) -> f32 {
+    if mods.hr() {
+        (hp * 1.4).min(10.0)
+    } else if mods.ez() {
+        hp * 0.5
+    } else {
+        hp
+    }
+}
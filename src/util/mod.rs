@@ -1,3 +1,10 @@
+pub mod cache;
 pub mod curve;
+pub mod date;
+pub mod hitresults;
 pub mod limited_queue;
 pub mod math;
+pub mod mode;
+pub mod mods;
+pub mod strain;
+pub mod sum;
@@ -0,0 +1,33 @@
+use rosu_pp::GameMode;
+
+use crate::Error;
+
+/// Check whether a map of mode `map` can be calculated by a version of mode `version`.
+///
+/// osu!standard maps are converted into every other mode, maps of any other
+/// mode can only be calculated by versions of that same mode.
+pub(crate) fn check_mode(map: GameMode, version: GameMode) -> Result<(), Error> {
+    if map == version || map == GameMode::Osu {
+        Ok(())
+    } else {
+        Err(Error::UnsupportedMode { version, map })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn converts() {
+        assert_eq!(check_mode(GameMode::Osu, GameMode::Mania), Ok(()));
+        assert_eq!(check_mode(GameMode::Taiko, GameMode::Taiko), Ok(()));
+        assert_eq!(
+            check_mode(GameMode::Mania, GameMode::Osu),
+            Err(Error::UnsupportedMode {
+                version: GameMode::Osu,
+                map: GameMode::Mania,
+            })
+        );
+    }
+}
@@ -0,0 +1,42 @@
+//! Bit values of mods that are not covered by [`rosu_pp::Mods`].
+
+pub(crate) const RELAX: u32 = 1 << 7;
+pub(crate) const AUTOPLAY: u32 = 1 << 11;
+pub(crate) const AUTOPILOT: u32 = 1 << 13;
+pub(crate) const CINEMA: u32 = 1 << 22;
+pub(crate) const TARGET_PRACTICE: u32 = 1 << 23;
+pub(crate) const SCORE_V2: u32 = 1 << 29;
+pub(crate) const MIRROR: u32 = 1 << 30;
+
+/// The key mods and the amount of columns they convert osu!standard maps to.
+const KEY_MODS: [(u32, u8); 9] = [
+    (1 << 26, 1),
+    (1 << 28, 2),
+    (1 << 27, 3),
+    (1 << 15, 4),
+    (1 << 16, 5),
+    (1 << 17, 6),
+    (1 << 18, 7),
+    (1 << 19, 8),
+    (1 << 24, 9),
+];
+
+/// The amount of columns that the key mods within `mods` specify, if any.
+pub(crate) fn key_count(mods: u32) -> Option<u8> {
+    KEY_MODS
+        .iter()
+        .find(|(bits, _)| mods & bits > 0)
+        .map(|(_, keys)| *keys)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn key_mods() {
+        assert_eq!(key_count(0), None);
+        assert_eq!(key_count((1 << 3) | (1 << 15)), Some(4));
+        assert_eq!(key_count(1 << 24), Some(9));
+    }
+}
@@ -0,0 +1,181 @@
+//! The strain machinery of the osu!standard versions up to 2021 July.
+//!
+//! A version only describes its skills through [`StrainKind`] and [`StrainValue`],
+//! the decay, the section peaks, and the weighting of the peaks are handled by [`Skill`].
+
+use std::cmp::Ordering;
+
+use super::sum::StrainSum;
+
+/// The factor by which each strain peak is weighted less than its predecessor.
+pub const DECAY_WEIGHT: f32 = 0.9;
+
+/// A hit object as processed by a [`Skill`].
+pub trait StrainObject {
+    /// The start time of the object.
+    fn time(&self) -> f32;
+
+    /// The milliseconds over which the strain decays before the object is added.
+    fn decay_delta(&self) -> f32;
+}
+
+/// The constants of a skill.
+pub trait StrainKind: Copy {
+    /// The factor by which the strain of an object is multiplied.
+    fn skill_multiplier(self) -> f32;
+
+    /// The portion of the strain that remains after one second.
+    fn strain_decay_base(self) -> f32;
+
+    /// Weight the strain peaks into the difficulty of the skill.
+    #[inline]
+    fn difficulty_value(self, strain_peaks: &mut [f32]) -> f32 {
+        weighted_sum(strain_peaks)
+    }
+}
+
+/// The strain that an object adds to a skill.
+pub trait StrainValue<T>: StrainKind {
+    /// The strain of the object, before applying the [`skill_multiplier`](StrainKind::skill_multiplier).
+    fn strain_value(self, current: &T) -> f32;
+}
+
+/// Collects the strain peaks of a skill in sections.
+///
+/// Each object is added through [`process`](Self::process). Whenever an object lies
+/// past the end of the current section, [`save_current_peak`](Self::save_current_peak)
+/// and [`start_new_section_from`](Self::start_new_section_from) have to be called
+/// for each section that ended.
+pub struct Skill<K> {
+    current_strain: f32,
+    current_section_peak: f32,
+
+    kind: K,
+    /// The strain peaks of all saved sections.
+    pub strain_peaks: Vec<f32>,
+
+    prev_time: Option<f32>,
+}
+
+impl<K: StrainKind> Skill<K> {
+    /// Create a skill without any strain peaks.
+    #[inline]
+    pub fn new(kind: K) -> Self {
+        Self {
+            current_strain: 1.0,
+            current_section_peak: 1.0,
+
+            kind,
+            strain_peaks: Vec::with_capacity(128),
+
+            prev_time: None,
+        }
+    }
+
+    /// Save the peak of the current section.
+    #[inline]
+    pub fn save_current_peak(&mut self) {
+        self.strain_peaks.push(self.current_section_peak);
+    }
+
+    /// Start a new section whose peak is the decayed strain at the given time.
+    #[inline]
+    pub fn start_new_section_from(&mut self, time: f32) {
+        self.current_section_peak = self.peak_strain(time - self.prev_time.unwrap());
+    }
+
+    /// Decay the strain and add the strain of the object.
+    #[inline]
+    pub fn process<T: StrainObject>(&mut self, current: &T)
+    where
+        K: StrainValue<T>,
+    {
+        self.current_strain *= self.strain_decay(current.decay_delta());
+        self.current_strain += self.kind.strain_value(current) * self.kind.skill_multiplier();
+        self.current_section_peak = self.current_section_peak.max(self.current_strain);
+        self.prev_time.replace(current.time());
+    }
+
+    /// Weight the saved strain peaks into the difficulty of the skill.
+    #[inline]
+    pub fn difficulty_value(&mut self) -> f32 {
+        self.kind.difficulty_value(&mut self.strain_peaks)
+    }
+
+    #[inline]
+    fn peak_strain(&self, delta_time: f32) -> f32 {
+        self.current_strain * self.strain_decay(delta_time)
+    }
+
+    #[inline]
+    fn strain_decay(&self, ms: f32) -> f32 {
+        self.kind.strain_decay_base().powf(ms / 1000.0)
+    }
+}
+
+/// Sort the strain peaks in descending order and sum them up,
+/// each peak is weighted by [`DECAY_WEIGHT`] less than its predecessor.
+pub fn weighted_sum(strain_peaks: &mut [f32]) -> f32 {
+    let mut difficulty = StrainSum::default();
+    let mut weight = 1.0;
+
+    strain_peaks.sort_unstable_by(|a, b| b.partial_cmp(a).unwrap_or(Ordering::Equal));
+
+    for &strain in strain_peaks.iter() {
+        difficulty += strain * weight;
+        weight *= DECAY_WEIGHT;
+    }
+
+    difficulty.value()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Copy, Clone)]
+    struct Constant;
+
+    struct Object(f32);
+
+    impl StrainObject for Object {
+        fn time(&self) -> f32 {
+            self.0
+        }
+
+        fn decay_delta(&self) -> f32 {
+            1000.0
+        }
+    }
+
+    impl StrainKind for Constant {
+        fn skill_multiplier(self) -> f32 {
+            2.0
+        }
+
+        fn strain_decay_base(self) -> f32 {
+            0.5
+        }
+    }
+
+    impl StrainValue<Object> for Constant {
+        fn strain_value(self, _: &Object) -> f32 {
+            1.0
+        }
+    }
+
+    #[test]
+    fn decays_and_adds() {
+        let mut skill = Skill::new(Constant);
+
+        skill.process(&Object(0.0));
+        skill.save_current_peak();
+        skill.start_new_section_from(1000.0);
+        skill.process(&Object(1000.0));
+        skill.save_current_peak();
+
+        // 1.0 * 0.5 + 2.0 = 2.5, then 2.5 * 0.5 + 2.0 = 3.25
+        assert_eq!(skill.strain_peaks, vec![2.5, 3.25]);
+        assert_eq!(skill.difficulty_value(), 3.25 + 2.5 * DECAY_WEIGHT);
+    }
+}
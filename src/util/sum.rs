@@ -0,0 +1,75 @@
+use std::ops::{Add, AddAssign, Sub};
+
+pub trait Float: Copy + Default + PartialOrd + Add<Output = Self> + Sub<Output = Self> {
+    fn abs(self) -> Self;
+}
+
+impl Float for f32 {
+    #[inline]
+    fn abs(self) -> Self {
+        f32::abs(self)
+    }
+}
+
+impl Float for f64 {
+    #[inline]
+    fn abs(self) -> Self {
+        f64::abs(self)
+    }
+}
+
+/// The sum of weighted strain peaks.
+///
+/// By default values are simply added up in order. With the `deterministic` feature
+/// the rounding error of each addition is carried along through Neumaier summation
+/// so the result no longer depends on how the error accumulates.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct StrainSum<F> {
+    sum: F,
+    compensation: F,
+}
+
+impl<F: Float> StrainSum<F> {
+    #[inline]
+    pub fn value(self) -> F {
+        self.sum + self.compensation
+    }
+}
+
+impl<F: Float> AddAssign<F> for StrainSum<F> {
+    #[cfg(not(feature = "deterministic"))]
+    #[inline]
+    fn add_assign(&mut self, value: F) {
+        self.sum = self.sum + value;
+    }
+
+    #[cfg(feature = "deterministic")]
+    #[inline]
+    fn add_assign(&mut self, value: F) {
+        let sum = self.sum + value;
+
+        if self.sum.abs() >= value.abs() {
+            self.compensation = self.compensation + ((self.sum - sum) + value);
+        } else {
+            self.compensation = self.compensation + ((value - sum) + self.sum);
+        }
+
+        self.sum = sum;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn sums_in_order() {
+        let mut sum = StrainSum::default();
+
+        for value in [1.0_f64, 0.5, 0.25] {
+            sum += value;
+        }
+
+        assert_eq!(sum.value(), 1.75);
+    }
+}
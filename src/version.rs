@@ -0,0 +1,812 @@
+//! Selecting a version at runtime.
+//!
+//! Each version lives in its own module with its own types. [`Version`] names them
+//! so that the version can come from a config file or a database, and [`AnyStars`]
+//! dispatches to the selected version.
+
+use std::{cmp::Ordering, fmt, str::FromStr};
+
+#[cfg(any(feature = "fruits-ppv1", feature = "catch-2020"))]
+use rosu_pp::catch::CatchDifficultyAttributes;
+use rosu_pp::{Beatmap, GameMode};
+
+use crate::{util::date, AnyStars, Error, MapAttributes};
+
+/// A version of one of the modes, each variant corresponds to a module of the same name.
+///
+/// Only the versions whose feature is enabled are available, see the crate's `Cargo.toml`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Version {
+    #[cfg(feature = "osu-2012-tp")]
+    Osu2012Tp,
+    #[cfg(feature = "osu-2014-february")]
+    Osu2014February,
+    #[cfg(feature = "osu-2014-may")]
+    Osu2014May,
+    #[cfg(feature = "osu-2014-july")]
+    Osu2014July,
+    #[cfg(feature = "osu-2015-february")]
+    Osu2015February,
+    #[cfg(feature = "osu-2015-april")]
+    Osu2015April,
+    #[cfg(feature = "osu-2018")]
+    Osu2018,
+    #[cfg(feature = "osu-2019")]
+    Osu2019,
+    #[cfg(feature = "osu-2021-january")]
+    Osu2021January,
+    #[cfg(feature = "osu-2021-july")]
+    Osu2021July,
+    #[cfg(feature = "osu-2021-november")]
+    Osu2021November,
+    #[cfg(feature = "osu-2022-september")]
+    Osu2022September,
+    #[cfg(feature = "taiko-ppv1")]
+    TaikoPpv1,
+    #[cfg(feature = "taiko-2020")]
+    Taiko2020,
+    #[cfg(feature = "taiko-2022")]
+    Taiko2022,
+    #[cfg(feature = "mania-ppv1")]
+    ManiaPpv1,
+    #[cfg(feature = "mania-2018")]
+    Mania2018,
+    #[cfg(feature = "mania-2022")]
+    Mania2022,
+    #[cfg(feature = "fruits-ppv1")]
+    FruitsPpv1,
+    #[cfg(feature = "catch-2020")]
+    Catch2020,
+}
+
+/// Access a constant of the module that corresponds to the version.
+macro_rules! version_const {
+    ($version:expr, $const:ident) => {
+        match $version {
+            #[cfg(feature = "osu-2012-tp")]
+            Version::Osu2012Tp => crate::osu_2012_tp::$const,
+            #[cfg(feature = "osu-2014-february")]
+            Version::Osu2014February => crate::osu_2014_february::$const,
+            #[cfg(feature = "osu-2014-may")]
+            Version::Osu2014May => crate::osu_2014_may::$const,
+            #[cfg(feature = "osu-2014-july")]
+            Version::Osu2014July => crate::osu_2014_july::$const,
+            #[cfg(feature = "osu-2015-february")]
+            Version::Osu2015February => crate::osu_2015_february::$const,
+            #[cfg(feature = "osu-2015-april")]
+            Version::Osu2015April => crate::osu_2015_april::$const,
+            #[cfg(feature = "osu-2018")]
+            Version::Osu2018 => crate::osu_2018::$const,
+            #[cfg(feature = "osu-2019")]
+            Version::Osu2019 => crate::osu_2019::$const,
+            #[cfg(feature = "osu-2021-january")]
+            Version::Osu2021January => crate::osu_2021_january::$const,
+            #[cfg(feature = "osu-2021-july")]
+            Version::Osu2021July => crate::osu_2021_july::$const,
+            #[cfg(feature = "osu-2021-november")]
+            Version::Osu2021November => crate::osu_2021_november::$const,
+            #[cfg(feature = "osu-2022-september")]
+            Version::Osu2022September => crate::osu_2022_september::$const,
+            #[cfg(feature = "taiko-ppv1")]
+            Version::TaikoPpv1 => crate::taiko_ppv1::$const,
+            #[cfg(feature = "taiko-2020")]
+            Version::Taiko2020 => crate::taiko_2020::$const,
+            #[cfg(feature = "taiko-2022")]
+            Version::Taiko2022 => crate::taiko_2022::$const,
+            #[cfg(feature = "mania-ppv1")]
+            Version::ManiaPpv1 => crate::mania_ppv1::$const,
+            #[cfg(feature = "mania-2018")]
+            Version::Mania2018 => crate::mania_2018::$const,
+            #[cfg(feature = "mania-2022")]
+            Version::Mania2022 => crate::mania_2022::$const,
+            #[cfg(feature = "fruits-ppv1")]
+            Version::FruitsPpv1 => crate::fruits_ppv1::$const,
+            #[cfg(feature = "catch-2020")]
+            Version::Catch2020 => crate::catch_2020::$const,
+        }
+    };
+}
+
+impl Version {
+    /// All enabled versions, grouped by mode and ordered by date within each mode.
+    pub const ALL: &'static [Self] = &[
+        #[cfg(feature = "osu-2012-tp")]
+        Self::Osu2012Tp,
+        #[cfg(feature = "osu-2014-february")]
+        Self::Osu2014February,
+        #[cfg(feature = "osu-2014-may")]
+        Self::Osu2014May,
+        #[cfg(feature = "osu-2014-july")]
+        Self::Osu2014July,
+        #[cfg(feature = "osu-2015-february")]
+        Self::Osu2015February,
+        #[cfg(feature = "osu-2015-april")]
+        Self::Osu2015April,
+        #[cfg(feature = "osu-2018")]
+        Self::Osu2018,
+        #[cfg(feature = "osu-2019")]
+        Self::Osu2019,
+        #[cfg(feature = "osu-2021-january")]
+        Self::Osu2021January,
+        #[cfg(feature = "osu-2021-july")]
+        Self::Osu2021July,
+        #[cfg(feature = "osu-2021-november")]
+        Self::Osu2021November,
+        #[cfg(feature = "osu-2022-september")]
+        Self::Osu2022September,
+        #[cfg(feature = "taiko-ppv1")]
+        Self::TaikoPpv1,
+        #[cfg(feature = "taiko-2020")]
+        Self::Taiko2020,
+        #[cfg(feature = "taiko-2022")]
+        Self::Taiko2022,
+        #[cfg(feature = "mania-ppv1")]
+        Self::ManiaPpv1,
+        #[cfg(feature = "mania-2018")]
+        Self::Mania2018,
+        #[cfg(feature = "mania-2022")]
+        Self::Mania2022,
+        #[cfg(feature = "fruits-ppv1")]
+        Self::FruitsPpv1,
+        #[cfg(feature = "catch-2020")]
+        Self::Catch2020,
+    ];
+
+    /// The mode that the version calculates.
+    pub fn mode(self) -> GameMode {
+        version_const!(self, MODE)
+    }
+
+    /// The name of the module of the version, e.g. `"osu_2015_april"`.
+    ///
+    /// Unlike [`name`](Self::name), the module name is unique across all modes.
+    /// It is also used for the [`Display`](fmt::Display) and [`FromStr`] implementations.
+    pub fn module_name(self) -> &'static str {
+        match self {
+            #[cfg(feature = "osu-2012-tp")]
+            Self::Osu2012Tp => "osu_2012_tp",
+            #[cfg(feature = "osu-2014-february")]
+            Self::Osu2014February => "osu_2014_february",
+            #[cfg(feature = "osu-2014-may")]
+            Self::Osu2014May => "osu_2014_may",
+            #[cfg(feature = "osu-2014-july")]
+            Self::Osu2014July => "osu_2014_july",
+            #[cfg(feature = "osu-2015-february")]
+            Self::Osu2015February => "osu_2015_february",
+            #[cfg(feature = "osu-2015-april")]
+            Self::Osu2015April => "osu_2015_april",
+            #[cfg(feature = "osu-2018")]
+            Self::Osu2018 => "osu_2018",
+            #[cfg(feature = "osu-2019")]
+            Self::Osu2019 => "osu_2019",
+            #[cfg(feature = "osu-2021-january")]
+            Self::Osu2021January => "osu_2021_january",
+            #[cfg(feature = "osu-2021-july")]
+            Self::Osu2021July => "osu_2021_july",
+            #[cfg(feature = "osu-2021-november")]
+            Self::Osu2021November => "osu_2021_november",
+            #[cfg(feature = "osu-2022-september")]
+            Self::Osu2022September => "osu_2022_september",
+            #[cfg(feature = "taiko-ppv1")]
+            Self::TaikoPpv1 => "taiko_ppv1",
+            #[cfg(feature = "taiko-2020")]
+            Self::Taiko2020 => "taiko_2020",
+            #[cfg(feature = "taiko-2022")]
+            Self::Taiko2022 => "taiko_2022",
+            #[cfg(feature = "mania-ppv1")]
+            Self::ManiaPpv1 => "mania_ppv1",
+            #[cfg(feature = "mania-2018")]
+            Self::Mania2018 => "mania_2018",
+            #[cfg(feature = "mania-2022")]
+            Self::Mania2022 => "mania_2022",
+            #[cfg(feature = "fruits-ppv1")]
+            Self::FruitsPpv1 => "fruits_ppv1",
+            #[cfg(feature = "catch-2020")]
+            Self::Catch2020 => "catch_2020",
+        }
+    }
+
+    /// The name of the version within its mode, e.g. `"2015-april"`.
+    pub fn name(self) -> &'static str {
+        version_const!(self, VERSION_NAME)
+    }
+
+    /// The approximate date at which the version went live, formatted as `YYYY-MM-DD`.
+    pub fn date(self) -> &'static str {
+        version_const!(self, VERSION_DATE)
+    }
+
+    /// Parse mod acronyms like `"HDDT"` or `"HD,DT"` into the mods value of the version.
+    ///
+    /// Mods that did not exist yet at the time of the version cause an error.
+    pub fn parse_mods(self, acronyms: &str) -> Result<u32, Error> {
+        version_const!(self, parse_mods)(acronyms)
+    }
+
+    /// The attributes of an osu!standard map after applying mods, the way the version
+    /// computed them, see the `map_attributes` function of the osu!standard modules.
+    ///
+    /// Returns `None` for versions of other modes.
+    pub fn map_attributes(self, map: &Beatmap, mods: u32) -> Option<MapAttributes> {
+        let attributes = match self {
+            #[cfg(feature = "osu-2012-tp")]
+            Self::Osu2012Tp => crate::osu_2012_tp::map_attributes(map, mods),
+            #[cfg(feature = "osu-2014-february")]
+            Self::Osu2014February => crate::osu_2014_february::map_attributes(map, mods),
+            #[cfg(feature = "osu-2014-may")]
+            Self::Osu2014May => crate::osu_2014_may::map_attributes(map, mods),
+            #[cfg(feature = "osu-2014-july")]
+            Self::Osu2014July => crate::osu_2014_july::map_attributes(map, mods),
+            #[cfg(feature = "osu-2015-february")]
+            Self::Osu2015February => crate::osu_2015_february::map_attributes(map, mods),
+            #[cfg(feature = "osu-2015-april")]
+            Self::Osu2015April => crate::osu_2015_april::map_attributes(map, mods),
+            #[cfg(feature = "osu-2018")]
+            Self::Osu2018 => crate::osu_2018::map_attributes(map, mods),
+            #[cfg(feature = "osu-2019")]
+            Self::Osu2019 => crate::osu_2019::map_attributes(map, mods),
+            #[cfg(feature = "osu-2021-january")]
+            Self::Osu2021January => crate::osu_2021_january::map_attributes(map, mods),
+            #[cfg(feature = "osu-2021-july")]
+            Self::Osu2021July => crate::osu_2021_july::map_attributes(map, mods),
+            #[cfg(feature = "osu-2021-november")]
+            Self::Osu2021November => crate::osu_2021_november::map_attributes(map, mods),
+            #[cfg(feature = "osu-2022-september")]
+            Self::Osu2022September => crate::osu_2022_september::map_attributes(map, mods),
+            #[cfg(feature = "taiko-ppv1")]
+            Self::TaikoPpv1 => return None,
+            #[cfg(feature = "taiko-2020")]
+            Self::Taiko2020 => return None,
+            #[cfg(feature = "taiko-2022")]
+            Self::Taiko2022 => return None,
+            #[cfg(feature = "mania-ppv1")]
+            Self::ManiaPpv1 => return None,
+            #[cfg(feature = "mania-2018")]
+            Self::Mania2018 => return None,
+            #[cfg(feature = "mania-2022")]
+            Self::Mania2022 => return None,
+            #[cfg(feature = "fruits-ppv1")]
+            Self::FruitsPpv1 => return None,
+            #[cfg(feature = "catch-2020")]
+            Self::Catch2020 => return None,
+        };
+
+        Some(attributes)
+    }
+
+    /// Metadata of the version, e.g. to display an overview of all versions.
+    pub fn info(self) -> VersionInfo {
+        let mode = self.mode();
+
+        let end = Self::ALL
+            .iter()
+            .copied()
+            .skip_while(|&version| version != self)
+            .nth(1)
+            .filter(|next| next.mode() == mode)
+            .map(Self::date);
+
+        let map_modes: &'static [GameMode] = match mode {
+            GameMode::Osu => &[GameMode::Osu],
+            GameMode::Taiko => &[GameMode::Taiko, GameMode::Osu],
+            GameMode::Catch => &[GameMode::Catch, GameMode::Osu],
+            GameMode::Mania => &[GameMode::Mania, GameMode::Osu],
+        };
+
+        VersionInfo {
+            version: self,
+            name: self.name(),
+            start: self.date(),
+            end,
+            description: version_const!(self, VERSION_DESCRIPTION),
+            mode,
+            map_modes,
+        }
+    }
+
+    /// The version of a mode that was live at the given unix timestamp in seconds.
+    ///
+    /// The dates of the versions are approximations, see [`date`](Self::date). Timestamps
+    /// before the first version of the mode fall back to that first version.
+    ///
+    /// Returns `None` if no version of the mode is enabled.
+    pub fn from_date(mode: GameMode, timestamp: i64) -> Option<Self> {
+        let days = date::timestamp_days(timestamp);
+
+        Self::from_days(mode, days).or_else(|| Self::first(mode))
+    }
+
+    /// The version of a mode that was live at the given days since 1970-01-01,
+    /// `None` if the days are before the first version of the mode.
+    fn from_days(mode: GameMode, days: i64) -> Option<Self> {
+        Self::versions(mode)
+            .take_while(|version| version.days() <= days)
+            .last()
+    }
+
+    fn first(mode: GameMode) -> Option<Self> {
+        Self::versions(mode).next()
+    }
+
+    /// The enabled versions of a mode, ordered by date.
+    pub fn versions(mode: GameMode) -> impl Iterator<Item = Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .filter(move |version| version.mode() == mode)
+    }
+
+    /// The enabled osu!standard versions, ordered by date.
+    #[inline]
+    pub fn osu_versions() -> impl Iterator<Item = Self> {
+        Self::versions(GameMode::Osu)
+    }
+
+    /// The most recent enabled version of a mode.
+    ///
+    /// Returns `None` if no version of the mode is enabled.
+    pub fn latest(mode: GameMode) -> Option<Self> {
+        Self::versions(mode).last()
+    }
+
+    fn days(self) -> i64 {
+        date::parse_days(self.date()).expect("invalid version date")
+    }
+}
+
+/// Versions are ordered by their [`date`](Version::date), versions of
+/// different modes with the same date are ordered by mode.
+impl PartialOrd for Version {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.date()
+            .cmp(other.date())
+            .then_with(|| (*self as u8).cmp(&(*other as u8)))
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.module_name())
+    }
+}
+
+/// Parses either the module name of a version, e.g. `"osu_2015_april"`, or a date.
+///
+/// Dates may be formatted loosely, e.g. `"2021-07"`, `"2021-07-15"`, or `"july2021"`,
+/// and resolve to the version that was live at the end of that date, i.e. a month or
+/// a year resolves to the last version that went live within it. Without a mode prefix
+/// such as `"taiko_"` or `"mania-"`, dates refer to osu!standard versions.
+///
+/// Case and the choice between `-` and `_` as separator don't matter.
+impl FromStr for Version {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_ascii_lowercase().replace('-', "_");
+
+        let module = Self::ALL
+            .iter()
+            .copied()
+            .find(|version| version.module_name() == name);
+
+        if let Some(version) = module {
+            return Ok(version);
+        }
+
+        const PREFIXES: [(&str, GameMode); 6] = [
+            ("osu_", GameMode::Osu),
+            ("taiko_", GameMode::Taiko),
+            ("mania_", GameMode::Mania),
+            ("catch_", GameMode::Catch),
+            ("fruits_", GameMode::Catch),
+            ("ctb_", GameMode::Catch),
+        ];
+
+        let (mode, date) = PREFIXES
+            .iter()
+            .find_map(|(prefix, mode)| name.strip_prefix(prefix).map(|date| (*mode, date)))
+            .unwrap_or((GameMode::Osu, name.as_str()));
+
+        date::parse_loose_span(date)
+            .and_then(|(_, last)| Self::from_days(mode, last))
+            .ok_or_else(|| Error::UnknownVersion(s.to_owned()))
+    }
+}
+
+/// Metadata of a [`Version`], see [`Version::info`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct VersionInfo {
+    /// The described version.
+    pub version: Version,
+    /// The name of the version within its mode, e.g. `"2015-april"`.
+    pub name: &'static str,
+    /// The approximate date at which the version went live, formatted as `YYYY-MM-DD`.
+    pub start: &'static str,
+    /// The date at which the next version of the mode went live,
+    /// `None` if the version is the latest one of its mode.
+    pub end: Option<&'static str>,
+    /// A short description of what changed in the version.
+    pub description: &'static str,
+    /// The mode that the version calculates.
+    pub mode: GameMode,
+    /// The modes of maps that the version accepts, i.e. its own mode and converts.
+    pub map_modes: &'static [GameMode],
+}
+
+/// The difficulty attributes of any version, see [`stars`].
+#[derive(Clone, Debug)]
+pub enum AnyDifficultyAttributes {
+    #[cfg(feature = "osu-2012-tp")]
+    Osu2012Tp(crate::osu_2012_tp::OsuDifficultyAttributes),
+    #[cfg(feature = "osu-2014-february")]
+    Osu2014February(crate::osu_2014_february::OsuDifficultyAttributes),
+    #[cfg(feature = "osu-2014-may")]
+    Osu2014May(crate::osu_2014_may::OsuDifficultyAttributes),
+    #[cfg(feature = "osu-2014-july")]
+    Osu2014July(crate::osu_2014_july::OsuDifficultyAttributes),
+    #[cfg(feature = "osu-2015-february")]
+    Osu2015February(crate::osu_2015_february::OsuDifficultyAttributes),
+    #[cfg(feature = "osu-2015-april")]
+    Osu2015April(crate::osu_2015_april::OsuDifficultyAttributes),
+    #[cfg(feature = "osu-2018")]
+    Osu2018(crate::osu_2018::OsuDifficultyAttributes),
+    #[cfg(feature = "osu-2019")]
+    Osu2019(crate::osu_2019::OsuDifficultyAttributes),
+    #[cfg(feature = "osu-2021-january")]
+    Osu2021January(crate::osu_2021_january::OsuDifficultyAttributes),
+    #[cfg(feature = "osu-2021-july")]
+    Osu2021July(crate::osu_2021_july::OsuDifficultyAttributes),
+    #[cfg(feature = "osu-2021-november")]
+    Osu2021November(crate::osu_2021_november::OsuDifficultyAttributes),
+    #[cfg(feature = "osu-2022-september")]
+    Osu2022September(crate::osu_2022_september::OsuDifficultyAttributes),
+    #[cfg(feature = "taiko-ppv1")]
+    TaikoPpv1(crate::taiko_ppv1::TaikoDifficultyAttributes),
+    #[cfg(feature = "taiko-2020")]
+    Taiko2020(crate::taiko_2020::TaikoDifficultyAttributes),
+    #[cfg(feature = "taiko-2022")]
+    Taiko2022(crate::taiko_2022::TaikoDifficultyAttributes),
+    #[cfg(feature = "mania-ppv1")]
+    ManiaPpv1(crate::mania_ppv1::ManiaDifficultyAttributes),
+    #[cfg(feature = "mania-2018")]
+    Mania2018(crate::mania_2018::ManiaDifficultyAttributes),
+    #[cfg(feature = "mania-2022")]
+    Mania2022(crate::mania_2022::ManiaDifficultyAttributes),
+    #[cfg(feature = "fruits-ppv1")]
+    FruitsPpv1(CatchDifficultyAttributes),
+    #[cfg(feature = "catch-2020")]
+    Catch2020(CatchDifficultyAttributes),
+}
+
+/// Match on all variants of [`AnyDifficultyAttributes`] or `AnyPerformanceAttributes`.
+macro_rules! any_attributes {
+    ($self:ident, $attrs:ident => $expr:expr) => {
+        match $self {
+            #[cfg(feature = "osu-2012-tp")]
+            Self::Osu2012Tp($attrs) => $expr,
+            #[cfg(feature = "osu-2014-february")]
+            Self::Osu2014February($attrs) => $expr,
+            #[cfg(feature = "osu-2014-may")]
+            Self::Osu2014May($attrs) => $expr,
+            #[cfg(feature = "osu-2014-july")]
+            Self::Osu2014July($attrs) => $expr,
+            #[cfg(feature = "osu-2015-february")]
+            Self::Osu2015February($attrs) => $expr,
+            #[cfg(feature = "osu-2015-april")]
+            Self::Osu2015April($attrs) => $expr,
+            #[cfg(feature = "osu-2018")]
+            Self::Osu2018($attrs) => $expr,
+            #[cfg(feature = "osu-2019")]
+            Self::Osu2019($attrs) => $expr,
+            #[cfg(feature = "osu-2021-january")]
+            Self::Osu2021January($attrs) => $expr,
+            #[cfg(feature = "osu-2021-july")]
+            Self::Osu2021July($attrs) => $expr,
+            #[cfg(feature = "osu-2021-november")]
+            Self::Osu2021November($attrs) => $expr,
+            #[cfg(feature = "osu-2022-september")]
+            Self::Osu2022September($attrs) => $expr,
+            #[cfg(feature = "taiko-ppv1")]
+            Self::TaikoPpv1($attrs) => $expr,
+            #[cfg(feature = "taiko-2020")]
+            Self::Taiko2020($attrs) => $expr,
+            #[cfg(feature = "taiko-2022")]
+            Self::Taiko2022($attrs) => $expr,
+            #[cfg(feature = "mania-ppv1")]
+            Self::ManiaPpv1($attrs) => $expr,
+            #[cfg(feature = "mania-2018")]
+            Self::Mania2018($attrs) => $expr,
+            #[cfg(feature = "mania-2022")]
+            Self::Mania2022($attrs) => $expr,
+            #[cfg(feature = "fruits-ppv1")]
+            Self::FruitsPpv1($attrs) => $expr,
+            #[cfg(feature = "catch-2020")]
+            Self::Catch2020($attrs) => $expr,
+        }
+    };
+    ($self:ident => version) => {
+        match $self {
+            #[cfg(feature = "osu-2012-tp")]
+            Self::Osu2012Tp(_) => Version::Osu2012Tp,
+            #[cfg(feature = "osu-2014-february")]
+            Self::Osu2014February(_) => Version::Osu2014February,
+            #[cfg(feature = "osu-2014-may")]
+            Self::Osu2014May(_) => Version::Osu2014May,
+            #[cfg(feature = "osu-2014-july")]
+            Self::Osu2014July(_) => Version::Osu2014July,
+            #[cfg(feature = "osu-2015-february")]
+            Self::Osu2015February(_) => Version::Osu2015February,
+            #[cfg(feature = "osu-2015-april")]
+            Self::Osu2015April(_) => Version::Osu2015April,
+            #[cfg(feature = "osu-2018")]
+            Self::Osu2018(_) => Version::Osu2018,
+            #[cfg(feature = "osu-2019")]
+            Self::Osu2019(_) => Version::Osu2019,
+            #[cfg(feature = "osu-2021-january")]
+            Self::Osu2021January(_) => Version::Osu2021January,
+            #[cfg(feature = "osu-2021-july")]
+            Self::Osu2021July(_) => Version::Osu2021July,
+            #[cfg(feature = "osu-2021-november")]
+            Self::Osu2021November(_) => Version::Osu2021November,
+            #[cfg(feature = "osu-2022-september")]
+            Self::Osu2022September(_) => Version::Osu2022September,
+            #[cfg(feature = "taiko-ppv1")]
+            Self::TaikoPpv1(_) => Version::TaikoPpv1,
+            #[cfg(feature = "taiko-2020")]
+            Self::Taiko2020(_) => Version::Taiko2020,
+            #[cfg(feature = "taiko-2022")]
+            Self::Taiko2022(_) => Version::Taiko2022,
+            #[cfg(feature = "mania-ppv1")]
+            Self::ManiaPpv1(_) => Version::ManiaPpv1,
+            #[cfg(feature = "mania-2018")]
+            Self::Mania2018(_) => Version::Mania2018,
+            #[cfg(feature = "mania-2022")]
+            Self::Mania2022(_) => Version::Mania2022,
+            #[cfg(feature = "fruits-ppv1")]
+            Self::FruitsPpv1(_) => Version::FruitsPpv1,
+            #[cfg(feature = "catch-2020")]
+            Self::Catch2020(_) => Version::Catch2020,
+        }
+    };
+}
+
+pub(crate) use any_attributes;
+
+impl AnyDifficultyAttributes {
+    /// The version that calculated the attributes.
+    pub fn version(&self) -> Version {
+        any_attributes!(self => version)
+    }
+
+    /// The mode that the attributes were calculated for.
+    #[inline]
+    pub fn mode(&self) -> GameMode {
+        self.version().mode()
+    }
+
+    /// The star rating of the map.
+    #[inline]
+    pub fn stars(&self) -> f64 {
+        any_attributes!(self, attrs => attrs.stars)
+    }
+}
+
+/// Calculate the difficulty of a map with the given version.
+///
+/// In case of a partial play, e.g. a fail, one can specify the amount of passed objects.
+/// Shorthand for [`AnyStars`], which also accepts a custom clock rate.
+///
+/// Returns [`Error::UnsupportedMode`] if the map can not be calculated by the version.
+pub fn stars(
+    map: &Beatmap,
+    mods: u32,
+    passed_objects: Option<usize>,
+    version: Version,
+) -> Result<AnyDifficultyAttributes, Error> {
+    let calculator = AnyStars::new(map).version(version).mods(mods);
+
+    match passed_objects {
+        Some(passed_objects) => calculator.passed_objects(passed_objects).calculate(),
+        None => calculator.calculate(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[cfg(all(feature = "osu-2015-april", feature = "osu-2021-july"))]
+    #[test]
+    fn dispatch() {
+        let map = Beatmap::default();
+        let attrs = stars(&map, 0, None, Version::Osu2015April).unwrap();
+
+        assert_eq!(attrs.version(), Version::Osu2015April);
+
+        let map = Beatmap {
+            mode: GameMode::Mania,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            stars(&map, 0, None, Version::Osu2021July).unwrap_err(),
+            Error::UnsupportedMode {
+                version: GameMode::Osu,
+                map: GameMode::Mania,
+            }
+        );
+    }
+
+    #[cfg(all(feature = "osu-2019", feature = "taiko-2022"))]
+    #[test]
+    fn map_attributes() {
+        let map = Beatmap {
+            ar: 9.0,
+            od: 8.0,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            Version::Osu2019.map_attributes(&map, 64),
+            Some(crate::osu_2019::map_attributes(&map, 64))
+        );
+        assert_eq!(Version::Taiko2022.map_attributes(&map, 64), None);
+    }
+
+    #[cfg(feature = "all")]
+    #[test]
+    fn from_date() {
+        // 2015-03-31 and 2015-04-01
+        assert_eq!(
+            Version::from_date(GameMode::Osu, 1_427_846_399),
+            Some(Version::Osu2015February)
+        );
+        assert_eq!(
+            Version::from_date(GameMode::Osu, 1_427_846_400),
+            Some(Version::Osu2015April)
+        );
+        assert_eq!(
+            Version::from_date(GameMode::Taiko, 0),
+            Some(Version::TaikoPpv1)
+        );
+        assert_eq!(
+            Version::from_date(GameMode::Mania, i64::MAX),
+            Some(Version::Mania2022)
+        );
+    }
+
+    #[cfg(feature = "all")]
+    #[test]
+    fn from_date_deploys() {
+        // Last second before and first second of 2019-02-05
+        assert_eq!(
+            Version::from_date(GameMode::Osu, 1_549_324_799),
+            Some(Version::Osu2018)
+        );
+        assert_eq!(
+            Version::from_date(GameMode::Osu, 1_549_324_800),
+            Some(Version::Osu2019)
+        );
+
+        // Last second before and first second of 2021-07-27
+        assert_eq!(
+            Version::from_date(GameMode::Osu, 1_627_343_999),
+            Some(Version::Osu2021January)
+        );
+        assert_eq!(
+            Version::from_date(GameMode::Osu, 1_627_344_000),
+            Some(Version::Osu2021July)
+        );
+
+        // Last second before and first second of 2021-11-09
+        assert_eq!(
+            Version::from_date(GameMode::Osu, 1_636_415_999),
+            Some(Version::Osu2021July)
+        );
+        assert_eq!(
+            Version::from_date(GameMode::Osu, 1_636_416_000),
+            Some(Version::Osu2021November)
+        );
+
+        // Last second before and first second of 2022-09-28
+        assert_eq!(
+            Version::from_date(GameMode::Osu, 1_664_323_199),
+            Some(Version::Osu2021November)
+        );
+        assert_eq!(
+            Version::from_date(GameMode::Osu, 1_664_323_200),
+            Some(Version::Osu2022September)
+        );
+
+        // Last second before and first second of 2022-09-28
+        assert_eq!(
+            Version::from_date(GameMode::Taiko, 1_664_323_199),
+            Some(Version::Taiko2020)
+        );
+        assert_eq!(
+            Version::from_date(GameMode::Taiko, 1_664_323_200),
+            Some(Version::Taiko2022)
+        );
+
+        // Last second before and first second of 2018-05-01
+        assert_eq!(
+            Version::from_date(GameMode::Mania, 1_525_132_799),
+            Some(Version::ManiaPpv1)
+        );
+        assert_eq!(
+            Version::from_date(GameMode::Mania, 1_525_132_800),
+            Some(Version::Mania2018)
+        );
+    }
+
+    #[cfg(all(feature = "osu-2015-april", feature = "osu-2022-september"))]
+    #[test]
+    fn parse_mods() {
+        use crate::util::mods::SCORE_V2;
+
+        assert_eq!(Version::Osu2015April.parse_mods("HDDT"), Ok(8 + 64));
+        assert_eq!(
+            Version::Osu2015April.parse_mods("HDV2"),
+            Err(Error::UnavailableMods(SCORE_V2))
+        );
+        assert_eq!(Version::Osu2022September.parse_mods("V2"), Ok(SCORE_V2));
+    }
+
+    #[cfg(feature = "all")]
+    #[test]
+    fn iterate() {
+        assert_eq!(Version::ALL.len(), 20);
+        assert_eq!(Version::osu_versions().count(), 12);
+        assert_eq!(Version::latest(GameMode::Taiko), Some(Version::Taiko2022));
+
+        let mut sorted = Version::ALL.to_vec();
+        sorted.sort();
+
+        assert!(sorted.windows(2).all(|w| w[0].date() <= w[1].date()));
+        assert!(Version::osu_versions()
+            .zip(Version::osu_versions().skip(1))
+            .all(|(a, b)| a < b));
+        assert!(Version::Osu2015April < Version::Mania2022);
+    }
+
+    #[cfg(feature = "all")]
+    #[test]
+    fn info() {
+        let info = Version::Osu2015February.info();
+
+        assert_eq!(info.start, "2015-02-01");
+        assert_eq!(info.end, Some("2015-04-01"));
+        assert_eq!(Version::Osu2022September.info().end, None);
+        assert_eq!(Version::Catch2020.info().map_modes.len(), 2);
+    }
+
+    #[test]
+    fn parse_module_names() {
+        for version in Version::ALL.iter().copied() {
+            assert_eq!(version.to_string().parse(), Ok(version));
+        }
+    }
+
+    #[cfg(feature = "all")]
+    #[test]
+    fn parse() {
+        assert_eq!("2021-07".parse(), Ok(Version::Osu2021July));
+        assert_eq!("july2021".parse(), Ok(Version::Osu2021July));
+        assert_eq!("OSU-2015-APRIL".parse(), Ok(Version::Osu2015April));
+        assert_eq!("2016-05-20".parse(), Ok(Version::Osu2015April));
+        assert_eq!("2021-07-01".parse(), Ok(Version::Osu2021January));
+        assert_eq!("2019".parse(), Ok(Version::Osu2019));
+        assert_eq!("taiko_2021".parse(), Ok(Version::Taiko2020));
+        assert_eq!(
+            "2010".parse::<Version>(),
+            Err(Error::UnknownVersion("2010".to_owned()))
+        );
+    }
+}